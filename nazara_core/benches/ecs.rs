@@ -0,0 +1,115 @@
+//! Benchmarks of the entity component system on 100k entities, run with `cargo bench -p
+//! nazara_core --bench ecs`
+//!
+//! Outside of `cargo bench`, each benchmark runs once to check it still works.
+
+use nazara_core::ecs::{Entity, With, World};
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const ENTITY_COUNT: usize = 100_000;
+const MEASURE_TIME: Duration = Duration::from_secs(2);
+
+struct Position(f32, f32);
+struct Velocity(f32, f32);
+struct Frozen;
+
+/// Spawn the entities, one out of ten being frozen
+fn populate(world: &mut World) -> Vec<Entity> {
+    (0..ENTITY_COUNT)
+        .map(|i| {
+            let entity = world.spawn((Position(0.0, 0.0), Velocity(1.0, i as f32)));
+            if i % 10 == 0 {
+                world.insert(entity, Frozen);
+            }
+            entity
+        })
+        .collect()
+}
+
+/// Run `routine` repeatedly for about [`MEASURE_TIME`] and print the mean time of an iteration
+fn bench<F: FnMut()>(name: &str, measure: bool, mut routine: F) {
+    let start = Instant::now();
+    let mut iterations = 0u32;
+    loop {
+        routine();
+        iterations += 1;
+
+        if !measure || start.elapsed() >= MEASURE_TIME {
+            break;
+        }
+    }
+
+    if measure {
+        println!(
+            "{:<20} {:>12.3?}/iter ({} iterations)",
+            name,
+            start.elapsed() / iterations,
+            iterations
+        );
+    }
+}
+
+fn spawn(measure: bool) {
+    bench("spawn", measure, || {
+        let mut world = World::new();
+        populate(&mut world);
+        assert_eq!(world.get_entity_count(), ENTITY_COUNT);
+        black_box(world);
+    });
+}
+
+fn query(measure: bool) {
+    let mut world = World::new();
+    populate(&mut world);
+
+    bench("query", measure, || {
+        world
+            .query::<(&mut Position, &Velocity)>()
+            .for_each(|_, (position, velocity)| {
+                position.0 += velocity.0;
+                position.1 += velocity.1;
+            });
+    });
+
+    let mut moved = 0;
+    world.query::<(&Position,)>().for_each(|_, (position,)| {
+        if position.0 > 0.0 {
+            moved += 1;
+        }
+    });
+    assert_eq!(moved, ENTITY_COUNT);
+}
+
+fn filtered_query(measure: bool) {
+    let mut world = World::new();
+    populate(&mut world);
+
+    bench("filtered query", measure, || {
+        let frozen = world.query_filtered::<(&Position,), With<Frozen>>().count();
+        assert_eq!(black_box(frozen), ENTITY_COUNT / 10);
+    });
+}
+
+fn spawn_and_despawn(measure: bool) {
+    bench("spawn and despawn", measure, || {
+        let mut world = World::new();
+        for entity in populate(&mut world) {
+            world.despawn(entity);
+        }
+        assert_eq!(world.get_entity_count(), 0);
+    });
+}
+
+fn main() {
+    // Cargo passes --bench to the benchmarks it runs through `cargo bench`
+    let measure = env::args().any(|arg| arg == "--bench");
+
+    spawn(measure);
+    query(measure);
+    filtered_query(measure);
+    spawn_and_despawn(measure);
+}
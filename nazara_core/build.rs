@@ -0,0 +1,17 @@
+use std::{env, process::Command};
+
+fn main() {
+    // Plugins share Rust types with the application, they are checked to be built by the
+    // same compiler
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| String::from("unknown rustc"));
+
+    println!("cargo:rustc-env=NAZARA_RUSTC_VERSION={}", version);
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
@@ -1,20 +1,340 @@
+mod plugin;
+
+use self::plugin::{LoadedPlugin, ModuleEntry};
+pub use self::plugin::{
+    Module, PluginDeclaration, PluginId, PluginRegistrar, CORE_VERSION, PLUGIN_API_VERSION,
+    RUSTC_VERSION,
+};
+
+use crate::{
+    config::Config,
+    ecs::{Schedule, System, World},
+    job::{JobCounter, JobSystem},
+    memory::{self, FrameAllocator, LeakReport, MemoryCategory, MemoryStats},
+    nz_profile_scope, nz_warning, profiler,
+    task::TaskScheduler,
+};
+
 /// Represents a Nazara application
 pub struct Application {
     should_close: bool,
+    config: Config,
+    task_scheduler: TaskScheduler,
+    job_system: Option<JobSystem>,
+    frame_jobs: JobCounter,
+    world: World,
+    schedule: Schedule,
+    modules: Vec<ModuleEntry>,
+    plugins: Vec<LoadedPlugin>,
+    next_plugin_id: u32,
+    frame_allocator: FrameAllocator,
+    memory_baseline: Vec<MemoryStats>,
 }
 
 impl Application {
     pub fn new() -> Application {
+        Application::with_config(Config::new())
+    }
+
+    /// Create an application configured by a [`Config`]
+    ///
+    /// The engine options are declared with their default values:
+    /// * `app.task_threads` - Number of threads of the task scheduler, 0 for one per core
+    /// * `app.job_threads` - Number of threads of the job system, 0 for one per core
+    /// * `app.profiler` - Enables the [`profiler`], which can also be enabled later
+    ///
+    /// ```
+    /// use nazara_core::application::Application;
+    /// use nazara_core::config::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.apply_args(vec![String::from("--app.task_threads=3")]).unwrap();
+    ///
+    /// let mut app = Application::with_config(config);
+    /// assert_eq!(app.get_task_scheduler().get_thread_count(), 3);
+    /// assert_eq!(app.get_config().get::<usize>("app.job_threads").unwrap(), 0);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `config` - Configuration, which may contain values for options not declared yet
+    pub fn with_config(mut config: Config) -> Application {
+        let memory_baseline = memory::get_all_stats();
+
+        config
+            .declare("app.task_threads", 0)
+            .with_range(0.0, 256.0)
+            .with_description("Number of threads of the task scheduler, 0 for one per core");
+        config
+            .declare("app.job_threads", 0)
+            .with_range(0.0, 256.0)
+            .with_description("Number of threads of the job system, 0 for one per core");
+        config
+            .declare("app.profiler", false)
+            .with_description("Records profiling scopes and per-frame statistics");
+
+        if config.get("app.profiler").unwrap_or(false) {
+            profiler::set_enabled(true);
+        }
+
         Application {
             should_close: false,
+            task_scheduler: TaskScheduler::new(config.get("app.task_threads").unwrap_or(0)),
+            config,
+            job_system: None,
+            frame_jobs: JobCounter::new(),
+            world: World::new(),
+            schedule: Schedule::new(),
+            modules: Vec::new(),
+            plugins: Vec::new(),
+            next_plugin_id: 0,
+            frame_allocator: FrameAllocator::default(),
+            memory_baseline,
         }
     }
 
+    /// Run one frame of the application, return false once it should close
+    ///
+    /// When the [`profiler`] is enabled, the statistics of the previous frame are collected
+    /// before the new frame begins.
+    ///
+    /// ```
+    /// use nazara_core::application::Application;
+    /// use nazara_core::config::Config;
+    /// use nazara_core::profiler;
+    ///
+    /// let mut config = Config::new();
+    /// config.apply_args(vec![String::from("--app.profiler=true")]).unwrap();
+    ///
+    /// let mut app = Application::with_config(config);
+    /// app.execute();
+    /// app.execute();
+    ///
+    /// let frame = profiler::get_last_frame().unwrap();
+    /// assert_eq!(frame.get_scope("Application::run_systems").unwrap().call_count, 1);
+    /// ```
     pub fn execute(&mut self) -> bool {
+        profiler::begin_frame();
+        self.frame_allocator.begin_frame();
+
+        if let Some(job_system) = &self.job_system {
+            nz_profile_scope!("Application::wait_frame_jobs", "core");
+            job_system.wait(&self.frame_jobs);
+        }
+
+        {
+            nz_profile_scope!("Application::process_completions", "core");
+            self.task_scheduler.process_completions();
+        }
+        {
+            nz_profile_scope!("Application::update_modules", "core");
+            self.update_modules();
+        }
+        {
+            nz_profile_scope!("Application::run_systems", "core");
+            self.schedule.run(&mut self.world);
+        }
+
         !self.should_close
     }
 
+    /// Request the application to close, the current frame is completed before [`execute`]
+    /// returns false
+    ///
+    /// ```
+    /// use nazara_core::application::Application;
+    ///
+    /// let mut app = Application::new();
+    /// assert!(!app.should_close());
+    ///
+    /// app.quit();
+    /// assert!(app.should_close());
+    /// assert!(!app.execute());
+    /// ```
+    ///
+    /// [`execute`]: Application::execute
+    pub fn quit(&mut self) {
+        self.should_close = true;
+    }
+
+    /// Return true once [`quit`](Application::quit) was called
+    pub fn should_close(&self) -> bool {
+        self.should_close
+    }
+
+    /// Return the [`TaskScheduler`] used to run background tasks
+    ///
+    /// Completion callbacks registered on it are run at the beginning of each frame.
+    ///
+    /// ```
+    /// use std::{cell::RefCell, path::Path, rc::Rc};
+    /// use nazara_core::application::Application;
+    /// use nazara_core::image::ImageLoader;
+    ///
+    /// let mut app = Application::new();
+    /// let scheduler = app.get_task_scheduler();
+    /// let handle = ImageLoader::load_from_file_async(scheduler, Path::new("./test_ressources/image.png"));
+    ///
+    /// let loaded = Rc::new(RefCell::new(None));
+    /// let output = loaded.clone();
+    /// scheduler.on_complete(&handle, move |image| *output.borrow_mut() = Some(image));
+    ///
+    /// while loaded.borrow().is_none() {
+    ///     app.execute();
+    /// }
+    ///
+    /// assert!(loaded.borrow_mut().take().unwrap().is_ok());
+    /// ```
+    pub fn get_task_scheduler(&mut self) -> &mut TaskScheduler {
+        &mut self.task_scheduler
+    }
+
+    /// Return the [`Config`] holding the options of the application and of the engine
+    pub fn get_config(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
+    /// Return the [`JobSystem`] used to run parallel jobs, its workers are started on first use
+    pub fn get_job_system(&mut self) -> &JobSystem {
+        let thread_count = self.config.get("app.job_threads").unwrap_or(0);
+        self.job_system
+            .get_or_insert_with(|| JobSystem::new(thread_count))
+    }
+
+    /// Queue a job which has to be finished before the next frame begins
+    ///
+    /// ```
+    /// use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+    /// use nazara_core::application::Application;
+    ///
+    /// let mut app = Application::new();
+    /// let updated = Arc::new(AtomicUsize::new(0));
+    ///
+    /// for _ in 0..100 {
+    ///     let updated = updated.clone();
+    ///     app.spawn_frame_job(move || {
+    ///         updated.fetch_add(1, Ordering::SeqCst);
+    ///     });
+    /// }
+    ///
+    /// app.execute();
+    /// assert_eq!(updated.load(Ordering::SeqCst), 100);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `job` - Function to run
+    pub fn spawn_frame_job<F>(&mut self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let counter = self.frame_jobs.clone();
+        self.get_job_system().spawn(&counter, job);
+    }
+
+    /// Return the [`World`] holding the entities of the application
+    pub fn get_world(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Add a system run on the world each frame, after the systems already added
+    ///
+    /// ```
+    /// use nazara_core::application::Application;
+    /// use nazara_core::ecs::{CommandBuffer, World};
+    ///
+    /// struct Frame(u32);
+    ///
+    /// let mut app = Application::new();
+    /// let entity = app.get_world().spawn((Frame(0),));
+    /// app.add_system(|world: &World, _: &mut CommandBuffer| {
+    ///     world.query::<(&mut Frame,)>().for_each(|_, (frame,)| frame.0 += 1);
+    /// });
+    ///
+    /// app.execute();
+    /// app.execute();
+    /// assert_eq!(app.get_world().get::<Frame>(entity).unwrap().0, 2);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `system` - System to add
+    pub fn add_system<S: System + 'static>(&mut self, system: S) {
+        self.schedule.add_system(system);
+    }
+
+    /// Return the [`FrameAllocator`] for temporary data, its allocations are freed when the
+    /// next frame begins
+    ///
+    /// ```
+    /// use nazara_core::application::Application;
+    ///
+    /// let mut app = Application::new();
+    /// app.execute();
+    /// let names = app.get_frame_allocator().alloc_str("visible entities");
+    /// assert_eq!(names, "visible entities");
+    ///
+    /// app.execute();
+    /// assert_eq!(app.get_frame_allocator().get_frame_size(), 0);
+    /// ```
+    pub fn get_frame_allocator(&self) -> &FrameAllocator {
+        &self.frame_allocator
+    }
+
+    /// Return the memory allocated since the application was created and not freed yet
+    ///
+    /// Only filled when the [`TrackingAllocator`](crate::memory::TrackingAllocator) is the global
+    /// allocator. When the application is dropped, the leaks of the categories other than
+    /// [`MemoryCategory::GENERAL`] are logged as warnings.
+    ///
+    /// ```
+    /// use nazara_core::application::Application;
+    /// use nazara_core::enums::PixelFormatType;
+    /// use nazara_core::image::Image;
+    /// use nazara_core::memory::{MemoryCategory, TrackingAllocator};
+    ///
+    /// #[global_allocator]
+    /// static ALLOCATOR: TrackingAllocator = TrackingAllocator::new();
+    ///
+    /// fn main() {
+    ///     let app = Application::new();
+    ///     let image = Image::new_2d(PixelFormatType::RGBA8, 4, 4);
+    ///
+    ///     let report = app.get_leak_report().ignore(MemoryCategory::GENERAL);
+    ///     assert_eq!(report.get_leaks().len(), 1);
+    ///     assert!(report.get_leaks()[0].bytes >= 4 * 4 * 4);
+    ///
+    ///     drop(image);
+    ///     assert!(app.get_leak_report().ignore(MemoryCategory::GENERAL).is_empty());
+    /// }
+    /// ```
+    pub fn get_leak_report(&self) -> LeakReport {
+        LeakReport::since(&self.memory_baseline)
+    }
+
     pub fn run(mut self) {
         while self.execute() {}
     }
 }
+
+impl Drop for Application {
+    fn drop(&mut self) {
+        self.unload_all();
+
+        if memory::is_tracking() {
+            // The engine data is released first, general allocations are still held by the
+            // application itself at this point
+            self.world = World::new();
+            self.schedule = Schedule::new();
+            self.job_system = None;
+
+            let report = self.get_leak_report().ignore(MemoryCategory::GENERAL);
+            if !report.is_empty() {
+                nz_warning!("memory", "{}", report);
+            }
+        }
+    }
+}
+
+impl Default for Application {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    ffi::CStr,
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::Arc,
+};
+
+use libloading::Library;
+
+use super::Application;
+use crate::{
+    errors::{NazaraError, NazaraResult, PluginError},
+    image::{FormatLoaderId, Image, ImageFormatLoader, ImageLoader},
+    nz_info,
+};
+
+/// Version of the plugin interface, plugins built against another version are rejected
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// Version of nazara_core, plugins must be built against the same version as the application
+pub const CORE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the compiler which built nazara_core, null-terminated, plugins must be built by
+/// the same compiler as the application
+pub const RUSTC_VERSION: &str = concat!(env!("NAZARA_RUSTC_VERSION"), "\0");
+
+/// Name of the symbol exported by [`nazara_plugin!`](crate::nazara_plugin)
+const DECLARATION_SYMBOL: &[u8] = b"nazara_plugin_declaration\0";
+
+/// Engine feature updated each frame by an [`Application`]
+pub trait Module {
+    /// Return the name of the module
+    fn get_name(&self) -> &str;
+
+    /// Called at each frame by [`Application::execute`]
+    fn update(&mut self) {}
+
+    /// Called before the module is dropped, when its plugin is unloaded or the application
+    /// is dropped
+    fn shutdown(&mut self) {}
+}
+
+/// Interface given to plugins to register their features
+pub trait PluginRegistrar {
+    /// Register a loader for additional image formats, unregistered when the plugin is unloaded
+    fn register_image_loader(&mut self, loader: Box<dyn ImageFormatLoader>);
+
+    /// Register a module, dropped when the plugin is unloaded
+    fn register_module(&mut self, module: Box<dyn Module>);
+}
+
+/// Entry point of a plugin, exported by [`nazara_plugin!`](crate::nazara_plugin)
+///
+/// Only the first two fields have a stable layout, the other ones are read once the versions
+/// are known to match.
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub api_version: u32,
+    /// Null-terminated [`RUSTC_VERSION`] of the plugin
+    pub rustc_version: *const c_char,
+    pub core_version: &'static str,
+    pub name: &'static str,
+    pub register: fn(&mut dyn PluginRegistrar),
+}
+
+// Safety: the version string is a static which is never written
+unsafe impl Sync for PluginDeclaration {}
+
+/// Declare the entry point of a plugin built as a `cdylib`
+///
+/// Plugins share Rust types with the application, so both have to be built by the same
+/// compiler against the same version of nazara_core.
+///
+/// ```ignore
+/// use nazara_core::application::{Module, PluginRegistrar};
+///
+/// struct Telemetry;
+///
+/// impl Module for Telemetry {
+///     fn get_name(&self) -> &str {
+///         "telemetry"
+///     }
+/// }
+///
+/// fn register(registrar: &mut dyn PluginRegistrar) {
+///     registrar.register_module(Box::new(Telemetry));
+/// }
+///
+/// nazara_core::nazara_plugin!("telemetry", register);
+/// ```
+#[macro_export]
+macro_rules! nazara_plugin {
+    ($name:expr, $register:expr) => {
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static nazara_plugin_declaration: $crate::application::PluginDeclaration =
+            $crate::application::PluginDeclaration {
+                api_version: $crate::application::PLUGIN_API_VERSION,
+                rustc_version: $crate::application::RUSTC_VERSION.as_ptr() as *const _,
+                core_version: $crate::application::CORE_VERSION,
+                name: $name,
+                register: $register,
+            };
+    };
+}
+
+/// Identifier of a plugin loaded by an [`Application`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PluginId(u32);
+
+pub(crate) struct ModuleEntry {
+    pub(crate) module: Box<dyn Module>,
+    pub(crate) plugin: Option<PluginId>,
+}
+
+pub(crate) struct LoadedPlugin {
+    id: PluginId,
+    name: String,
+    image_loaders: Vec<FormatLoaderId>,
+    // Shared with the image loaders of the plugin, which can outlive it
+    library: Arc<Library>,
+}
+
+/// Image loader of a plugin, keeping its library loaded as long as it is used
+struct PluginImageLoader {
+    // Dropped before the library holding its code
+    loader: Box<dyn ImageFormatLoader>,
+    _library: Arc<Library>,
+}
+
+impl ImageFormatLoader for PluginImageLoader {
+    fn supports_extension(&self, extension: &str) -> bool {
+        self.loader.supports_extension(extension)
+    }
+
+    fn load(&self, data: &[u8]) -> NazaraResult<Image> {
+        self.loader.load(data)
+    }
+}
+
+struct HostRegistrar {
+    library: Arc<Library>,
+    modules: Vec<Box<dyn Module>>,
+    image_loaders: Vec<FormatLoaderId>,
+}
+
+impl PluginRegistrar for HostRegistrar {
+    fn register_image_loader(&mut self, loader: Box<dyn ImageFormatLoader>) {
+        let id = ImageLoader::register_format_loader(PluginImageLoader {
+            loader,
+            _library: self.library.clone(),
+        });
+        self.image_loaders.push(id);
+    }
+
+    fn register_module(&mut self, module: Box<dyn Module>) {
+        self.modules.push(module);
+    }
+}
+
+impl Application {
+    /// Load a plugin from a dynamic library and register its features
+    ///
+    /// # Arguments
+    /// * `path` - Path of the library (`.so`, `.dll` or `.dylib`)
+    pub fn load_plugin(&mut self, path: &Path) -> NazaraResult<PluginId> {
+        // Loading a library runs its initialization code, which is trusted like any plugin code
+        let library =
+            unsafe { Library::new(path) }.map_err(|e| NazaraError::from(PluginError::from(e)))?;
+
+        // Safety: the declaration lives as long as the library, and its layout starts with the
+        // fields checked before reading the other ones
+        let declaration = unsafe {
+            let symbol = library
+                .get::<*const PluginDeclaration>(DECLARATION_SYMBOL)
+                .map_err(|_| NazaraError::from(PluginError::MissingEntryPoint))?;
+            &**symbol
+        };
+
+        if declaration.api_version != PLUGIN_API_VERSION {
+            return Err(NazaraError::from(PluginError::IncompatibleVersion(
+                format!(
+                    "plugin API version {}, expected {}",
+                    declaration.api_version, PLUGIN_API_VERSION
+                ),
+            )));
+        }
+        // Safety: plugins of this API version export a null-terminated compiler version
+        let rustc_version = unsafe { CStr::from_ptr(declaration.rustc_version) };
+        if rustc_version.to_bytes_with_nul() != RUSTC_VERSION.as_bytes() {
+            return Err(NazaraError::from(PluginError::IncompatibleVersion(
+                format!(
+                    "built by {}, expected {}",
+                    rustc_version.to_string_lossy(),
+                    RUSTC_VERSION.trim_end_matches('\0')
+                ),
+            )));
+        }
+        if declaration.core_version != CORE_VERSION {
+            return Err(NazaraError::from(PluginError::IncompatibleVersion(
+                format!(
+                    "built with nazara_core {}, expected {}",
+                    declaration.core_version, CORE_VERSION
+                ),
+            )));
+        }
+
+        let name = declaration.name.to_string();
+        let register = declaration.register;
+        let library = Arc::new(library);
+        let mut registrar = HostRegistrar {
+            library: library.clone(),
+            modules: Vec::new(),
+            image_loaders: Vec::new(),
+        };
+        if panic::catch_unwind(AssertUnwindSafe(|| register(&mut registrar))).is_err() {
+            // Features registered before the panic are removed before unloading the library
+            for id in registrar.image_loaders {
+                ImageLoader::unregister_format_loader(id);
+            }
+            drop(registrar.modules);
+
+            return Err(NazaraError::from(PluginError::LoadError(format!(
+                "{} panicked while registering",
+                name
+            ))));
+        }
+
+        let id = PluginId(self.next_plugin_id);
+        self.next_plugin_id += 1;
+
+        nz_info!(
+            "plugin",
+            "Loaded plugin {} ({} modules, {} image loaders)",
+            name,
+            registrar.modules.len(),
+            registrar.image_loaders.len()
+        );
+
+        self.modules
+            .extend(registrar.modules.into_iter().map(|module| ModuleEntry {
+                module,
+                plugin: Some(id),
+            }));
+        self.plugins.push(LoadedPlugin {
+            id,
+            name,
+            image_loaders: registrar.image_loaders,
+            library,
+        });
+
+        Ok(id)
+    }
+
+    /// Shut down the modules of a plugin, unregister its features and unload its library
+    ///
+    /// # Arguments
+    /// * `id` - Plugin to unload
+    pub fn unload_plugin(&mut self, id: PluginId) -> NazaraResult<()> {
+        let index = self
+            .plugins
+            .iter()
+            .position(|p| p.id == id)
+            .ok_or_else(|| NazaraError::from(PluginError::NotLoaded))?;
+        let plugin = self.plugins.remove(index);
+
+        let (plugin_modules, modules) = self
+            .modules
+            .drain(..)
+            .partition(|entry| entry.plugin == Some(id));
+        self.modules = modules;
+        Application::shutdown_modules(plugin_modules);
+
+        for loader in &plugin.image_loaders {
+            ImageLoader::unregister_format_loader(*loader);
+        }
+
+        // Loaders still used, by a loading task for example, keep the library loaded
+        nz_info!("plugin", "Unloaded plugin {}", plugin.name);
+        drop(plugin.library);
+
+        Ok(())
+    }
+
+    /// Return the name of a loaded plugin
+    ///
+    /// # Arguments
+    /// * `id` - Plugin to get the name of
+    pub fn get_plugin_name(&self, id: PluginId) -> Option<&str> {
+        self.plugins
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.name.as_str())
+    }
+
+    /// Return the loaded plugins, in loading order
+    pub fn get_plugins(&self) -> Vec<PluginId> {
+        self.plugins.iter().map(|p| p.id).collect()
+    }
+
+    /// Add a module updated at each frame
+    ///
+    /// ```
+    /// use std::{cell::Cell, rc::Rc};
+    /// use nazara_core::application::{Application, Module};
+    ///
+    /// struct FrameCounter(Rc<Cell<u32>>);
+    ///
+    /// impl Module for FrameCounter {
+    ///     fn get_name(&self) -> &str {
+    ///         "frame counter"
+    ///     }
+    ///
+    ///     fn update(&mut self) {
+    ///         self.0.set(self.0.get() + 1);
+    ///     }
+    /// }
+    ///
+    /// let frames = Rc::new(Cell::new(0));
+    /// let mut app = Application::new();
+    /// app.add_module(FrameCounter(frames.clone()));
+    ///
+    /// app.execute();
+    /// app.execute();
+    /// assert_eq!(frames.get(), 2);
+    /// assert_eq!(app.get_module_names(), vec!["frame counter"]);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `module` - Module to add
+    pub fn add_module<M: Module + 'static>(&mut self, module: M) {
+        self.modules.push(ModuleEntry {
+            module: Box::new(module),
+            plugin: None,
+        });
+    }
+
+    /// Return the names of the modules, in update order
+    pub fn get_module_names(&self) -> Vec<&str> {
+        self.modules.iter().map(|m| m.module.get_name()).collect()
+    }
+
+    pub(crate) fn update_modules(&mut self) {
+        for entry in &mut self.modules {
+            entry.module.update();
+        }
+    }
+
+    pub(crate) fn shutdown_modules(modules: Vec<ModuleEntry>) {
+        for mut entry in modules.into_iter().rev() {
+            entry.module.shutdown();
+        }
+    }
+
+    /// Unload all plugins, modules being shut down in reverse order of registration
+    pub(crate) fn unload_all(&mut self) {
+        Application::shutdown_modules(std::mem::take(&mut self.modules));
+
+        while let Some(plugin) = self.plugins.last() {
+            let id = plugin.id;
+            let _ = self.unload_plugin(id);
+        }
+    }
+}
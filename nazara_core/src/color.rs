@@ -1,4 +1,26 @@
 /// Represent a rgba color
+///
+/// With the `serde` feature, colors and the other engine types (enums, transforms, bounding
+/// volumes, images) can be saved to any serde format.
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use nazara_core::color::Color;
+/// use nazara_core::scene::Transform;
+///
+/// let color = Color { r: 1.0, g: 0.5, b: 0.0, a: 1.0 };
+/// let json = serde_json::to_string(&color).unwrap();
+/// assert_eq!(json, r#"{"r":1.0,"g":0.5,"b":0.0,"a":1.0}"#);
+/// assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+///
+/// let transform = Transform::identity();
+/// let json = serde_json::to_string(&transform).unwrap();
+/// assert_eq!(serde_json::from_str::<Transform>(&json).unwrap(), transform);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -0,0 +1,406 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{
+    errors::{ConfigError, NazaraError, NazaraResult},
+    nz_warning,
+};
+
+mod format;
+mod value;
+
+pub use self::value::{ConfigType, ConfigValue};
+
+/// Declared configuration option
+pub struct ConfigOption {
+    default: ConfigValue,
+    value: ConfigValue,
+    range: Option<(f64, f64)>,
+    description: String,
+}
+
+impl ConfigOption {
+    /// Restricts the values of a numeric option, values already out of range are replaced by
+    /// the default value
+    ///
+    /// # Arguments
+    /// * `min` - Minimum value, included
+    /// * `max` - Maximum value, included
+    pub fn with_range(&mut self, min: f64, max: f64) -> &mut Self {
+        self.range = Some((min, max));
+        if !self.is_in_range(&self.value) {
+            nz_warning!(
+                "config",
+                "{} is out of the [{}, {}] range, using the default value",
+                self.value,
+                min,
+                max
+            );
+            self.value = self.default.clone();
+        }
+
+        self
+    }
+
+    /// Sets the text describing the option
+    ///
+    /// # Arguments
+    /// * `description` - Description of the option
+    pub fn with_description<T: Into<String>>(&mut self, description: T) -> &mut Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Returns the current value
+    pub fn get_value(&self) -> &ConfigValue {
+        &self.value
+    }
+
+    /// Returns the default value
+    pub fn get_default(&self) -> &ConfigValue {
+        &self.default
+    }
+
+    /// Returns the allowed range of a numeric option
+    pub fn get_range(&self) -> Option<(f64, f64)> {
+        self.range
+    }
+
+    /// Returns the text describing the option
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    fn is_in_range(&self, value: &ConfigValue) -> bool {
+        match (self.range, value.as_f64()) {
+            (Some((min, max)), Some(value)) => value >= min && value <= max,
+            _ => true,
+        }
+    }
+}
+
+/// Set of typed options with default values, loaded from files and command-line arguments
+///
+/// Options are identified by dotted keys (`window.width`), the part before the last dot being
+/// the section in INI files and the table in TOML files. Values read for options which are not
+/// declared yet are kept until they are, so configuration can be loaded before every subsystem
+/// declared its options.
+///
+/// ```
+/// use nazara_core::config::Config;
+///
+/// let mut config = Config::new();
+/// config.load_from_ini_str("[audio]\nvolume = 0.5\n").unwrap();
+///
+/// config.declare("audio.volume", 1.0).with_range(0.0, 1.0);
+/// config.declare("window.width", 800).with_range(1.0, 16384.0);
+/// config.declare("window.vsync", true);
+///
+/// let args = ["game", "--window.width=1280", "--window.vsync=false", "level1"];
+/// let remaining = config.apply_args(args.iter().map(|s| s.to_string())).unwrap();
+/// assert_eq!(remaining, vec!["game", "level1"]);
+///
+/// assert_eq!(config.get::<f32>("audio.volume").unwrap(), 0.5);
+/// assert_eq!(config.get::<u32>("window.width").unwrap(), 1280);
+/// assert_eq!(config.get::<bool>("window.vsync").unwrap(), false);
+///
+/// assert!(config.set("audio.volume", 2.0).is_err());
+/// assert!(config.set("window.width", "wide").is_err());
+/// assert!(config.get::<String>("window.width").is_err());
+/// ```
+#[derive(Default)]
+pub struct Config {
+    options: BTreeMap<String, ConfigOption>,
+    pending: BTreeMap<String, ConfigValue>,
+}
+
+impl Config {
+    /// Creates a new configuration without any option
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    /// Declares an option, its type being the type of the default value
+    ///
+    /// If a value was loaded for this key before, it becomes the current value. Declaring an
+    /// option again returns the existing one.
+    ///
+    /// # Arguments
+    /// * `key` - Dotted name of the option
+    /// * `default` - Default value
+    pub fn declare<T: Into<ConfigValue>>(&mut self, key: &str, default: T) -> &mut ConfigOption {
+        let pending = self.pending.remove(key);
+        self.options.entry(key.to_string()).or_insert_with(|| {
+            let default = default.into();
+            let value = match pending {
+                Some(value) => value.convert_to(&default).unwrap_or_else(|| {
+                    nz_warning!(
+                        "config",
+                        "{} is not a valid {} for option {}, using the default value",
+                        value,
+                        default.get_type_name(),
+                        key
+                    );
+                    default.clone()
+                }),
+                None => default.clone(),
+            };
+
+            ConfigOption {
+                default,
+                value,
+                range: None,
+                description: String::new(),
+            }
+        })
+    }
+
+    /// Returns true if an option was declared with this key
+    ///
+    /// # Arguments
+    /// * `key` - Dotted name of the option
+    pub fn is_declared(&self, key: &str) -> bool {
+        self.options.contains_key(key)
+    }
+
+    /// Returns a declared option
+    ///
+    /// # Arguments
+    /// * `key` - Dotted name of the option
+    pub fn get_option(&self, key: &str) -> Option<&ConfigOption> {
+        self.options.get(key)
+    }
+
+    /// Returns the keys of the declared options, sorted
+    pub fn get_keys(&self) -> Vec<&str> {
+        self.options.keys().map(|key| key.as_str()).collect()
+    }
+
+    /// Returns the current value of an option
+    ///
+    /// # Arguments
+    /// * `key` - Dotted name of the option
+    pub fn get<T: ConfigType>(&self, key: &str) -> NazaraResult<T> {
+        let option = self.option(key)?;
+        T::from_value(&option.value)
+            .ok_or_else(|| NazaraError::from(ConfigError::InvalidValue(key.to_string())))
+    }
+
+    /// Changes the value of an option, checking its type and range
+    ///
+    /// Strings are parsed when the option has another type.
+    ///
+    /// # Arguments
+    /// * `key` - Dotted name of the option
+    /// * `value` - New value
+    pub fn set<T: Into<ConfigValue>>(&mut self, key: &str, value: T) -> NazaraResult<()> {
+        let option = self
+            .options
+            .get_mut(key)
+            .ok_or_else(|| NazaraError::from(ConfigError::UnknownOption(key.to_string())))?;
+
+        let value = value
+            .into()
+            .convert_to(&option.default)
+            .ok_or_else(|| NazaraError::from(ConfigError::InvalidValue(key.to_string())))?;
+        if !option.is_in_range(&value) {
+            return Err(NazaraError::from(ConfigError::OutOfRange(key.to_string())));
+        }
+
+        option.value = value;
+        Ok(())
+    }
+
+    /// Restores the default value of an option
+    ///
+    /// # Arguments
+    /// * `key` - Dotted name of the option
+    pub fn reset(&mut self, key: &str) -> NazaraResult<()> {
+        let option = self
+            .options
+            .get_mut(key)
+            .ok_or_else(|| NazaraError::from(ConfigError::UnknownOption(key.to_string())))?;
+        option.value = option.default.clone();
+
+        Ok(())
+    }
+
+    /// Applies `--key=value` arguments, returns the other arguments
+    ///
+    /// `--key` alone sets a boolean option to true.
+    ///
+    /// # Arguments
+    /// * `args` - Command-line arguments, usually [`std::env::args`]
+    pub fn apply_args<I: IntoIterator<Item = String>>(
+        &mut self,
+        args: I,
+    ) -> NazaraResult<Vec<String>> {
+        let mut remaining = Vec::new();
+        for arg in args {
+            let option = match arg.strip_prefix("--") {
+                Some(option) if !option.is_empty() => option,
+                _ => {
+                    remaining.push(arg);
+                    continue;
+                }
+            };
+
+            let (key, value) = option.split_once('=').unwrap_or((option, "true"));
+            self.load_value(key, ConfigValue::String(value.to_string()))?;
+        }
+
+        Ok(remaining)
+    }
+
+    /// Loads values from a TOML or INI file, depending on its extension (`.toml`, `.ini` or
+    /// `.cfg`)
+    ///
+    /// # Arguments
+    /// * `path` - Path of the file
+    pub fn load_from_file(&mut self, path: &Path) -> NazaraResult<()> {
+        let content =
+            fs::read_to_string(path).map_err(|e| NazaraError::from(ConfigError::from(e)))?;
+
+        match Config::get_extension(path)?.as_str() {
+            "toml" => self.load_from_toml_str(&content),
+            _ => self.load_from_ini_str(&content),
+        }
+    }
+
+    /// Loads values from a TOML document
+    ///
+    /// ```
+    /// use nazara_core::config::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.declare("language", "en");
+    /// config.declare("window.width", 800);
+    /// config.declare("window.fullscreen", false);
+    ///
+    /// config
+    ///     .load_from_toml_str("language = \"fr\"\n[window]\nwidth = 1920\nfullscreen = true\n")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     config.to_ini_string(),
+    ///     "language = fr\n\n[window]\nfullscreen = true\nwidth = 1920\n"
+    /// );
+    ///
+    /// assert!(config.load_from_toml_str("[window]\nwidth = \"wide\"\n").is_err());
+    /// assert!(config.load_from_toml_str("[window\n").is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `content` - TOML document
+    pub fn load_from_toml_str(&mut self, content: &str) -> NazaraResult<()> {
+        for (key, value) in format::parse_toml(content)? {
+            self.load_value(&key, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads values from an INI document
+    ///
+    /// # Arguments
+    /// * `content` - INI document
+    pub fn load_from_ini_str(&mut self, content: &str) -> NazaraResult<()> {
+        for (key, value) in format::parse_ini(content)? {
+            self.load_value(&key, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves all values to a TOML or INI file, depending on its extension (`.toml`, `.ini` or
+    /// `.cfg`)
+    ///
+    /// ```
+    /// use nazara_core::config::Config;
+    ///
+    /// let path = std::env::temp_dir().join("nazara_config_doctest.toml");
+    ///
+    /// let mut config = Config::new();
+    /// config.declare("window.title", "Nazara");
+    /// config.declare("window.width", 800);
+    /// config.declare("audio.volume", 1.0);
+    /// config.set("window.width", 1024).unwrap();
+    /// config.save_to_file(&path).unwrap();
+    ///
+    /// let mut loaded = Config::new();
+    /// loaded.load_from_file(&path).unwrap();
+    /// loaded.declare("window.width", 800);
+    /// loaded.declare("window.title", "");
+    /// assert_eq!(loaded.get::<u32>("window.width").unwrap(), 1024);
+    /// assert_eq!(loaded.get::<String>("window.title").unwrap(), "Nazara");
+    ///
+    /// std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Arguments
+    /// * `path` - Path of the file
+    pub fn save_to_file(&self, path: &Path) -> NazaraResult<()> {
+        let content = match Config::get_extension(path)?.as_str() {
+            "toml" => self.to_toml_string()?,
+            _ => self.to_ini_string(),
+        };
+
+        fs::write(path, content).map_err(|e| NazaraError::from(ConfigError::from(e)))
+    }
+
+    /// Returns all values as a TOML document
+    pub fn to_toml_string(&self) -> NazaraResult<String> {
+        format::write_toml(&self.get_values())
+    }
+
+    /// Returns all values as an INI document
+    pub fn to_ini_string(&self) -> String {
+        format::write_ini(&self.get_values())
+    }
+
+    /// Returns the values of the declared options and the values loaded for undeclared keys,
+    /// sorted by key
+    fn get_values(&self) -> Vec<(&str, &ConfigValue)> {
+        let mut values: Vec<(&str, &ConfigValue)> = self
+            .options
+            .iter()
+            .map(|(key, option)| (key.as_str(), &option.value))
+            .chain(
+                self.pending
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value)),
+            )
+            .collect();
+        values.sort_by(|a, b| a.0.cmp(b.0));
+
+        values
+    }
+
+    fn load_value(&mut self, key: &str, value: ConfigValue) -> NazaraResult<()> {
+        if self.options.contains_key(key) {
+            self.set(key, value)
+        } else {
+            self.pending.insert(key.to_string(), value);
+            Ok(())
+        }
+    }
+
+    fn option(&self, key: &str) -> NazaraResult<&ConfigOption> {
+        self.options
+            .get(key)
+            .ok_or_else(|| NazaraError::from(ConfigError::UnknownOption(key.to_string())))
+    }
+
+    fn get_extension(path: &Path) -> NazaraResult<String> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "toml" | "ini" | "cfg" => Ok(extension),
+            _ => Err(NazaraError::from(ConfigError::UnsupportedFormat(
+                path.display().to_string(),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::Write;
+
+use toml::{value::Table, Value};
+
+use super::value::ConfigValue;
+use crate::errors::{ConfigError, NazaraError, NazaraResult};
+
+/// Parses an INI document, keys of sections being prefixed by the section name
+/// (`width` in `[window]` becomes `window.width`)
+pub(crate) fn parse_ini(content: &str) -> NazaraResult<Vec<(String, ConfigValue)>> {
+    let mut section = String::new();
+    let mut values = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: &str| {
+            NazaraError::from(ConfigError::ParseError(format!(
+                "line {}: {}",
+                index + 1,
+                message
+            )))
+        };
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| error("unterminated section"))?;
+            section = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected key = value"))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(error("empty key"));
+        }
+
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+
+        let key = if section.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", section, key)
+        };
+        values.push((key, ConfigValue::String(value.to_string())));
+    }
+
+    Ok(values)
+}
+
+/// Parses a TOML document, keys of tables being prefixed by the table names
+pub(crate) fn parse_toml(content: &str) -> NazaraResult<Vec<(String, ConfigValue)>> {
+    let table: Table =
+        toml::from_str(content).map_err(|e| NazaraError::from(ConfigError::from(e)))?;
+
+    let mut values = Vec::new();
+    flatten_table(&table, "", &mut values)?;
+
+    Ok(values)
+}
+
+fn flatten_table(
+    table: &Table,
+    prefix: &str,
+    values: &mut Vec<(String, ConfigValue)>,
+) -> NazaraResult<()> {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+
+        let value = match *value {
+            Value::Boolean(v) => ConfigValue::Bool(v),
+            Value::Integer(v) => ConfigValue::Integer(v),
+            Value::Float(v) => ConfigValue::Float(v),
+            Value::String(ref v) => ConfigValue::String(v.clone()),
+            Value::Datetime(ref v) => ConfigValue::String(v.to_string()),
+            Value::Table(ref table) => {
+                flatten_table(table, &key, values)?;
+                continue;
+            }
+            Value::Array(_) => {
+                return Err(NazaraError::from(ConfigError::ParseError(format!(
+                    "{}: arrays are not supported",
+                    key
+                ))))
+            }
+        };
+        values.push((key, value));
+    }
+
+    Ok(())
+}
+
+/// Splits a key into its section and name (`window.width` gives `window` and `width`)
+fn split_key(key: &str) -> (&str, &str) {
+    match key.rfind('.') {
+        Some(index) => (&key[..index], &key[index + 1..]),
+        None => ("", key),
+    }
+}
+
+/// Writes sorted values to an INI document
+pub(crate) fn write_ini(values: &[(&str, &ConfigValue)]) -> String {
+    let mut content = String::new();
+    let mut current_section = "";
+
+    for &(key, value) in values.iter().filter(|(key, _)| !key.contains('.')) {
+        writeln!(content, "{} = {}", key, value).unwrap();
+    }
+
+    for &(key, value) in values.iter().filter(|(key, _)| key.contains('.')) {
+        let (section, name) = split_key(key);
+        if section != current_section {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            writeln!(content, "[{}]", section).unwrap();
+            current_section = section;
+        }
+        writeln!(content, "{} = {}", name, value).unwrap();
+    }
+
+    content
+}
+
+/// Writes values to a TOML document, dotted keys becoming nested tables
+pub(crate) fn write_toml(values: &[(&str, &ConfigValue)]) -> NazaraResult<String> {
+    let mut root = Table::new();
+
+    for &(key, value) in values {
+        let invalid = || NazaraError::from(ConfigError::InvalidValue(key.to_string()));
+
+        let mut components: Vec<&str> = key.split('.').collect();
+        let name = components.pop().unwrap();
+        let mut table = &mut root;
+        for component in components {
+            table = match table
+                .entry(component.to_string())
+                .or_insert_with(|| Value::Table(Table::new()))
+            {
+                Value::Table(ref mut table) => table,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let value = match *value {
+            ConfigValue::Bool(v) => Value::Boolean(v),
+            ConfigValue::Integer(v) => Value::Integer(v),
+            ConfigValue::Float(v) => Value::Float(v),
+            ConfigValue::String(ref v) => Value::String(v.clone()),
+        };
+        if table.insert(name.to_string(), value).is_some() {
+            return Err(invalid());
+        }
+    }
+
+    toml::to_string(&root).map_err(|e| NazaraError::from(ConfigError::ParseError(e.to_string())))
+}
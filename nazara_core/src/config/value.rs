@@ -0,0 +1,162 @@
+use std::{convert::TryFrom, fmt};
+
+/// Value of a configuration option
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl ConfigValue {
+    /// Returns the name of the type of the value
+    pub fn get_type_name(&self) -> &'static str {
+        match *self {
+            ConfigValue::Bool(_) => "boolean",
+            ConfigValue::Integer(_) => "integer",
+            ConfigValue::Float(_) => "float",
+            ConfigValue::String(_) => "string",
+        }
+    }
+
+    /// Returns the value as a number, `None` for booleans and strings
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            ConfigValue::Integer(value) => Some(value as f64),
+            ConfigValue::Float(value) => Some(value),
+            ConfigValue::Bool(_) | ConfigValue::String(_) => None,
+        }
+    }
+
+    /// Converts a value to the type of another one, parsing strings as needed
+    ///
+    /// # Arguments
+    /// * `kind` - Value having the target type
+    pub(crate) fn convert_to(&self, kind: &ConfigValue) -> Option<ConfigValue> {
+        match (self, kind) {
+            (ConfigValue::Bool(v), ConfigValue::Bool(_)) => Some(ConfigValue::Bool(*v)),
+            (ConfigValue::Integer(v), ConfigValue::Integer(_)) => Some(ConfigValue::Integer(*v)),
+            (ConfigValue::Integer(v), ConfigValue::Float(_)) => Some(ConfigValue::Float(*v as f64)),
+            (ConfigValue::Float(v), ConfigValue::Float(_)) => Some(ConfigValue::Float(*v)),
+            (ConfigValue::Float(v), ConfigValue::Integer(_)) if v.fract() == 0.0 => {
+                Some(ConfigValue::Integer(*v as i64))
+            }
+            (ConfigValue::String(s), ConfigValue::Bool(_)) => {
+                match s.trim().to_ascii_lowercase().as_str() {
+                    "true" | "yes" | "on" | "1" => Some(ConfigValue::Bool(true)),
+                    "false" | "no" | "off" | "0" => Some(ConfigValue::Bool(false)),
+                    _ => None,
+                }
+            }
+            (ConfigValue::String(s), ConfigValue::Integer(_)) => {
+                s.trim().parse().ok().map(ConfigValue::Integer)
+            }
+            (ConfigValue::String(s), ConfigValue::Float(_)) => {
+                s.trim().parse().ok().map(ConfigValue::Float)
+            }
+            (value, ConfigValue::String(_)) => Some(ConfigValue::String(value.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ConfigValue::Bool(value) => value.fmt(fmt),
+            ConfigValue::Integer(value) => value.fmt(fmt),
+            ConfigValue::Float(value) => fmt::Debug::fmt(&value, fmt),
+            ConfigValue::String(ref value) => value.fmt(fmt),
+        }
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        ConfigValue::Bool(value)
+    }
+}
+
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        ConfigValue::String(value.to_string())
+    }
+}
+
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        ConfigValue::String(value)
+    }
+}
+
+impl From<f32> for ConfigValue {
+    fn from(value: f32) -> Self {
+        ConfigValue::Float(value as f64)
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(value: f64) -> Self {
+        ConfigValue::Float(value)
+    }
+}
+
+/// Rust type an option can be read as
+pub trait ConfigType: Sized {
+    /// Converts a [`ConfigValue`], `None` if it has another type or doesn't fit
+    fn from_value(value: &ConfigValue) -> Option<Self>;
+}
+
+impl ConfigType for bool {
+    fn from_value(value: &ConfigValue) -> Option<bool> {
+        match *value {
+            ConfigValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ConfigType for String {
+    fn from_value(value: &ConfigValue) -> Option<String> {
+        match *value {
+            ConfigValue::String(ref value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl ConfigType for f64 {
+    fn from_value(value: &ConfigValue) -> Option<f64> {
+        value.as_f64()
+    }
+}
+
+impl ConfigType for f32 {
+    fn from_value(value: &ConfigValue) -> Option<f32> {
+        value.as_f64().map(|value| value as f32)
+    }
+}
+
+macro_rules! impl_integer {
+    ($($type:ty),+) => {
+        $(
+            impl From<$type> for ConfigValue {
+                fn from(value: $type) -> Self {
+                    ConfigValue::Integer(i64::try_from(value).unwrap_or(i64::MAX))
+                }
+            }
+
+            impl ConfigType for $type {
+                fn from_value(value: &ConfigValue) -> Option<$type> {
+                    match *value {
+                        ConfigValue::Integer(value) => <$type>::try_from(value).ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
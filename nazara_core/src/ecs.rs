@@ -0,0 +1,14 @@
+mod commands;
+mod entity;
+mod query;
+mod schedule;
+mod storage;
+mod world;
+
+pub use self::{
+    commands::CommandBuffer,
+    entity::Entity,
+    query::{Changed, Fetch, Query, QueryFilter, With, Without},
+    schedule::{Schedule, System},
+    world::{Bundle, World},
+};
@@ -0,0 +1,112 @@
+use super::{
+    entity::Entity,
+    world::{Bundle, World},
+};
+
+type Command = Box<dyn FnOnce(&mut World)>;
+
+/// Records structural changes of a [`World`] to apply them later
+///
+/// Systems only get a shared reference to the world, so spawning, despawning and adding or
+/// removing components is done through a command buffer applied after the system ran.
+///
+/// ```
+/// use nazara_core::ecs::{CommandBuffer, World};
+///
+/// struct Lifetime(u32);
+/// struct Explosion;
+///
+/// let mut world = World::new();
+/// world.spawn((Lifetime(0),));
+/// world.spawn((Lifetime(3),));
+///
+/// let mut commands = CommandBuffer::new();
+/// world.query::<(&Lifetime,)>().for_each(|entity, (lifetime,)| {
+///     if lifetime.0 == 0 {
+///         commands.despawn(entity);
+///         commands.spawn((Explosion,));
+///     }
+/// });
+/// assert_eq!(commands.len(), 2);
+///
+/// world.apply_commands(&mut commands);
+/// assert!(commands.is_empty());
+/// assert_eq!(world.query::<(&Lifetime,)>().count(), 1);
+/// assert_eq!(world.query::<(&Explosion,)>().count(), 1);
+/// ```
+#[derive(Default)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+}
+
+impl CommandBuffer {
+    /// Creates a new empty command buffer
+    pub fn new() -> CommandBuffer {
+        CommandBuffer::default()
+    }
+
+    /// Records the creation of an entity
+    ///
+    /// # Arguments
+    /// * `bundle` - Tuple of components of the entity
+    pub fn spawn<B: Bundle>(&mut self, bundle: B) {
+        self.push(move |world| {
+            world.spawn(bundle);
+        });
+    }
+
+    /// Records the destruction of an entity
+    ///
+    /// # Arguments
+    /// * `entity` - Entity to destroy
+    pub fn despawn(&mut self, entity: Entity) {
+        self.push(move |world| {
+            world.despawn(entity);
+        });
+    }
+
+    /// Records the addition of a component
+    ///
+    /// # Arguments
+    /// * `entity` - Entity receiving the component
+    /// * `component` - Component to add
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) {
+        self.push(move |world| {
+            world.insert(entity, component);
+        });
+    }
+
+    /// Records the removal of a component
+    ///
+    /// # Arguments
+    /// * `entity` - Entity owning the component
+    pub fn remove<T: 'static>(&mut self, entity: Entity) {
+        self.push(move |world| {
+            world.remove::<T>(entity);
+        });
+    }
+
+    /// Records any change of the world
+    ///
+    /// # Arguments
+    /// * `command` - Function called with the world when the buffer is applied
+    pub fn push<F: FnOnce(&mut World) + 'static>(&mut self, command: F) {
+        self.commands.push(Box::new(command));
+    }
+
+    /// Returns how many commands are recorded
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if no command is recorded
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub(crate) fn apply(&mut self, world: &mut World) {
+        for command in self.commands.drain(..) {
+            command(world);
+        }
+    }
+}
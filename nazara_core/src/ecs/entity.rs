@@ -0,0 +1,78 @@
+/// Identifier of an entity of a [`World`](super::World)
+///
+/// Indices of despawned entities are reused, their generation allows to detect stale handles.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    /// Returns the index of the entity, which is reused after the entity is despawned
+    pub fn get_index(self) -> u32 {
+        self.index
+    }
+
+    /// Returns how many times the index of the entity was reused
+    pub fn get_generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// Allocates entity indices and keeps track of their generation
+#[derive(Default)]
+pub(crate) struct EntityAllocator {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    count: usize,
+}
+
+impl EntityAllocator {
+    pub(crate) fn allocate(&mut self) -> Entity {
+        self.count += 1;
+
+        match self.free.pop() {
+            Some(index) => {
+                self.alive[index as usize] = true;
+                Entity {
+                    index,
+                    generation: self.generations[index as usize],
+                }
+            }
+            None => {
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity {
+                    index: (self.generations.len() - 1) as u32,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    pub(crate) fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        let index = entity.index as usize;
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        self.count -= 1;
+
+        true
+    }
+
+    pub(crate) fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        index < self.alive.len()
+            && self.alive[index]
+            && self.generations[index] == entity.generation
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.count
+    }
+}
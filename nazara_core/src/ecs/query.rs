@@ -0,0 +1,300 @@
+use std::{
+    cell::{Ref, RefMut},
+    marker::PhantomData,
+};
+
+use super::{entity::Entity, storage::ComponentStorage, world::World};
+
+/// Components accessed by a [`Query`]: `&T`, `&mut T` or tuples of up to eight of them
+pub trait Fetch {
+    /// Borrow of the storages held while iterating
+    type Borrow<'w>;
+    /// Value given to the query callback for each entity
+    type Item<'a>;
+
+    /// Returns the entities having all fetched components, `None` if one of them was never
+    /// added to any entity
+    fn candidates(world: &World) -> Option<Vec<Entity>>;
+
+    /// Returns how many entities have the component with the fewest entities
+    fn min_len(world: &World) -> usize;
+
+    /// Removes the entities which don't have all fetched components
+    fn retain(world: &World, entities: &mut Vec<Entity>);
+
+    /// Borrows the storages, panics if they are already borrowed incompatibly
+    fn borrow(world: &World) -> Self::Borrow<'_>;
+
+    /// Returns the components of an entity, which must have all of them
+    fn fetch<'a>(borrow: &'a mut Self::Borrow<'_>, entity: Entity, tick: u32) -> Self::Item<'a>;
+}
+
+impl<T: 'static> Fetch for &T {
+    type Borrow<'w> = Ref<'w, ComponentStorage<T>>;
+    type Item<'a> = &'a T;
+
+    fn candidates(world: &World) -> Option<Vec<Entity>> {
+        world.storage::<T>().map(|s| s.borrow().entities().to_vec())
+    }
+
+    fn min_len(world: &World) -> usize {
+        world.storage::<T>().map(|s| s.borrow().len()).unwrap_or(0)
+    }
+
+    fn retain(world: &World, entities: &mut Vec<Entity>) {
+        match world.storage::<T>() {
+            Some(storage) => {
+                let storage = storage.borrow();
+                entities.retain(|&e| storage.contains(e));
+            }
+            None => entities.clear(),
+        }
+    }
+
+    fn borrow(world: &World) -> Self::Borrow<'_> {
+        world.storage::<T>().unwrap().borrow()
+    }
+
+    fn fetch<'a>(borrow: &'a mut Self::Borrow<'_>, entity: Entity, _tick: u32) -> &'a T {
+        borrow.get(entity).unwrap()
+    }
+}
+
+impl<T: 'static> Fetch for &mut T {
+    type Borrow<'w> = RefMut<'w, ComponentStorage<T>>;
+    type Item<'a> = &'a mut T;
+
+    fn candidates(world: &World) -> Option<Vec<Entity>> {
+        <&T as Fetch>::candidates(world)
+    }
+
+    fn min_len(world: &World) -> usize {
+        <&T as Fetch>::min_len(world)
+    }
+
+    fn retain(world: &World, entities: &mut Vec<Entity>) {
+        <&T as Fetch>::retain(world, entities)
+    }
+
+    fn borrow(world: &World) -> Self::Borrow<'_> {
+        world.storage::<T>().unwrap().borrow_mut()
+    }
+
+    fn fetch<'a>(borrow: &'a mut Self::Borrow<'_>, entity: Entity, tick: u32) -> &'a mut T {
+        borrow.get_mut(entity, tick).unwrap()
+    }
+}
+
+macro_rules! impl_fetch {
+    ($(($name:ident, $index:tt)),+) => {
+        impl<$($name: Fetch),+> Fetch for ($($name,)+) {
+            type Borrow<'w> = ($($name::Borrow<'w>,)+);
+            type Item<'a> = ($($name::Item<'a>,)+);
+
+            fn candidates(world: &World) -> Option<Vec<Entity>> {
+                // Start from the smallest storage, then keep entities having all components
+                let mut smallest: Option<(usize, Vec<Entity>)> = None;
+                $(
+                    let len = $name::min_len(world);
+                    if smallest.as_ref().map(|&(l, _)| len < l).unwrap_or(true) {
+                        smallest = Some((len, $name::candidates(world)?));
+                    }
+                )+
+
+                let mut entities = smallest.map(|(_, e)| e).unwrap_or_default();
+                Self::retain(world, &mut entities);
+                Some(entities)
+            }
+
+            fn min_len(world: &World) -> usize {
+                let mut len = usize::MAX;
+                $(len = len.min($name::min_len(world));)+
+                len
+            }
+
+            fn retain(world: &World, entities: &mut Vec<Entity>) {
+                $($name::retain(world, entities);)+
+            }
+
+            fn borrow(world: &World) -> Self::Borrow<'_> {
+                ($($name::borrow(world),)+)
+            }
+
+            fn fetch<'a>(
+                borrow: &'a mut Self::Borrow<'_>,
+                entity: Entity,
+                tick: u32,
+            ) -> Self::Item<'a> {
+                ($($name::fetch(&mut borrow.$index, entity, tick),)+)
+            }
+        }
+    };
+}
+
+impl_fetch!((A, 0));
+impl_fetch!((A, 0), (B, 1));
+impl_fetch!((A, 0), (B, 1), (C, 2));
+impl_fetch!((A, 0), (B, 1), (C, 2), (D, 3));
+impl_fetch!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+impl_fetch!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
+impl_fetch!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6));
+impl_fetch!(
+    (A, 0),
+    (B, 1),
+    (C, 2),
+    (D, 3),
+    (E, 4),
+    (F, 5),
+    (G, 6),
+    (H, 7)
+);
+
+/// Restricts the entities matched by a [`Query`]: [`With`], [`Without`], [`Changed`] or tuples
+/// of up to eight of them
+pub trait QueryFilter {
+    /// Removes the entities not matching the filter
+    fn filter(world: &World, entities: &mut Vec<Entity>);
+}
+
+impl QueryFilter for () {
+    fn filter(_world: &World, _entities: &mut Vec<Entity>) {}
+}
+
+/// Only matches entities having a component of type `T`, without accessing it
+pub struct With<T>(PhantomData<T>);
+
+impl<T: 'static> QueryFilter for With<T> {
+    fn filter(world: &World, entities: &mut Vec<Entity>) {
+        <&T as Fetch>::retain(world, entities);
+    }
+}
+
+/// Only matches entities not having a component of type `T`
+pub struct Without<T>(PhantomData<T>);
+
+impl<T: 'static> QueryFilter for Without<T> {
+    fn filter(world: &World, entities: &mut Vec<Entity>) {
+        if let Some(storage) = world.storage::<T>() {
+            let storage = storage.borrow();
+            entities.retain(|&e| !storage.contains(e));
+        }
+    }
+}
+
+/// Only matches entities whose component of type `T` was added or mutably accessed since the
+/// last run of the current system (or since [`World::clear_trackers`] outside of systems)
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T: 'static> QueryFilter for Changed<T> {
+    fn filter(world: &World, entities: &mut Vec<Entity>) {
+        let reference = world.get_change_reference();
+        match world.storage::<T>() {
+            Some(storage) => {
+                let storage = storage.borrow();
+                entities.retain(|&e| {
+                    storage
+                        .changed_tick(e)
+                        .map(|tick| tick > reference)
+                        .unwrap_or(false)
+                });
+            }
+            None => entities.clear(),
+        }
+    }
+}
+
+macro_rules! impl_filter {
+    ($($name:ident),+) => {
+        impl<$($name: QueryFilter),+> QueryFilter for ($($name,)+) {
+            fn filter(world: &World, entities: &mut Vec<Entity>) {
+                $($name::filter(world, entities);)+
+            }
+        }
+    };
+}
+
+impl_filter!(A);
+impl_filter!(A, B);
+impl_filter!(A, B, C);
+impl_filter!(A, B, C, D);
+impl_filter!(A, B, C, D, E);
+impl_filter!(A, B, C, D, E, F);
+impl_filter!(A, B, C, D, E, F, G);
+impl_filter!(A, B, C, D, E, F, G, H);
+
+/// Iterates on the entities having the components `Q` and matching the filter `F`
+///
+/// Matching entities are collected when iterating, then the storages are borrowed for the whole
+/// iteration: accessing the same component type mutably twice, or from a nested query while it
+/// is mutably borrowed, panics.
+///
+/// ```
+/// use nazara_core::ecs::{Changed, With, Without, World};
+///
+/// struct Health(u32);
+/// struct Player;
+/// struct Dead;
+///
+/// let mut world = World::new();
+/// let player = world.spawn((Health(100), Player));
+/// world.spawn((Health(50), Player, Dead));
+/// world.spawn((Health(10),));
+///
+/// let alive_players = world.query_filtered::<(&Health,), (With<Player>, Without<Dead>)>();
+/// assert_eq!(alive_players.entities(), vec![player]);
+///
+/// world.clear_trackers();
+/// assert_eq!(world.query_filtered::<(&Health,), Changed<Health>>().count(), 0);
+///
+/// world.get_mut::<Health>(player).unwrap().0 -= 30;
+/// world.query_filtered::<(&Health,), Changed<Health>>().for_each(|entity, (health,)| {
+///     assert_eq!(entity, player);
+///     assert_eq!(health.0, 70);
+/// });
+/// ```
+pub struct Query<'w, Q: Fetch, F: QueryFilter> {
+    world: &'w World,
+    marker: PhantomData<fn() -> (Q, F)>,
+}
+
+impl<'w, Q: Fetch, F: QueryFilter> Query<'w, Q, F> {
+    pub(crate) fn new(world: &'w World) -> Query<'w, Q, F> {
+        Query {
+            world,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the entities matched by the query
+    pub fn entities(&self) -> Vec<Entity> {
+        let mut entities = Q::candidates(self.world).unwrap_or_default();
+        F::filter(self.world, &mut entities);
+
+        entities
+    }
+
+    /// Returns how many entities are matched by the query
+    pub fn count(&self) -> usize {
+        self.entities().len()
+    }
+
+    /// Calls a function with each matched entity and its components
+    ///
+    /// # Arguments
+    /// * `func` - Function called with each entity and the fetched components
+    pub fn for_each<C>(self, mut func: C)
+    where
+        C: for<'a> FnMut(Entity, Q::Item<'a>),
+    {
+        let entities = self.entities();
+        if entities.is_empty() {
+            return;
+        }
+
+        let tick = self.world.get_tick();
+        let mut borrow = Q::borrow(self.world);
+        for entity in entities {
+            func(entity, Q::fetch(&mut borrow, entity, tick));
+        }
+    }
+}
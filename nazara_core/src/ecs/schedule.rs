@@ -0,0 +1,103 @@
+use super::{commands::CommandBuffer, world::World};
+
+/// Logic run on a [`World`] each frame
+///
+/// Implemented for functions taking the world and a [`CommandBuffer`], applied once the system
+/// ran.
+pub trait System {
+    /// Runs the system
+    fn run(&mut self, world: &World, commands: &mut CommandBuffer);
+}
+
+impl<F: FnMut(&World, &mut CommandBuffer)> System for F {
+    fn run(&mut self, world: &World, commands: &mut CommandBuffer) {
+        self(world, commands)
+    }
+}
+
+struct ScheduledSystem {
+    system: Box<dyn System>,
+    last_run: u32,
+}
+
+/// Ordered list of systems
+///
+/// Each system sees the changes made by the systems and commands which ran before it, and its
+/// [`Changed`](super::Changed) filters match the components modified since its previous run.
+///
+/// ```
+/// use nazara_core::ecs::{Changed, CommandBuffer, Schedule, World};
+///
+/// struct Position(i32);
+/// struct Velocity(i32);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn((Position(0), Velocity(2)));
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_system(|world: &World, _: &mut CommandBuffer| {
+///     world.query::<(&mut Position, &Velocity)>().for_each(|_, (position, velocity)| {
+///         position.0 += velocity.0;
+///     });
+/// });
+/// schedule.add_system(|world: &World, commands: &mut CommandBuffer| {
+///     world.query_filtered::<(&Position,), Changed<Position>>().for_each(|entity, (position,)| {
+///         if position.0 >= 4 {
+///             commands.remove::<Velocity>(entity);
+///         }
+///     });
+/// });
+///
+/// schedule.run(&mut world);
+/// schedule.run(&mut world);
+/// schedule.run(&mut world);
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 4);
+/// assert!(!world.has::<Velocity>(entity));
+/// ```
+#[derive(Default)]
+pub struct Schedule {
+    systems: Vec<ScheduledSystem>,
+    commands: CommandBuffer,
+}
+
+impl Schedule {
+    /// Creates a new schedule without any system
+    pub fn new() -> Schedule {
+        Schedule::default()
+    }
+
+    /// Adds a system, run after the systems already added
+    ///
+    /// # Arguments
+    /// * `system` - System to add
+    pub fn add_system<S: System + 'static>(&mut self, system: S) {
+        self.systems.push(ScheduledSystem {
+            system: Box::new(system),
+            last_run: 0,
+        });
+    }
+
+    /// Returns how many systems are scheduled
+    pub fn get_system_count(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Runs all systems in order, applying their commands after each of them
+    ///
+    /// # Arguments
+    /// * `world` - World the systems run on
+    pub fn run(&mut self, world: &mut World) {
+        let reference = world.get_change_reference();
+
+        for scheduled in &mut self.systems {
+            world.set_change_reference(scheduled.last_run);
+            scheduled.system.run(world, &mut self.commands);
+            scheduled.last_run = world.get_tick();
+            world.advance_tick();
+
+            world.apply_commands(&mut self.commands);
+        }
+
+        world.set_change_reference(reference);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{any::Any, cell::RefCell};
+
+use super::entity::Entity;
+
+const INVALID: u32 = u32::MAX;
+
+/// Sparse set storing the components of one type
+///
+/// Components are densely packed for fast iteration, the sparse array maps entity indices to
+/// their position in the dense arrays.
+pub struct ComponentStorage<T> {
+    sparse: Vec<u32>,
+    entities: Vec<Entity>,
+    components: Vec<T>,
+    changed: Vec<u32>,
+}
+
+impl<T> ComponentStorage<T> {
+    pub(crate) fn new() -> ComponentStorage<T> {
+        ComponentStorage {
+            sparse: Vec::new(),
+            entities: Vec::new(),
+            components: Vec::new(),
+            changed: Vec::new(),
+        }
+    }
+
+    fn dense_index(&self, entity: Entity) -> Option<usize> {
+        match self.sparse.get(entity.get_index() as usize) {
+            Some(&index) if index != INVALID && self.entities[index as usize] == entity => {
+                Some(index as usize)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, entity: Entity, component: T, tick: u32) {
+        if let Some(index) = self.dense_index(entity) {
+            self.components[index] = component;
+            self.changed[index] = tick;
+            return;
+        }
+
+        let sparse_index = entity.get_index() as usize;
+        if sparse_index >= self.sparse.len() {
+            self.sparse.resize(sparse_index + 1, INVALID);
+        }
+
+        self.sparse[sparse_index] = self.entities.len() as u32;
+        self.entities.push(entity);
+        self.components.push(component);
+        self.changed.push(tick);
+    }
+
+    pub(crate) fn remove(&mut self, entity: Entity) -> Option<T> {
+        let index = self.dense_index(entity)?;
+
+        self.sparse[entity.get_index() as usize] = INVALID;
+        self.entities.swap_remove(index);
+        self.changed.swap_remove(index);
+        let component = self.components.swap_remove(index);
+
+        if let Some(moved) = self.entities.get(index) {
+            self.sparse[moved.get_index() as usize] = index as u32;
+        }
+
+        Some(component)
+    }
+
+    pub(crate) fn contains(&self, entity: Entity) -> bool {
+        self.dense_index(entity).is_some()
+    }
+
+    pub(crate) fn get(&self, entity: Entity) -> Option<&T> {
+        self.dense_index(entity).map(|i| &self.components[i])
+    }
+
+    /// Returns a mutable reference to a component, marking it as changed
+    pub(crate) fn get_mut(&mut self, entity: Entity, tick: u32) -> Option<&mut T> {
+        let index = self.dense_index(entity)?;
+        self.changed[index] = tick;
+
+        Some(&mut self.components[index])
+    }
+
+    pub(crate) fn changed_tick(&self, entity: Entity) -> Option<u32> {
+        self.dense_index(entity).map(|i| self.changed[i])
+    }
+
+    pub(crate) fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entities.len()
+    }
+}
+
+/// Type-erased component storage, so the world can hold storages of any component type
+pub(crate) trait AnyStorage {
+    fn remove_entity(&self, entity: Entity);
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: 'static> AnyStorage for RefCell<ComponentStorage<T>> {
+    fn remove_entity(&self, entity: Entity) {
+        self.borrow_mut().remove(entity);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
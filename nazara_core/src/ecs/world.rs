@@ -0,0 +1,249 @@
+use std::{
+    any::TypeId,
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+};
+
+use super::{
+    commands::CommandBuffer,
+    entity::{Entity, EntityAllocator},
+    query::{Fetch, Query, QueryFilter},
+    storage::{AnyStorage, ComponentStorage},
+};
+
+/// Set of components which can be added to an entity at once
+///
+/// Implemented for tuples of up to eight components.
+pub trait Bundle: 'static {
+    /// Adds the components to an entity
+    fn insert_into(self, world: &mut World, entity: Entity);
+}
+
+macro_rules! impl_bundle {
+    ($($name:ident),+) => {
+        impl<$($name: 'static),+> Bundle for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn insert_into(self, world: &mut World, entity: Entity) {
+                let ($($name,)+) = self;
+                $(world.insert(entity, $name);)+
+            }
+        }
+    };
+}
+
+impl_bundle!(A);
+impl_bundle!(A, B);
+impl_bundle!(A, B, C);
+impl_bundle!(A, B, C, D);
+impl_bundle!(A, B, C, D, E);
+impl_bundle!(A, B, C, D, E, F);
+impl_bundle!(A, B, C, D, E, F, G);
+impl_bundle!(A, B, C, D, E, F, G, H);
+
+/// Container of entities and of their components
+///
+/// Components can be of any `'static` type, and are stored in one sparse set per type.
+///
+/// ```
+/// use nazara_core::ecs::World;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Position(f32, f32);
+/// struct Velocity(f32, f32);
+///
+/// let mut world = World::new();
+/// let player = world.spawn((Position(0.0, 0.0), Velocity(1.0, 2.0)));
+/// let tree = world.spawn((Position(5.0, 5.0),));
+///
+/// world.query::<(&mut Position, &Velocity)>().for_each(|_, (position, velocity)| {
+///     position.0 += velocity.0;
+///     position.1 += velocity.1;
+/// });
+///
+/// assert_eq!(*world.get::<Position>(player).unwrap(), Position(1.0, 2.0));
+/// assert_eq!(*world.get::<Position>(tree).unwrap(), Position(5.0, 5.0));
+///
+/// world.despawn(player);
+/// assert!(!world.is_alive(player));
+/// assert!(world.get::<Position>(player).is_none());
+///
+/// // The index of the player is reused, but not its generation
+/// let rock = world.spawn((Position(9.0, 9.0),));
+/// assert_eq!(rock.get_index(), player.get_index());
+/// assert!(world.get::<Position>(player).is_none());
+/// ```
+pub struct World {
+    entities: EntityAllocator,
+    storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    tick: u32,
+    change_reference: u32,
+}
+
+impl World {
+    /// Creates a new empty world
+    pub fn new() -> World {
+        World {
+            entities: EntityAllocator::default(),
+            storages: HashMap::new(),
+            tick: 1,
+            change_reference: 0,
+        }
+    }
+
+    /// Creates a new entity with a set of components
+    ///
+    /// # Arguments
+    /// * `bundle` - Tuple of components of the entity
+    pub fn spawn<B: Bundle>(&mut self, bundle: B) -> Entity {
+        let entity = self.entities.allocate();
+        bundle.insert_into(self, entity);
+
+        entity
+    }
+
+    /// Creates a new entity without any component
+    pub fn spawn_empty(&mut self) -> Entity {
+        self.entities.allocate()
+    }
+
+    /// Destroys an entity and its components, returns false if it was already despawned
+    ///
+    /// # Arguments
+    /// * `entity` - Entity to destroy
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.entities.free(entity) {
+            return false;
+        }
+
+        for storage in self.storages.values() {
+            storage.remove_entity(entity);
+        }
+
+        true
+    }
+
+    /// Returns true if the entity was not despawned
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Returns how many entities are alive
+    pub fn get_entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Adds or replaces a component of an entity, returns false if the entity is not alive
+    ///
+    /// # Arguments
+    /// * `entity` - Entity receiving the component
+    /// * `component` - Component to add
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        let tick = self.tick;
+        self.storage_or_insert::<T>()
+            .borrow_mut()
+            .insert(entity, component, tick);
+
+        true
+    }
+
+    /// Removes a component from an entity and returns it
+    ///
+    /// # Arguments
+    /// * `entity` - Entity owning the component
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        self.storage::<T>()?.borrow_mut().remove(entity)
+    }
+
+    /// Returns true if the entity has a component of this type
+    pub fn has<T: 'static>(&self, entity: Entity) -> bool {
+        self.storage::<T>()
+            .map(|s| s.borrow().contains(entity))
+            .unwrap_or(false)
+    }
+
+    /// Returns a component of an entity
+    ///
+    /// # Panics
+    /// If the components of this type are mutably borrowed (by a running query for example).
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<Ref<'_, T>> {
+        let storage = self.storage::<T>()?.borrow();
+        Ref::filter_map(storage, |s| s.get(entity)).ok()
+    }
+
+    /// Returns a mutable reference to a component of an entity, marking it as changed
+    ///
+    /// # Panics
+    /// If the components of this type are already borrowed (by a running query for example).
+    pub fn get_mut<T: 'static>(&self, entity: Entity) -> Option<RefMut<'_, T>> {
+        let tick = self.tick;
+        let storage = self.storage::<T>()?.borrow_mut();
+        RefMut::filter_map(storage, |s| s.get_mut(entity, tick)).ok()
+    }
+
+    /// Creates a query giving access to the entities having all components of `Q`
+    pub fn query<Q: Fetch>(&self) -> Query<'_, Q, ()> {
+        Query::new(self)
+    }
+
+    /// Creates a query giving access to the entities having all components of `Q` and matching
+    /// the filter `F`
+    pub fn query_filtered<Q: Fetch, F: QueryFilter>(&self) -> Query<'_, Q, F> {
+        Query::new(self)
+    }
+
+    /// Runs and clears the commands of a buffer
+    ///
+    /// # Arguments
+    /// * `commands` - Commands to apply
+    pub fn apply_commands(&mut self, commands: &mut CommandBuffer) {
+        commands.apply(self);
+    }
+
+    /// Forgets the changes made so far, [`Changed`](super::Changed) filters only match
+    /// components modified after this call
+    pub fn clear_trackers(&mut self) {
+        self.change_reference = self.tick;
+        self.tick += 1;
+    }
+
+    pub(crate) fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    pub(crate) fn get_change_reference(&self) -> u32 {
+        self.change_reference
+    }
+
+    pub(crate) fn set_change_reference(&mut self, reference: u32) {
+        self.change_reference = reference;
+    }
+
+    pub(crate) fn advance_tick(&mut self) {
+        self.tick += 1;
+    }
+
+    pub(crate) fn storage<T: 'static>(&self) -> Option<&RefCell<ComponentStorage<T>>> {
+        self.storages
+            .get(&TypeId::of::<T>())
+            .map(|s| s.as_any().downcast_ref().unwrap())
+    }
+
+    fn storage_or_insert<T: 'static>(&mut self) -> &RefCell<ComponentStorage<T>> {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(RefCell::new(ComponentStorage::<T>::new())))
+            .as_any()
+            .downcast_ref()
+            .unwrap()
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,46 @@
 use image::ColorType;
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents the byte order of multi-byte values
+pub enum Endianness {
+    /// Most significant byte first
+    BigEndian,
+    /// Least significant byte first
+    LittleEndian,
+}
+
+impl Endianness {
+    /// Return the byte order of the target platform
+    pub fn native() -> Endianness {
+        if cfg!(target_endian = "big") {
+            Endianness::BigEndian
+        } else {
+            Endianness::LittleEndian
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents one of the planes of a frustum
+pub enum FrustumPlane {
+    /// Plane at the bottom of the view
+    Bottom,
+    /// Plane at the far end of the view
+    Far,
+    /// Plane on the left of the view
+    Left,
+    /// Plane at the near end of the view
+    Near,
+    /// Plane on the right of the view
+    Right,
+    /// Plane at the top of the view
+    Top,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents an image format
 pub enum ImageType {
     /// TODO
@@ -17,7 +57,20 @@ pub enum ImageType {
     Single3D,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents the position of a volume relative to another one
+pub enum IntersectionSide {
+    /// The volume is entirely inside the other one
+    Inside,
+    /// The volume is partially inside the other one
+    Intersecting,
+    /// The volume is entirely outside the other one
+    Outside,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents the content of a pixel format
 pub enum PixelFormatContent {
     /// Pixel format has at least one of the RGBA component
@@ -29,6 +82,7 @@ pub enum PixelFormatContent {
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a pixel format type.
 pub enum PixelFormatType {
     /// 8 bits grey pixel
@@ -1,4 +1,4 @@
-use std::{error::Error, fmt, io};
+use std::{error::Error, fmt, io, path::PathBuf};
 
 use crate::enums::PixelFormatType;
 
@@ -6,13 +6,31 @@ pub type NazaraResult<T> = Result<T, NazaraError>;
 
 #[derive(Debug)]
 pub enum NazaraError {
+    ConfigError(ConfigError),
     ImageError(ImageError),
+    ResourceError(ResourceError),
+    JobError(JobError),
+    TaskError(TaskError),
+    MemoryError(MemoryError),
+    PlatformError(PlatformError),
+    PluginError(PluginError),
+    SceneError(SceneError),
+    StreamError(StreamError),
 }
 
 impl fmt::Display for NazaraError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
+            NazaraError::ConfigError(ref e) => e.fmt(fmt),
             NazaraError::ImageError(ref e) => e.fmt(fmt),
+            NazaraError::ResourceError(ref e) => e.fmt(fmt),
+            NazaraError::JobError(ref e) => e.fmt(fmt),
+            NazaraError::TaskError(ref e) => e.fmt(fmt),
+            NazaraError::MemoryError(ref e) => e.fmt(fmt),
+            NazaraError::PlatformError(ref e) => e.fmt(fmt),
+            NazaraError::PluginError(ref e) => e.fmt(fmt),
+            NazaraError::SceneError(ref e) => e.fmt(fmt),
+            NazaraError::StreamError(ref e) => e.fmt(fmt),
         }
     }
 }
@@ -20,23 +38,95 @@ impl fmt::Display for NazaraError {
 impl Error for NazaraError {
     fn description(&self) -> &str {
         match *self {
+            NazaraError::ConfigError(_) => "Config error",
             NazaraError::ImageError(_) => "Image error",
+            NazaraError::ResourceError(_) => "Resource error",
+            NazaraError::JobError(_) => "Job error",
+            NazaraError::TaskError(_) => "Task error",
+            NazaraError::MemoryError(_) => "Memory error",
+            NazaraError::PlatformError(_) => "Platform error",
+            NazaraError::PluginError(_) => "Plugin error",
+            NazaraError::SceneError(_) => "Scene error",
+            NazaraError::StreamError(_) => "Stream error",
         }
     }
 
     fn cause(&self) -> Option<&dyn Error> {
         match *self {
+            NazaraError::ConfigError(ref e) => Some(e),
             NazaraError::ImageError(ref e) => Some(e),
+            NazaraError::ResourceError(ref e) => Some(e),
+            NazaraError::JobError(ref e) => Some(e),
+            NazaraError::TaskError(ref e) => Some(e),
+            NazaraError::MemoryError(ref e) => Some(e),
+            NazaraError::PlatformError(ref e) => Some(e),
+            NazaraError::PluginError(ref e) => Some(e),
+            NazaraError::SceneError(ref e) => Some(e),
+            NazaraError::StreamError(ref e) => Some(e),
         }
     }
 }
 
+impl From<ConfigError> for NazaraError {
+    fn from(err: ConfigError) -> Self {
+        NazaraError::ConfigError(err)
+    }
+}
+
 impl From<ImageError> for NazaraError {
     fn from(err: ImageError) -> Self {
         NazaraError::ImageError(err)
     }
 }
 
+impl From<ResourceError> for NazaraError {
+    fn from(err: ResourceError) -> Self {
+        NazaraError::ResourceError(err)
+    }
+}
+
+impl From<JobError> for NazaraError {
+    fn from(err: JobError) -> Self {
+        NazaraError::JobError(err)
+    }
+}
+
+impl From<TaskError> for NazaraError {
+    fn from(err: TaskError) -> Self {
+        NazaraError::TaskError(err)
+    }
+}
+
+impl From<MemoryError> for NazaraError {
+    fn from(err: MemoryError) -> Self {
+        NazaraError::MemoryError(err)
+    }
+}
+
+impl From<PlatformError> for NazaraError {
+    fn from(err: PlatformError) -> Self {
+        NazaraError::PlatformError(err)
+    }
+}
+
+impl From<PluginError> for NazaraError {
+    fn from(err: PluginError) -> Self {
+        NazaraError::PluginError(err)
+    }
+}
+
+impl From<SceneError> for NazaraError {
+    fn from(err: SceneError) -> Self {
+        NazaraError::SceneError(err)
+    }
+}
+
+impl From<StreamError> for NazaraError {
+    fn from(err: StreamError) -> Self {
+        NazaraError::StreamError(err)
+    }
+}
+
 #[derive(Debug)]
 pub enum ImageError {
     /// The Image is not formatted properly
@@ -142,3 +232,419 @@ impl From<image::ImageError> for ImageError {
         }
     }
 }
+
+#[derive(Debug)]
+pub enum ResourceError {
+    /// The file watcher could not be created or could not watch a path
+    WatchError(String),
+
+    /// The resource is not watched by the hot reloader
+    NotWatched(PathBuf),
+
+    /// The virtual path is malformed or uses an unknown mount point
+    InvalidPath(String),
+
+    /// No mounted backend contains the requested virtual path
+    NotFound(String),
+
+    /// An archive is corrupted or uses an unsupported format
+    ArchiveError(String),
+
+    /// An I/O Error occurred while accessing a resource
+    IoError(io::Error),
+
+    /// The loading of the resource was cancelled
+    Cancelled,
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ResourceError::WatchError(ref e) => write!(fmt, "Watch error: {}", e),
+            ResourceError::NotWatched(ref p) => {
+                write!(fmt, "The resource `{}` is not watched", p.display())
+            }
+            ResourceError::InvalidPath(ref p) => write!(fmt, "Invalid virtual path `{}`", p),
+            ResourceError::NotFound(ref p) => write!(fmt, "The resource `{}` was not found", p),
+            ResourceError::ArchiveError(ref e) => write!(fmt, "Archive error: {}", e),
+            ResourceError::IoError(ref e) => e.fmt(fmt),
+            ResourceError::Cancelled => write!(fmt, "The loading was cancelled"),
+        }
+    }
+}
+
+impl Error for ResourceError {
+    fn description(&self) -> &str {
+        match *self {
+            ResourceError::WatchError(_) => "Watch error",
+            ResourceError::NotWatched(_) => "Resource not watched",
+            ResourceError::InvalidPath(_) => "Invalid path",
+            ResourceError::NotFound(_) => "Resource not found",
+            ResourceError::ArchiveError(_) => "Archive error",
+            ResourceError::IoError(_) => "IO error",
+            ResourceError::Cancelled => "Cancelled",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        match *self {
+            ResourceError::IoError(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ResourceError {
+    fn from(err: io::Error) -> ResourceError {
+        ResourceError::IoError(err)
+    }
+}
+
+impl From<notify::Error> for ResourceError {
+    fn from(err: notify::Error) -> Self {
+        ResourceError::WatchError(err.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for ResourceError {
+    fn from(err: zip::result::ZipError) -> Self {
+        match err {
+            zip::result::ZipError::Io(e) => ResourceError::IoError(e),
+            e => ResourceError::ArchiveError(e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum JobError {
+    /// The jobs of a graph depend on each other
+    CyclicDependency,
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            JobError::CyclicDependency => write!(fmt, "The job graph has a cyclic dependency"),
+        }
+    }
+}
+
+impl Error for JobError {
+    fn description(&self) -> &str {
+        match *self {
+            JobError::CyclicDependency => "Cyclic dependency",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TaskError {
+    /// The task was cancelled before running
+    Cancelled,
+
+    /// The task panicked before returning its result
+    Panicked,
+
+    /// The result of the task was already taken, by the handle or a completion callback
+    ResultTaken,
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            TaskError::Cancelled => write!(fmt, "The task was cancelled before running"),
+            TaskError::Panicked => write!(fmt, "The task panicked"),
+            TaskError::ResultTaken => write!(fmt, "The result of the task was already taken"),
+        }
+    }
+}
+
+impl Error for TaskError {
+    fn description(&self) -> &str {
+        match *self {
+            TaskError::Cancelled => "Task cancelled",
+            TaskError::Panicked => "Task panicked",
+            TaskError::ResultTaken => "Task result taken",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SceneError {
+    /// The node was removed or belongs to another scene
+    InvalidNode,
+
+    /// The node would become its own ancestor
+    CyclicHierarchy,
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SceneError::InvalidNode => write!(fmt, "The node does not exist in this scene"),
+            SceneError::CyclicHierarchy => {
+                write!(fmt, "A node can't be attached to one of its descendants")
+            }
+        }
+    }
+}
+
+impl Error for SceneError {
+    fn description(&self) -> &str {
+        match *self {
+            SceneError::InvalidNode => "Invalid node",
+            SceneError::CyclicHierarchy => "Cyclic hierarchy",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    /// The stream doesn't start with the expected identifier
+    InvalidMagic,
+
+    /// The stream was written by a newer version
+    UnsupportedVersion(u32),
+
+    /// The stream contains a value which can't be read
+    InvalidData(String),
+
+    /// An I/O error occurred while reading or writing the stream
+    IoError(io::Error),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            StreamError::InvalidMagic => write!(fmt, "The stream has an unknown format"),
+            StreamError::UnsupportedVersion(version) => {
+                write!(fmt, "Version {} of the stream is not supported", version)
+            }
+            StreamError::InvalidData(ref e) => write!(fmt, "Invalid data: {}", e),
+            StreamError::IoError(ref e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn description(&self) -> &str {
+        match *self {
+            StreamError::InvalidMagic => "Invalid magic",
+            StreamError::UnsupportedVersion(_) => "Unsupported version",
+            StreamError::InvalidData(_) => "Invalid data",
+            StreamError::IoError(_) => "IO error",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        match *self {
+            StreamError::IoError(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> StreamError {
+        StreamError::IoError(err)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// No option was declared with this key
+    UnknownOption(String),
+
+    /// The value can't be converted to the type of the option
+    InvalidValue(String),
+
+    /// The value is outside of the range of the option
+    OutOfRange(String),
+
+    /// The configuration file is not well formed
+    ParseError(String),
+
+    /// The configuration file format is not supported
+    UnsupportedFormat(String),
+
+    /// An I/O error occurred while reading or writing a configuration file
+    IoError(io::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ConfigError::UnknownOption(ref key) => write!(fmt, "Unknown option: {}", key),
+            ConfigError::InvalidValue(ref key) => {
+                write!(fmt, "Invalid value type for option {}", key)
+            }
+            ConfigError::OutOfRange(ref key) => {
+                write!(fmt, "Value out of range for option {}", key)
+            }
+            ConfigError::ParseError(ref e) => write!(fmt, "Parse error: {}", e),
+            ConfigError::UnsupportedFormat(ref e) => write!(fmt, "Unsupported format: {}", e),
+            ConfigError::IoError(ref e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn description(&self) -> &str {
+        match *self {
+            ConfigError::UnknownOption(_) => "Unknown option",
+            ConfigError::InvalidValue(_) => "Invalid value",
+            ConfigError::OutOfRange(_) => "Out of range",
+            ConfigError::ParseError(_) => "Parse error",
+            ConfigError::UnsupportedFormat(_) => "Unsupported format",
+            ConfigError::IoError(_) => "IO error",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        match *self {
+            ConfigError::IoError(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(err: io::Error) -> ConfigError {
+        ConfigError::IoError(err)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(err: toml::de::Error) -> ConfigError {
+        ConfigError::ParseError(err.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    /// The dynamic library could not be loaded
+    LoadError(String),
+
+    /// The library doesn't export a plugin declaration
+    MissingEntryPoint,
+
+    /// The plugin was built for another version of the plugin API or of Nazara
+    IncompatibleVersion(String),
+
+    /// No plugin is loaded with this identifier
+    NotLoaded,
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            PluginError::LoadError(ref e) => write!(fmt, "Failed to load plugin: {}", e),
+            PluginError::MissingEntryPoint => {
+                write!(fmt, "The library is not a Nazara plugin")
+            }
+            PluginError::IncompatibleVersion(ref e) => {
+                write!(fmt, "Incompatible plugin version: {}", e)
+            }
+            PluginError::NotLoaded => write!(fmt, "The plugin is not loaded"),
+        }
+    }
+}
+
+impl Error for PluginError {
+    fn description(&self) -> &str {
+        match *self {
+            PluginError::LoadError(_) => "Load error",
+            PluginError::MissingEntryPoint => "Missing entry point",
+            PluginError::IncompatibleVersion(_) => "Incompatible version",
+            PluginError::NotLoaded => "Not loaded",
+        }
+    }
+}
+
+impl From<libloading::Error> for PluginError {
+    fn from(err: libloading::Error) -> PluginError {
+        PluginError::LoadError(err.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub enum MemoryError {
+    /// All the memory categories are already registered
+    TooManyCategories,
+}
+
+impl fmt::Display for MemoryError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            MemoryError::TooManyCategories => write!(
+                fmt,
+                "No more than {} memory categories can be registered",
+                crate::memory::MAX_CATEGORIES
+            ),
+        }
+    }
+}
+
+impl Error for MemoryError {
+    fn description(&self) -> &str {
+        match *self {
+            MemoryError::TooManyCategories => "Too many categories",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PlatformError {
+    /// The windowing system couldn't be reached, as on machines without a display
+    NoDisplay(String),
+
+    /// The event loop is no longer available, once the application started running
+    EventLoopUnavailable,
+
+    /// The platform refused to create a window
+    WindowCreation(String),
+
+    /// The operation is not supported by the platform or the backend
+    Unsupported(String),
+
+    /// A gamepad mapping string couldn't be parsed
+    InvalidMapping(String),
+
+    /// The clipboard couldn't be read or written
+    Clipboard(String),
+}
+
+impl fmt::Display for PlatformError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            PlatformError::NoDisplay(ref reason) => {
+                write!(fmt, "No display available: {}", reason)
+            }
+            PlatformError::EventLoopUnavailable => write!(
+                fmt,
+                "The event loop is unavailable, windows must be created before running the application"
+            ),
+            PlatformError::WindowCreation(ref reason) => {
+                write!(fmt, "Failed to create window: {}", reason)
+            }
+            PlatformError::Unsupported(ref reason) => write!(fmt, "Unsupported: {}", reason),
+            PlatformError::InvalidMapping(ref reason) => {
+                write!(fmt, "Invalid gamepad mapping: {}", reason)
+            }
+            PlatformError::Clipboard(ref reason) => write!(fmt, "Clipboard error: {}", reason),
+        }
+    }
+}
+
+impl Error for PlatformError {
+    fn description(&self) -> &str {
+        match *self {
+            PlatformError::NoDisplay(_) => "No display",
+            PlatformError::EventLoopUnavailable => "Event loop unavailable",
+            PlatformError::WindowCreation(_) => "Window creation failed",
+            PlatformError::Unsupported(_) => "Unsupported operation",
+            PlatformError::InvalidMapping(_) => "Invalid gamepad mapping",
+            PlatformError::Clipboard(_) => "Clipboard error",
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    errors::{ImageError, NazaraError, NazaraResult, ResourceError},
+    image::{Image, ImageLoader},
+};
+
+/// Shared handle to an [`Image`] which is updated in place when its file is reloaded
+pub type ImageHandle = Arc<RwLock<Image>>;
+
+/// Callback called after each reload attempt of a watched image
+pub type ReloadCallback = Box<dyn FnMut(&Path, Result<&ImageHandle, &NazaraError>)>;
+
+/// Last known state of a polled file
+#[derive(PartialEq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<FileStamp> {
+        fs::metadata(path).ok().map(|m| FileStamp {
+            modified: m.modified().ok(),
+            len: m.len(),
+        })
+    }
+}
+
+enum FileWatcher {
+    Native {
+        watcher: RecommendedWatcher,
+        events: Receiver<DebouncedEvent>,
+        directories: HashMap<PathBuf, usize>,
+    },
+    Polling {
+        interval: Duration,
+        last_check: Instant,
+        stamps: HashMap<PathBuf, Option<FileStamp>>,
+    },
+}
+
+impl FileWatcher {
+    fn watch(&mut self, path: &Path) -> NazaraResult<()> {
+        match *self {
+            FileWatcher::Native {
+                ref mut watcher,
+                ref mut directories,
+                ..
+            } => {
+                // Watch the parent directory, as most editors save files by replacing them
+                let directory = path.parent().unwrap_or(path).to_path_buf();
+                let count = directories.entry(directory.clone()).or_insert(0);
+                if *count == 0 {
+                    watcher
+                        .watch(&directory, RecursiveMode::NonRecursive)
+                        .map_err(|e| NazaraError::from(ResourceError::from(e)))?;
+                }
+                *count += 1;
+            }
+            FileWatcher::Polling { ref mut stamps, .. } => {
+                stamps.insert(path.to_path_buf(), FileStamp::read(path));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unwatch(&mut self, path: &Path) -> NazaraResult<()> {
+        match *self {
+            FileWatcher::Native {
+                ref mut watcher,
+                ref mut directories,
+                ..
+            } => {
+                let directory = path.parent().unwrap_or(path).to_path_buf();
+                if let Some(count) = directories.get_mut(&directory) {
+                    *count -= 1;
+                    if *count == 0 {
+                        directories.remove(&directory);
+                        watcher
+                            .unwatch(&directory)
+                            .map_err(|e| NazaraError::from(ResourceError::from(e)))?;
+                    }
+                }
+            }
+            FileWatcher::Polling { ref mut stamps, .. } => {
+                stamps.remove(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the paths which changed since the last call
+    fn changed_paths(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+
+        match *self {
+            FileWatcher::Native { ref events, .. } => {
+                while let Ok(event) = events.try_recv() {
+                    match event {
+                        DebouncedEvent::Create(path)
+                        | DebouncedEvent::Write(path)
+                        | DebouncedEvent::Rename(_, path) => {
+                            let path = fs::canonicalize(&path).unwrap_or(path);
+                            if !changed.contains(&path) {
+                                changed.push(path);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            FileWatcher::Polling {
+                interval,
+                ref mut last_check,
+                ref mut stamps,
+            } => {
+                if last_check.elapsed() >= interval {
+                    *last_check = Instant::now();
+
+                    for (path, stamp) in stamps.iter_mut() {
+                        let current = FileStamp::read(path);
+                        if current.is_some() && current != *stamp {
+                            changed.push(path.clone());
+                        }
+                        *stamp = current;
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+struct WatchedImage {
+    handle: ImageHandle,
+    subscribers: Vec<ReloadCallback>,
+}
+
+/// Watches image files on disk and reloads them in place when they change
+///
+/// The native file watcher of the platform (inotify, FSEvents, ReadDirectoryChangesW) is used
+/// when available, otherwise files are polled at a fixed interval.
+///
+/// Reloads only happen when calling [`HotReloader::poll`], which is meant to be called once per
+/// frame from the main loop, so subscribers are always run on the thread owning the reloader.
+///
+/// ```
+/// use std::{fs, thread, time::{Duration, Instant}};
+/// use nazara_core::hot_reload::HotReloader;
+/// use nazara_core::enums::PixelFormatType;
+///
+/// let dir = std::env::temp_dir().join(format!("nazara_hot_reload_{}", std::process::id()));
+/// fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("texture.img");
+/// fs::copy("./test_ressources/image.png", &path).unwrap();
+///
+/// let mut reloader = HotReloader::new_polling(Duration::from_millis(10));
+/// let image = reloader.watch_image(&path).unwrap();
+/// assert_eq!(image.read().unwrap().get_pixel_format(), PixelFormatType::RGB8);
+///
+/// fs::copy("./test_ressources/image.gif", &path).unwrap();
+///
+/// let start = Instant::now();
+/// while reloader.poll() == 0 && start.elapsed() < Duration::from_secs(10) {
+///     thread::sleep(Duration::from_millis(10));
+/// }
+///
+/// // The handle we got earlier now holds the new content
+/// assert_eq!(image.read().unwrap().get_pixel_format(), PixelFormatType::RGBA8);
+/// fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct HotReloader {
+    watcher: FileWatcher,
+    images: HashMap<PathBuf, WatchedImage>,
+}
+
+impl HotReloader {
+    /// Creates a new hot reloader using the native file watcher of the platform, falling back
+    /// to polling if it is not available.
+    ///
+    /// # Arguments
+    /// * `delay` - Delay used to debounce file events (and polling interval for the fallback)
+    pub fn new(delay: Duration) -> HotReloader {
+        let (tx, events) = channel();
+        match notify::watcher(tx, delay) {
+            Ok(watcher) => HotReloader::from_watcher(FileWatcher::Native {
+                watcher,
+                events,
+                directories: HashMap::new(),
+            }),
+            Err(_) => HotReloader::new_polling(delay),
+        }
+    }
+
+    /// Creates a new hot reloader which polls watched files for modifications
+    ///
+    /// # Arguments
+    /// * `interval` - Minimum interval between two checks of the watched files
+    pub fn new_polling(interval: Duration) -> HotReloader {
+        HotReloader::from_watcher(FileWatcher::Polling {
+            interval,
+            last_check: Instant::now(),
+            stamps: HashMap::new(),
+        })
+    }
+
+    fn from_watcher(watcher: FileWatcher) -> HotReloader {
+        HotReloader {
+            watcher,
+            images: HashMap::new(),
+        }
+    }
+
+    /// Loads an image from a file and watches it for modifications
+    ///
+    /// Watching the same file twice returns the same handle.
+    ///
+    /// # Arguments
+    /// * `file` - [`std::path::Path`] of file to load
+    pub fn watch_image(&mut self, file: &Path) -> NazaraResult<ImageHandle> {
+        let path = fs::canonicalize(file).map_err(|e| NazaraError::from(ImageError::from(e)))?;
+        if let Some(watched) = self.images.get(&path) {
+            return Ok(watched.handle.clone());
+        }
+
+        let handle = Arc::new(RwLock::new(ImageLoader::load_from_file(&path)?));
+        self.watcher.watch(&path)?;
+        self.images.insert(
+            path,
+            WatchedImage {
+                handle: handle.clone(),
+                subscribers: Vec::new(),
+            },
+        );
+
+        Ok(handle)
+    }
+
+    /// Stops watching an image file, its handle is kept alive but won't be updated anymore
+    ///
+    /// # Arguments
+    /// * `file` - [`std::path::Path`] of the watched file
+    pub fn unwatch(&mut self, file: &Path) -> NazaraResult<()> {
+        let path = self.resolve(file)?;
+        self.images.remove(&path);
+        self.watcher.unwatch(&path)
+    }
+
+    /// Registers a callback called each time a watched image is reloaded (or fails to reload)
+    ///
+    /// ```
+    /// use std::{cell::Cell, fs, rc::Rc, thread, time::{Duration, Instant}};
+    /// use nazara_core::hot_reload::HotReloader;
+    /// use cgmath::Vector3;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("nazara_subscribe_{}", std::process::id()));
+    /// fs::create_dir_all(&dir).unwrap();
+    /// let path = dir.join("texture.png");
+    /// fs::copy("./test_ressources/image.png", &path).unwrap();
+    ///
+    /// let mut reloader = HotReloader::new(Duration::from_millis(50));
+    /// reloader.watch_image(&path).unwrap();
+    ///
+    /// let reloads = Rc::new(Cell::new(0));
+    /// let counter = reloads.clone();
+    /// reloader.subscribe(&path, move |_, result| {
+    ///     let image = result.unwrap().read().unwrap();
+    ///     assert_eq!(image.get_dims(), Vector3 { x: 800, y: 629, z: 1 });
+    ///     counter.set(counter.get() + 1);
+    /// }).unwrap();
+    ///
+    /// fs::copy("./test_ressources/image.gif", &path).unwrap();
+    ///
+    /// let start = Instant::now();
+    /// while reloads.get() == 0 && start.elapsed() < Duration::from_secs(10) {
+    ///     reloader.poll();
+    ///     thread::sleep(Duration::from_millis(10));
+    /// }
+    ///
+    /// assert!(reloads.get() >= 1);
+    /// fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    ///
+    /// # Arguments
+    /// * `file` - [`std::path::Path`] of the watched file
+    /// * `callback` - Function called with the path and the result of the reload
+    pub fn subscribe<F>(&mut self, file: &Path, callback: F) -> NazaraResult<()>
+    where
+        F: FnMut(&Path, Result<&ImageHandle, &NazaraError>) + 'static,
+    {
+        let path = self.resolve(file)?;
+        let watched = self.images.get_mut(&path).unwrap();
+        watched.subscribers.push(Box::new(callback));
+
+        Ok(())
+    }
+
+    /// Processes pending file events, reloading modified images and notifying their subscribers.
+    ///
+    /// If an image fails to reload, its handle keeps its previous content.
+    ///
+    /// Returns how many images were successfully reloaded.
+    pub fn poll(&mut self) -> usize {
+        let mut reloaded = 0;
+        for path in self.watcher.changed_paths() {
+            if let Some(watched) = self.images.get_mut(&path) {
+                let result = ImageLoader::load_from_file(&path).map(|image| {
+                    *watched.handle.write().unwrap() = image;
+                });
+
+                match result {
+                    Ok(()) => {
+                        reloaded += 1;
+                        for subscriber in &mut watched.subscribers {
+                            subscriber(&path, Ok(&watched.handle));
+                        }
+                    }
+                    Err(ref e) => {
+                        for subscriber in &mut watched.subscribers {
+                            subscriber(&path, Err(e));
+                        }
+                    }
+                }
+            }
+        }
+
+        reloaded
+    }
+
+    fn resolve(&self, file: &Path) -> NazaraResult<PathBuf> {
+        let path = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        if self.images.contains_key(&path) {
+            Ok(path)
+        } else {
+            Err(NazaraError::from(ResourceError::NotWatched(path)))
+        }
+    }
+}
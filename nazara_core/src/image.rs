@@ -1,7 +1,11 @@
 use std::{
-    fs::File,
+    fs::{self, File},
     io::{BufRead, BufReader, Cursor, Seek},
     path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use cgmath::Vector3;
@@ -9,7 +13,10 @@ use image::{io::Reader, DynamicImage, GenericImageView};
 
 use crate::{
     enums::{ImageType, PixelFormatType},
-    errors::{ImageError, NazaraError, NazaraResult},
+    errors::{ImageError, NazaraError, NazaraResult, ResourceError},
+    memory::MemoryCategory,
+    task::{TaskHandle, TaskScheduler},
+    vfs::Vfs,
 };
 
 /// Image structure for Nazarust
@@ -45,6 +52,7 @@ impl Image {
     ///
     /// [`ImageType::Single1D`]: crate::enums::ImageType::Single1D
     pub fn new_1d(format: PixelFormatType, width: usize) -> Image {
+        let _scope = MemoryCategory::IMAGE.enter();
         let size = PixelFormatType::compute_size(format, width);
 
         Image {
@@ -78,6 +86,7 @@ impl Image {
     ///
     /// [`ImageType::Single2D`]: crate::enums::ImageType::Single2D
     pub fn new_2d(format: PixelFormatType, width: usize, height: usize) -> Image {
+        let _scope = MemoryCategory::IMAGE.enter();
         let size = PixelFormatType::compute_size(format, width * height);
 
         Image {
@@ -112,6 +121,7 @@ impl Image {
     ///
     /// [`ImageType::Single3D`]: crate::enums::ImageType::Single3D
     pub fn new_3d(format: PixelFormatType, width: usize, height: usize, depth: usize) -> Image {
+        let _scope = MemoryCategory::IMAGE.enter();
         let size = PixelFormatType::compute_size(format, width * height * depth);
 
         Image {
@@ -302,6 +312,14 @@ impl Image {
         PixelFormatType::compute_size(self.pixel_format, dims.x * dims.y * dims.z)
     }
 
+    /// Return the pixels of the specified mipmap level of an [`Image`] instance.
+    ///
+    /// # Parameters
+    /// * `level` - Mipmap level
+    pub fn get_mipmap_content(&self, level: usize) -> &[u8] {
+        &self.content[level]
+    }
+
     /// Update the content (including all mipmaps) of an [`Image`] instance.
     pub fn update_content(&mut self, new_content: Vec<Vec<u8>>) {
         let level_count = self.content.len();
@@ -322,10 +340,112 @@ impl Image {
     }
 }
 
+/// Decoder for an image format not handled by [`ImageLoader`] itself
+///
+/// Loaders are registered with [`ImageLoader::register_format_loader`] and used for files
+/// having one of their extensions, before the built-in formats.
+pub trait ImageFormatLoader: Send + Sync {
+    /// Return true if this loader decodes files with this extension (lowercase, without dot)
+    fn supports_extension(&self, extension: &str) -> bool;
+
+    /// Decode an image from the content of a file
+    fn load(&self, data: &[u8]) -> NazaraResult<Image>;
+}
+
+/// Identifier of a registered [`ImageFormatLoader`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FormatLoaderId(u64);
+
+static FORMAT_LOADERS: RwLock<Vec<(FormatLoaderId, Arc<dyn ImageFormatLoader>)>> =
+    RwLock::new(Vec::new());
+static NEXT_FORMAT_LOADER_ID: AtomicU64 = AtomicU64::new(0);
+
 /// Image loader for Nazarust
 pub struct ImageLoader {}
 
 impl ImageLoader {
+    /// Register a loader for additional image formats
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use nazara_core::enums::PixelFormatType;
+    /// use nazara_core::errors::NazaraResult;
+    /// use nazara_core::image::{Image, ImageFormatLoader, ImageLoader};
+    ///
+    /// // Raw RGBA8 pixels, the first byte being the width
+    /// struct RawLoader;
+    ///
+    /// impl ImageFormatLoader for RawLoader {
+    ///     fn supports_extension(&self, extension: &str) -> bool {
+    ///         extension == "raw"
+    ///     }
+    ///
+    ///     fn load(&self, data: &[u8]) -> NazaraResult<Image> {
+    ///         let width = data[0] as usize;
+    ///         let height = (data.len() - 1) / (width * 4);
+    ///         let mut image = Image::new_2d(PixelFormatType::RGBA8, width, height);
+    ///         image.update_mipmap_content(0, data[1..].to_vec());
+    ///         Ok(image)
+    ///     }
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("nazara_doctest.raw");
+    /// std::fs::write(&path, [1, 0, 64, 128, 255]).unwrap();
+    ///
+    /// let id = ImageLoader::register_format_loader(RawLoader);
+    /// let image = ImageLoader::load_from_file(&path).unwrap();
+    /// assert_eq!(image.get_mipmap_content(0), &[0, 64, 128, 255]);
+    ///
+    /// assert!(ImageLoader::unregister_format_loader(id));
+    /// assert!(ImageLoader::load_from_file(&path).is_err());
+    /// std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Arguments
+    /// * `loader` - Loader to register
+    pub fn register_format_loader<L>(loader: L) -> FormatLoaderId
+    where
+        L: ImageFormatLoader + 'static,
+    {
+        ImageLoader::register_shared_format_loader(Arc::new(loader))
+    }
+
+    /// Register a loader for additional image formats which is already shared
+    ///
+    /// # Arguments
+    /// * `loader` - Loader to register
+    pub fn register_shared_format_loader(loader: Arc<dyn ImageFormatLoader>) -> FormatLoaderId {
+        let id = FormatLoaderId(NEXT_FORMAT_LOADER_ID.fetch_add(1, Ordering::Relaxed));
+        FORMAT_LOADERS.write().unwrap().push((id, loader));
+
+        id
+    }
+
+    /// Unregister a loader, return false if it was not registered
+    ///
+    /// # Arguments
+    /// * `id` - Identifier returned when registering the loader
+    pub fn unregister_format_loader(id: FormatLoaderId) -> bool {
+        let mut loaders = FORMAT_LOADERS.write().unwrap();
+        let count = loaders.len();
+        loaders.retain(|(loader_id, _)| *loader_id != id);
+
+        loaders.len() != count
+    }
+
+    /// Return the last registered loader supporting the extension of a path
+    fn find_format_loader(path: &str) -> Option<Arc<dyn ImageFormatLoader>> {
+        let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+
+        FORMAT_LOADERS
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|(_, loader)| loader.supports_extension(&extension))
+            .map(|(_, loader)| loader.clone())
+    }
+
     /// Load an image from file
     ///
     /// # Example
@@ -344,10 +464,88 @@ impl ImageLoader {
     /// # Arguments
     /// * `file` - [`std::path::Path`] of file to load
     pub fn load_from_file(file: &Path) -> NazaraResult<Image> {
+        if let Some(loader) = ImageLoader::find_format_loader(&file.to_string_lossy()) {
+            let content = fs::read(file).map_err(|e| NazaraError::from(ImageError::from(e)))?;
+            return loader.load(&content);
+        }
+
         let file = File::open(file).map_err(|e| NazaraError::from(ImageError::from(e)))?;
         ImageLoader::load_from_reader(BufReader::new(file))
     }
 
+    /// Load an image from file on a worker thread of a [`TaskScheduler`]
+    ///
+    /// The task reports its progress and stops as soon as possible when cancelled, in which
+    /// case its result is a [`ResourceError::Cancelled`] error.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use nazara_core::image::ImageLoader;
+    /// use nazara_core::task::TaskScheduler;
+    /// use nazara_core::enums::PixelFormatType;
+    /// let mut scheduler = TaskScheduler::new(1);
+    /// let handle = ImageLoader::load_from_file_async(&mut scheduler, Path::new("./test_ressources/image.png"));
+    ///
+    /// // Do something else while the image is loading
+    ///
+    /// let image = handle.wait().and_then(|loaded| loaded).unwrap();
+    /// assert_eq!(image.get_pixel_format(), PixelFormatType::RGB8);
+    /// assert_eq!(handle.get_progress(), 1.0);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `scheduler` - [`TaskScheduler`] running the task
+    /// * `file` - [`std::path::Path`] of file to load
+    pub fn load_from_file_async(
+        scheduler: &mut TaskScheduler,
+        file: &Path,
+    ) -> TaskHandle<NazaraResult<Image>> {
+        let file = file.to_path_buf();
+
+        scheduler.spawn(move |ctx| {
+            let content = fs::read(&file).map_err(|e| NazaraError::from(ImageError::from(e)))?;
+            if ctx.is_cancelled() {
+                return Err(NazaraError::from(ResourceError::Cancelled));
+            }
+
+            ctx.set_progress(0.5);
+            match ImageLoader::find_format_loader(&file.to_string_lossy()) {
+                Some(loader) => loader.load(&content),
+                None => ImageLoader::load_from_mem(&content),
+            }
+        })
+    }
+
+    /// Load an image from a file of a virtual filesystem
+    ///
+    /// # Example
+    /// ```
+    /// use nazara_core::image::{ImageLoader, Image};
+    /// use nazara_core::vfs::{DirectoryBackend, Vfs};
+    /// use nazara_core::enums::{PixelFormatType, ImageType};
+    /// use cgmath::Vector3;
+    /// let mut vfs = Vfs::new();
+    /// vfs.mount("assets", DirectoryBackend::new("./test_ressources")).unwrap();
+    ///
+    /// let image: Image = ImageLoader::load_from_vfs(&vfs, "assets:/image.png").unwrap();
+    ///
+    /// assert_eq!(image.get_pixel_format(), PixelFormatType::RGB8);
+    /// assert_eq!(image.get_image_type(), ImageType::Single2D);
+    /// assert_eq!(image.get_dims(), Vector3 { x: 800, y: 629, z:1 });
+    /// ```
+    ///
+    /// # Arguments
+    /// * `vfs` - [`Vfs`] containing the file
+    /// * `path` - Virtual path of file to load (`mount:/dir/file`)
+    pub fn load_from_vfs(vfs: &Vfs, path: &str) -> NazaraResult<Image> {
+        if let Some(loader) = ImageLoader::find_format_loader(path) {
+            return loader.load(&vfs.read(path)?);
+        }
+
+        ImageLoader::load_from_reader(vfs.open(path)?)
+    }
+
     /// Load an image from memory
     ///
     /// # Example
@@ -411,6 +609,7 @@ impl ImageLoader {
     ///
     /// * `reader` - Reader instance from which image will be loaded
     pub fn load_from_reader<R: BufRead + Seek>(reader: R) -> NazaraResult<Image> {
+        let _scope = MemoryCategory::IMAGE.enter();
         let reader = Reader::new(reader)
             .with_guessed_format()
             .map_err(|e| NazaraError::from(ImageError::from(e)))?;
@@ -432,3 +631,168 @@ impl ImageLoader {
         Ok(new_image)
     }
 }
+
+/// Images are serialized with their pixels stored as one byte blob per mipmap level, which
+/// binary formats store as is.
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use nazara_core::enums::PixelFormatType;
+/// use nazara_core::image::Image;
+///
+/// let mut image = Image::new_2d(PixelFormatType::RGBA8, 2, 1);
+/// image.update_mipmap_content(0, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+///
+/// let json = serde_json::to_string(&image).unwrap();
+/// let copy: Image = serde_json::from_str(&json).unwrap();
+/// assert_eq!(copy.get_dims(), image.get_dims());
+/// assert_eq!(copy.get_mipmap_content(0), image.get_mipmap_content(0));
+///
+/// // The size of the pixel data is checked
+/// let truncated = json.replace("255,255]", "255]");
+/// assert!(serde_json::from_str::<Image>(&truncated).is_err());
+///
+/// // And so are the dimensions
+/// let huge = json.replace("[2,1,1]", "[4294967296,4294967296,1]");
+/// assert!(serde_json::from_str::<Image>(&huge).is_err());
+/// let tall = json.replace("[2,1,1]", "[2,1,4]");
+/// assert!(serde_json::from_str::<Image>(&tall).is_err());
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+impl serde::Serialize for Image {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Blob<'a>(&'a [u8]);
+
+        impl serde::Serialize for Blob<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        let levels: Vec<Blob> = self.content.iter().map(|level| Blob(level)).collect();
+        let dimensions = [self.dimensions.x, self.dimensions.y, self.dimensions.z];
+
+        let mut state = serializer.serialize_struct("Image", 4)?;
+        state.serialize_field("image_type", &self.image_type)?;
+        state.serialize_field("pixel_format", &self.pixel_format)?;
+        state.serialize_field("dimensions", &dimensions)?;
+        state.serialize_field("levels", &levels)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Image {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Image, D::Error> {
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct Blob(Vec<u8>);
+
+        struct BlobVisitor;
+
+        impl<'de> Visitor<'de> for BlobVisitor {
+            type Value = Blob;
+
+            fn expecting(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                fmt.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Blob, E> {
+                Ok(Blob(bytes.to_vec()))
+            }
+
+            fn visit_byte_buf<E: Error>(self, bytes: Vec<u8>) -> Result<Blob, E> {
+                Ok(Blob(bytes))
+            }
+
+            // Text formats store bytes as sequences
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Blob, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+
+                Ok(Blob(bytes))
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for Blob {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Blob, D::Error> {
+                deserializer.deserialize_byte_buf(BlobVisitor)
+            }
+        }
+
+        #[derive(serde::Deserialize)]
+        struct ImageData {
+            image_type: ImageType,
+            pixel_format: PixelFormatType,
+            dimensions: [usize; 3],
+            levels: Vec<Blob>,
+        }
+
+        let data = ImageData::deserialize(deserializer)?;
+        if data.levels.is_empty() {
+            return Err(D::Error::custom("image has no mipmap level"));
+        }
+
+        let [width, height, depth] = data.dimensions;
+        let dimensions_match = match data.image_type {
+            ImageType::Single1D => height == 1 && depth == 1,
+            ImageType::Single2D | ImageType::Array1D => depth == 1,
+            ImageType::Cubemap => width == height && depth == 6,
+            ImageType::Single3D | ImageType::Array2D => true,
+        };
+        if !dimensions_match {
+            return Err(D::Error::custom(format!(
+                "{:?} image can't be {}x{}x{}",
+                data.image_type, width, height, depth
+            )));
+        }
+
+        // Each level halves the dimensions, until they are all 1
+        let largest = width.max(height).max(depth).max(1);
+        let max_level_count = (usize::BITS - largest.leading_zeros()) as usize;
+        if data.levels.len() > max_level_count {
+            return Err(D::Error::custom(format!(
+                "image has {} mipmap levels, at most {} are possible",
+                data.levels.len(),
+                max_level_count
+            )));
+        }
+
+        let image = Image {
+            dimensions: Vector3::new(width, height, depth),
+            content: data.levels.into_iter().map(|level| level.0).collect(),
+            image_type: data.image_type,
+            pixel_format: data.pixel_format,
+        };
+
+        // The dimensions are untrusted, the size of the levels is computed without overflowing
+        let bits_per_pixel = image.pixel_format.info().bits_per_pixel as usize;
+        for (level, content) in image.content.iter().enumerate() {
+            let dims = Image::compute_mipmap_dims(image.dimensions, level);
+            let expected = dims
+                .x
+                .checked_mul(dims.y)
+                .and_then(|count| count.checked_mul(dims.z))
+                .and_then(|count| count.checked_mul(bits_per_pixel))
+                .map(|bits| bits.div_ceil(8))
+                .ok_or_else(|| D::Error::custom("image dimensions are too large"))?;
+
+            if content.len() != expected {
+                return Err(D::Error::custom(format!(
+                    "mipmap level {} has {} bytes instead of {}",
+                    level,
+                    content.len(),
+                    expected
+                )));
+            }
+        }
+
+        Ok(image)
+    }
+}
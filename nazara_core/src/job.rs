@@ -0,0 +1,645 @@
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    mem,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    errors::{JobError, NazaraError, NazaraResult},
+    nz_profile_scope,
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct JobEntry {
+    job: Job,
+    counter: Option<JobCounter>,
+}
+
+thread_local! {
+    // Identifier of the job system owning this worker thread, and index of the worker
+    static CURRENT_WORKER: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+}
+
+struct Shared {
+    // One deque per worker: owners pop from the back, thieves steal from the front
+    local_queues: Vec<Mutex<VecDeque<JobEntry>>>,
+    // Jobs pushed from threads which are not workers
+    global_queue: Mutex<VecDeque<JobEntry>>,
+    queued: AtomicUsize,
+    sleep_lock: Mutex<()>,
+    wake_up: Condvar,
+    shutdown: AtomicBool,
+}
+
+impl Shared {
+    fn id(self: &Arc<Self>) -> usize {
+        Arc::as_ptr(self) as usize
+    }
+
+    fn current_worker(self: &Arc<Self>) -> Option<usize> {
+        let id = self.id();
+        CURRENT_WORKER.with(|w| match w.get() {
+            Some((system, index)) if system == id => Some(index),
+            _ => None,
+        })
+    }
+
+    fn push(self: &Arc<Self>, entry: JobEntry) {
+        // Counted before being queued, so the count never goes below zero when it is taken
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        match self.current_worker() {
+            Some(index) => self.local_queues[index].lock().unwrap().push_back(entry),
+            None => self.global_queue.lock().unwrap().push_back(entry),
+        }
+
+        let _guard = self.sleep_lock.lock().unwrap();
+        self.wake_up.notify_one();
+    }
+
+    fn find_job(self: &Arc<Self>) -> Option<JobEntry> {
+        if self.queued.load(Ordering::SeqCst) == 0 {
+            return None;
+        }
+
+        let worker = self.current_worker();
+        let entry = worker
+            .and_then(|index| self.local_queues[index].lock().unwrap().pop_back())
+            .or_else(|| self.global_queue.lock().unwrap().pop_front())
+            .or_else(|| {
+                // Steal from the other workers, starting after our own queue
+                let count = self.local_queues.len();
+                let start = worker.map(|i| i + 1).unwrap_or(0);
+                (0..count)
+                    .map(|offset| (start + offset) % count)
+                    .filter(|&index| Some(index) != worker)
+                    .find_map(|index| self.local_queues[index].lock().unwrap().pop_front())
+            });
+
+        if entry.is_some() {
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        entry
+    }
+
+    fn execute(self: &Arc<Self>, entry: JobEntry) {
+        nz_profile_scope!("job", "job");
+        let result = panic::catch_unwind(AssertUnwindSafe(entry.job));
+
+        if let Some(counter) = entry.counter {
+            if result.is_err() {
+                counter.inner.panicked.store(true, Ordering::SeqCst);
+            }
+            if counter.inner.remaining.fetch_sub(1, Ordering::SeqCst) == 1 {
+                // Wake up the threads parked while waiting on this counter
+                let _guard = self.sleep_lock.lock().unwrap();
+                self.wake_up.notify_all();
+            }
+        }
+    }
+
+    fn worker_loop(self: Arc<Self>, index: usize) {
+        CURRENT_WORKER.with(|w| w.set(Some((self.id(), index))));
+
+        while !self.shutdown.load(Ordering::SeqCst) {
+            match self.find_job() {
+                Some(entry) => self.execute(entry),
+                None => {
+                    let guard = self.sleep_lock.lock().unwrap();
+                    if self.queued.load(Ordering::SeqCst) == 0
+                        && !self.shutdown.load(Ordering::SeqCst)
+                    {
+                        let _ = self
+                            .wake_up
+                            .wait_timeout(guard, Duration::from_millis(10))
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct CounterInner {
+    remaining: AtomicUsize,
+    panicked: AtomicBool,
+}
+
+/// Counts the unfinished jobs of a group, used to wait on them
+///
+/// Cloning a counter gives another reference to the same count.
+#[derive(Clone)]
+pub struct JobCounter {
+    inner: Arc<CounterInner>,
+}
+
+impl JobCounter {
+    /// Creates a new counter without any job
+    pub fn new() -> JobCounter {
+        JobCounter {
+            inner: Arc::new(CounterInner {
+                remaining: AtomicUsize::new(0),
+                panicked: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Returns how many jobs of this counter are not finished yet
+    pub fn get_remaining(&self) -> usize {
+        self.inner.remaining.load(Ordering::SeqCst)
+    }
+
+    /// Returns true if all jobs of this counter are finished
+    pub fn is_done(&self) -> bool {
+        self.get_remaining() == 0
+    }
+
+    /// Returns true if a job of this counter panicked
+    pub fn has_panicked(&self) -> bool {
+        self.inner.panicked.load(Ordering::SeqCst)
+    }
+
+    fn add(&self, count: usize) {
+        self.inner.remaining.fetch_add(count, Ordering::SeqCst);
+    }
+}
+
+impl Default for JobCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Identifier of a job inside a [`JobGraph`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct JobId(usize);
+
+struct GraphNode {
+    job: Job,
+    dependents: Vec<usize>,
+    dependency_count: usize,
+}
+
+/// Set of jobs with ordering constraints, run with [`JobSystem::run_graph`]
+///
+/// A job starts only once all the jobs it depends on are finished.
+///
+/// ```
+/// use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+/// use nazara_core::job::{JobGraph, JobSystem};
+///
+/// let jobs = JobSystem::new(8);
+///
+/// for _ in 0..50 {
+///     // A long chain of jobs must run in order even if they are spread among workers
+///     let step = Arc::new(AtomicUsize::new(0));
+///     let mut graph = JobGraph::new();
+///     let mut previous = None;
+///     for i in 0..64 {
+///         let step = step.clone();
+///         let id = graph.add_job(move || {
+///             assert_eq!(step.fetch_add(1, Ordering::SeqCst), i);
+///         });
+///         if let Some(previous) = previous {
+///             graph.add_dependency(id, previous);
+///         }
+///         previous = Some(id);
+///     }
+///
+///     let counter = jobs.run_graph(graph).unwrap();
+///     jobs.wait(&counter);
+///     assert!(!counter.has_panicked());
+///     assert_eq!(step.load(Ordering::SeqCst), 64);
+/// }
+/// ```
+pub struct JobGraph {
+    nodes: Vec<GraphNode>,
+}
+
+impl JobGraph {
+    /// Creates a new empty graph
+    pub fn new() -> JobGraph {
+        JobGraph { nodes: Vec::new() }
+    }
+
+    /// Adds a job to the graph, returning its identifier
+    ///
+    /// # Arguments
+    /// * `job` - Function to run
+    pub fn add_job<F>(&mut self, job: F) -> JobId
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.nodes.push(GraphNode {
+            job: Box::new(job),
+            dependents: Vec::new(),
+            dependency_count: 0,
+        });
+
+        JobId(self.nodes.len() - 1)
+    }
+
+    /// Makes a job wait for another one to be finished before starting
+    ///
+    /// # Arguments
+    /// * `job` - Job which has to wait
+    /// * `dependency` - Job which has to be finished first
+    pub fn add_dependency(&mut self, job: JobId, dependency: JobId) {
+        assert!(job.0 < self.nodes.len(), "Invalid job id");
+        assert!(dependency.0 < self.nodes.len(), "Invalid job id");
+
+        self.nodes[dependency.0].dependents.push(job.0);
+        self.nodes[job.0].dependency_count += 1;
+    }
+
+    /// Returns how many jobs are in the graph
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the graph has no job
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn has_cycle(&self) -> bool {
+        let mut remaining: Vec<usize> = self.nodes.iter().map(|n| n.dependency_count).collect();
+        let mut ready: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| remaining[i] == 0)
+            .collect();
+
+        let mut visited = 0;
+        while let Some(index) = ready.pop() {
+            visited += 1;
+            for &dependent in &self.nodes[index].dependents {
+                remaining[dependent] -= 1;
+                if remaining[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        visited != self.nodes.len()
+    }
+}
+
+impl Default for JobGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A graph job waiting for its dependencies
+struct PendingNode {
+    job: Mutex<Option<Job>>,
+    remaining: AtomicUsize,
+    dependents: Vec<usize>,
+}
+
+/// Runs short CPU-bound jobs in parallel on a set of work-stealing worker threads
+///
+/// Each worker owns a queue of jobs, and steals from the other workers when its queue is
+/// empty. Threads waiting on a [`JobCounter`] execute pending jobs instead of blocking, so jobs
+/// can spawn and wait for other jobs.
+///
+/// ```
+/// use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+/// use nazara_core::job::{JobCounter, JobSystem};
+///
+/// let jobs = JobSystem::new(4);
+/// let counter = JobCounter::new();
+/// let total = Arc::new(AtomicUsize::new(0));
+///
+/// for i in 0..1000 {
+///     let total = total.clone();
+///     jobs.spawn(&counter, move || {
+///         total.fetch_add(i, Ordering::Relaxed);
+///     });
+/// }
+///
+/// jobs.wait(&counter);
+/// assert_eq!(total.load(Ordering::Relaxed), 499500);
+/// ```
+pub struct JobSystem {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl JobSystem {
+    /// Creates a new job system and starts its workers
+    ///
+    /// # Arguments
+    /// * `thread_count` - Number of worker threads, 0 to use the number of available cores
+    pub fn new(thread_count: usize) -> JobSystem {
+        let thread_count = if thread_count == 0 {
+            thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        } else {
+            thread_count
+        };
+
+        let shared = Arc::new(Shared {
+            local_queues: (0..thread_count)
+                .map(|_| Mutex::new(VecDeque::new()))
+                .collect(),
+            global_queue: Mutex::new(VecDeque::new()),
+            queued: AtomicUsize::new(0),
+            sleep_lock: Mutex::new(()),
+            wake_up: Condvar::new(),
+            shutdown: AtomicBool::new(false),
+        });
+
+        let workers = (0..thread_count)
+            .map(|index| {
+                let shared = shared.clone();
+                thread::Builder::new()
+                    .name(format!("nazara job worker #{}", index))
+                    .spawn(move || shared.worker_loop(index))
+                    .unwrap()
+            })
+            .collect();
+
+        JobSystem { shared, workers }
+    }
+
+    /// Returns how many worker threads this job system uses
+    pub fn get_thread_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues a job, incrementing the counter until it is finished
+    ///
+    /// A panicking job doesn't stop its worker, the panic is reported by
+    /// [`JobCounter::has_panicked`].
+    ///
+    /// # Arguments
+    /// * `counter` - Counter tracking the job
+    /// * `job` - Function to run
+    pub fn spawn<F>(&self, counter: &JobCounter, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        counter.add(1);
+        self.shared.push(JobEntry {
+            job: Box::new(job),
+            counter: Some(counter.clone()),
+        });
+    }
+
+    /// Waits for all jobs of a counter to be finished, running pending jobs meanwhile
+    ///
+    /// When no job is pending, the thread sleeps until a job is queued or the counter is done.
+    ///
+    /// # Arguments
+    /// * `counter` - Counter to wait on
+    pub fn wait(&self, counter: &JobCounter) {
+        while !counter.is_done() {
+            match self.shared.find_job() {
+                Some(entry) => self.shared.execute(entry),
+                None => {
+                    let guard = self.shared.sleep_lock.lock().unwrap();
+                    if self.shared.queued.load(Ordering::SeqCst) == 0 && !counter.is_done() {
+                        drop(self.shared.wake_up.wait(guard).unwrap());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Queues the jobs of a graph, the returned counter is done once all of them are finished
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use nazara_core::job::{JobGraph, JobSystem};
+    ///
+    /// let jobs = JobSystem::new(4);
+    ///
+    /// for _ in 0..100 {
+    ///     let order = Arc::new(Mutex::new(Vec::new()));
+    ///     let mut graph = JobGraph::new();
+    ///
+    ///     // physics -> (animation, audio) -> render
+    ///     let ids: Vec<_> = ["physics", "animation", "audio", "render"]
+    ///         .iter()
+    ///         .map(|&name| {
+    ///             let order = order.clone();
+    ///             graph.add_job(move || order.lock().unwrap().push(name))
+    ///         })
+    ///         .collect();
+    ///     graph.add_dependency(ids[1], ids[0]);
+    ///     graph.add_dependency(ids[2], ids[0]);
+    ///     graph.add_dependency(ids[3], ids[1]);
+    ///     graph.add_dependency(ids[3], ids[2]);
+    ///
+    ///     let counter = jobs.run_graph(graph).unwrap();
+    ///     jobs.wait(&counter);
+    ///
+    ///     let order = order.lock().unwrap();
+    ///     assert_eq!(order.len(), 4);
+    ///     assert_eq!(order[0], "physics");
+    ///     assert_eq!(order[3], "render");
+    /// }
+    ///
+    /// let mut cyclic = JobGraph::new();
+    /// let a = cyclic.add_job(|| ());
+    /// let b = cyclic.add_job(|| ());
+    /// cyclic.add_dependency(a, b);
+    /// cyclic.add_dependency(b, a);
+    /// assert!(jobs.run_graph(cyclic).is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `graph` - Jobs to run
+    pub fn run_graph(&self, graph: JobGraph) -> NazaraResult<JobCounter> {
+        if graph.has_cycle() {
+            return Err(NazaraError::from(JobError::CyclicDependency));
+        }
+
+        let counter = JobCounter::new();
+        counter.add(graph.len());
+
+        let nodes: Arc<Vec<PendingNode>> = Arc::new(
+            graph
+                .nodes
+                .into_iter()
+                .map(|node| PendingNode {
+                    job: Mutex::new(Some(node.job)),
+                    remaining: AtomicUsize::new(node.dependency_count),
+                    dependents: node.dependents,
+                })
+                .collect(),
+        );
+
+        // Roots are collected first, as queued jobs may already release their dependents
+        let roots: Vec<usize> = (0..nodes.len())
+            .filter(|&index| nodes[index].remaining.load(Ordering::SeqCst) == 0)
+            .collect();
+
+        for index in roots {
+            JobSystem::push_node(&self.shared, &nodes, index, &counter);
+        }
+
+        Ok(counter)
+    }
+
+    fn push_node(
+        shared: &Arc<Shared>,
+        nodes: &Arc<Vec<PendingNode>>,
+        index: usize,
+        counter: &JobCounter,
+    ) {
+        let job = nodes[index].job.lock().unwrap().take().unwrap();
+        let (shared_ref, nodes_ref, counter_ref) = (shared.clone(), nodes.clone(), counter.clone());
+
+        shared.push(JobEntry {
+            job: Box::new(move || {
+                // Dependents are released even if the job panics
+                let result = panic::catch_unwind(AssertUnwindSafe(job));
+
+                for &dependent in &nodes_ref[index].dependents {
+                    if nodes_ref[dependent]
+                        .remaining
+                        .fetch_sub(1, Ordering::SeqCst)
+                        == 1
+                    {
+                        JobSystem::push_node(&shared_ref, &nodes_ref, dependent, &counter_ref);
+                    }
+                }
+
+                if let Err(e) = result {
+                    panic::resume_unwind(e);
+                }
+            }),
+            counter: Some(counter.clone()),
+        });
+    }
+
+    /// Calls a function on each element of a slice, splitting it in jobs run by the workers, and
+    /// waits for all of them to be processed
+    ///
+    /// The calling thread runs pending jobs while waiting, so a job can call this function
+    /// without starting any thread. A panic of the function is propagated once all the jobs are
+    /// finished.
+    ///
+    /// ```
+    /// use std::panic::{self, AssertUnwindSafe};
+    /// use std::thread;
+    /// use nazara_core::job::JobSystem;
+    ///
+    /// let jobs = JobSystem::new(4);
+    /// let mut positions = vec![0.0f32; 10_000];
+    ///
+    /// jobs.parallel_for(&mut positions, 256, |index, position| {
+    ///     *position = index as f32 * 2.0;
+    /// });
+    ///
+    /// assert!(positions.iter().enumerate().all(|(i, &p)| p == i as f32 * 2.0));
+    ///
+    /// // Only the workers and the calling thread process the elements
+    /// let mut names = vec![String::new(); 64];
+    /// jobs.parallel_for(&mut names, 1, |_, name| {
+    ///     *name = thread::current().name().unwrap_or_default().to_string();
+    /// });
+    /// assert!(names.iter().all(|name| name == "main" || name.starts_with("nazara job worker")));
+    ///
+    /// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     jobs.parallel_for(&mut positions, 256, |index, _| assert_ne!(index, 5000));
+    /// }));
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `data` - Elements to process
+    /// * `chunk_size` - Number of elements processed by each job, 0 to choose automatically
+    /// * `func` - Function called with the index and a reference to each element
+    pub fn parallel_for<T, F>(&self, data: &mut [T], chunk_size: usize, func: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut T) + Sync,
+    {
+        if data.is_empty() {
+            return;
+        }
+
+        let chunk_size = if chunk_size == 0 {
+            data.len().div_ceil(self.get_thread_count() * 4).max(1)
+        } else {
+            chunk_size
+        };
+
+        let counter = JobCounter::new();
+        // The jobs borrow the data and the function, they must be finished before returning,
+        // even when unwinding
+        let waiter = WaitGuard {
+            jobs: self,
+            counter: &counter,
+        };
+
+        let func = &func;
+        for (index, chunk) in data.chunks_mut(chunk_size).enumerate() {
+            let offset = index * chunk_size;
+            let job: Box<dyn FnOnce() + Send + '_> = Box::new(move || {
+                nz_profile_scope!("job", "parallel_for");
+                for (i, element) in chunk.iter_mut().enumerate() {
+                    func(offset + i, element);
+                }
+            });
+
+            // Safety: only the lifetime is changed, the guard doesn't let this function end
+            // before the job is finished
+            let job = unsafe { mem::transmute::<Box<dyn FnOnce() + Send + '_>, Job>(job) };
+
+            counter.add(1);
+            self.shared.push(JobEntry {
+                job,
+                counter: Some(counter.clone()),
+            });
+        }
+
+        drop(waiter);
+        if counter.has_panicked() {
+            panic!("a job of parallel_for panicked");
+        }
+    }
+}
+
+/// Waits on a counter when dropped
+struct WaitGuard<'a> {
+    jobs: &'a JobSystem,
+    counter: &'a JobCounter,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.jobs.wait(self.counter);
+    }
+}
+
+impl Default for JobSystem {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Drop for JobSystem {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        {
+            let _guard = self.shared.sleep_lock.lock().unwrap();
+            self.shared.wake_up.notify_all();
+        }
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -1,8 +1,23 @@
 pub mod application;
 pub mod color;
+pub mod config;
+pub mod ecs;
 pub mod enums;
 pub mod errors;
+pub mod hot_reload;
 pub mod image;
+pub mod job;
+pub mod log;
+pub mod math;
+pub mod memory;
+pub mod noise;
 pub mod pixel_formats;
+pub mod profiler;
+pub mod random;
+pub mod scene;
+pub mod signal;
+pub mod stream;
+pub mod task;
+pub mod vfs;
 
 extern crate cgmath;
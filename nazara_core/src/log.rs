@@ -0,0 +1,574 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock, RwLock},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents the severity of a log message
+pub enum LogLevel {
+    /// Very detailed messages, usually disabled
+    Trace,
+    /// Messages useful when debugging
+    Debug,
+    /// Informative messages
+    Info,
+    /// Something unexpected happened but the engine can continue
+    Warning,
+    /// Something failed
+    Error,
+}
+
+impl LogLevel {
+    /// Returns the name of the level as displayed in formatted messages
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warning => "WARNING",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str(self.name())
+    }
+}
+
+/// A message sent to the log sinks
+pub struct LogRecord<'a> {
+    /// Severity of the message
+    pub level: LogLevel,
+    /// Subsystem which emitted the message (`core`, `platform`, ...)
+    pub category: &'a str,
+    /// Content of the message
+    pub message: fmt::Arguments<'a>,
+    /// Time at which the message was emitted
+    pub timestamp: SystemTime,
+    /// Name of the thread which emitted the message
+    pub thread_name: &'a str,
+}
+
+impl<'a> LogRecord<'a> {
+    /// Formats the record as `2019-11-20 14:02:11.327 [INFO] [category] (thread) message`
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use nazara_core::log::{LogLevel, LogRecord};
+    ///
+    /// let record = LogRecord {
+    ///     level: LogLevel::Warning,
+    ///     category: "render",
+    ///     message: format_args!("{} draw calls", 42),
+    ///     timestamp: UNIX_EPOCH + Duration::from_millis(1_574_258_531_327),
+    ///     thread_name: "main",
+    /// };
+    ///
+    /// assert_eq!(record.format(), "2019-11-20 14:02:11.327 [WARNING] [render] (main) 42 draw calls");
+    /// ```
+    pub fn format(&self) -> String {
+        format!(
+            "{} [{}] [{}] ({}) {}",
+            format_timestamp(self.timestamp),
+            self.level,
+            self.category,
+            self.thread_name,
+            self.message
+        )
+    }
+}
+
+/// Destination of log messages
+pub trait LogSink: Send + Sync {
+    /// Writes a message
+    fn write(&self, record: &LogRecord);
+
+    /// Flushes buffered messages
+    fn flush(&self) {}
+}
+
+/// Identifier of a sink registered with [`add_sink`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SinkId(u64);
+
+struct Logger {
+    default_level: LogLevel,
+    category_levels: HashMap<String, LogLevel>,
+    sinks: Vec<(SinkId, Arc<dyn LogSink>)>,
+    next_sink_id: u64,
+}
+
+fn logger() -> &'static RwLock<Logger> {
+    static LOGGER: OnceLock<RwLock<Logger>> = OnceLock::new();
+    LOGGER.get_or_init(|| {
+        RwLock::new(Logger {
+            default_level: LogLevel::Info,
+            category_levels: HashMap::new(),
+            sinks: vec![(SinkId(0), Arc::new(StderrSink::new()))],
+            next_sink_id: 1,
+        })
+    })
+}
+
+/// Adds a sink which will receive all enabled messages
+///
+/// Messages are written to a [`StderrSink`] until [`clear_sinks`] is called.
+///
+/// # Arguments
+/// * `sink` - Sink to add
+pub fn add_sink<S: LogSink + 'static>(sink: S) -> SinkId {
+    let mut logger = logger().write().unwrap();
+    let id = SinkId(logger.next_sink_id);
+    logger.next_sink_id += 1;
+    logger.sinks.push((id, Arc::new(sink)));
+
+    id
+}
+
+/// Removes a sink, returns false if it was not registered
+///
+/// # Arguments
+/// * `id` - Identifier returned by [`add_sink`]
+pub fn remove_sink(id: SinkId) -> bool {
+    let mut logger = logger().write().unwrap();
+    let count = logger.sinks.len();
+    logger.sinks.retain(|&(sink_id, _)| sink_id != id);
+
+    count != logger.sinks.len()
+}
+
+/// Removes all sinks, including the default [`StderrSink`]
+pub fn clear_sinks() {
+    logger().write().unwrap().sinks.clear();
+}
+
+/// Sets the minimum level of the messages of categories without a specific level
+///
+/// # Arguments
+/// * `level` - Minimum level of the messages to write
+pub fn set_level(level: LogLevel) {
+    logger().write().unwrap().default_level = level;
+}
+
+/// Sets the minimum level of the messages of a category
+///
+/// # Arguments
+/// * `category` - Category of the messages
+/// * `level` - Minimum level of the messages to write, `None` to use the default level
+pub fn set_category_level(category: &str, level: Option<LogLevel>) {
+    let mut logger = logger().write().unwrap();
+    match level {
+        Some(level) => {
+            logger.category_levels.insert(category.to_string(), level);
+        }
+        None => {
+            logger.category_levels.remove(category);
+        }
+    }
+}
+
+/// Returns true if messages of this level and category are written
+///
+/// # Arguments
+/// * `level` - Level of the message
+/// * `category` - Category of the message
+pub fn is_enabled(level: LogLevel, category: &str) -> bool {
+    let logger = logger().read().unwrap();
+    let min_level = logger
+        .category_levels
+        .get(category)
+        .copied()
+        .unwrap_or(logger.default_level);
+
+    level >= min_level
+}
+
+/// Sends a message to all sinks if its level is enabled for its category
+///
+/// The [`nz_log!`](crate::nz_log) family of macros should be preferred.
+///
+/// # Arguments
+/// * `level` - Level of the message
+/// * `category` - Category of the message
+/// * `message` - Content of the message
+pub fn log(level: LogLevel, category: &str, message: fmt::Arguments) {
+    if !is_enabled(level, category) {
+        return;
+    }
+
+    let current = thread::current();
+    let record = LogRecord {
+        level,
+        category,
+        message,
+        timestamp: SystemTime::now(),
+        thread_name: current.name().unwrap_or("unnamed"),
+    };
+
+    // Sinks are cloned so they may log themselves without deadlocking
+    let sinks: Vec<Arc<dyn LogSink>> = logger()
+        .read()
+        .unwrap()
+        .sinks
+        .iter()
+        .map(|(_, sink)| sink.clone())
+        .collect();
+
+    for sink in sinks {
+        sink.write(&record);
+    }
+}
+
+/// Flushes all sinks
+pub fn flush() {
+    for (_, sink) in logger().read().unwrap().sinks.iter() {
+        sink.flush();
+    }
+}
+
+/// Logs a message with a level and a category
+///
+/// ```
+/// use nazara_core::{nz_log, nz_info, nz_error};
+/// use nazara_core::log::LogLevel;
+///
+/// nz_log!(LogLevel::Debug, "core", "not shown by default");
+/// nz_info!("core", "loaded {} images", 3);
+/// nz_error!("platform", "failed to create window");
+/// ```
+#[macro_export]
+macro_rules! nz_log {
+    ($level:expr, $category:expr, $($arg:tt)+) => {
+        $crate::log::log($level, $category, format_args!($($arg)+))
+    };
+}
+
+/// Logs a message with the [`LogLevel::Trace`](crate::log::LogLevel::Trace) level
+#[macro_export]
+macro_rules! nz_trace {
+    ($category:expr, $($arg:tt)+) => {
+        $crate::nz_log!($crate::log::LogLevel::Trace, $category, $($arg)+)
+    };
+}
+
+/// Logs a message with the [`LogLevel::Debug`](crate::log::LogLevel::Debug) level
+#[macro_export]
+macro_rules! nz_debug {
+    ($category:expr, $($arg:tt)+) => {
+        $crate::nz_log!($crate::log::LogLevel::Debug, $category, $($arg)+)
+    };
+}
+
+/// Logs a message with the [`LogLevel::Info`](crate::log::LogLevel::Info) level
+#[macro_export]
+macro_rules! nz_info {
+    ($category:expr, $($arg:tt)+) => {
+        $crate::nz_log!($crate::log::LogLevel::Info, $category, $($arg)+)
+    };
+}
+
+/// Logs a message with the [`LogLevel::Warning`](crate::log::LogLevel::Warning) level
+#[macro_export]
+macro_rules! nz_warning {
+    ($category:expr, $($arg:tt)+) => {
+        $crate::nz_log!($crate::log::LogLevel::Warning, $category, $($arg)+)
+    };
+}
+
+/// Logs a message with the [`LogLevel::Error`](crate::log::LogLevel::Error) level
+#[macro_export]
+macro_rules! nz_error {
+    ($category:expr, $($arg:tt)+) => {
+        $crate::nz_log!($crate::log::LogLevel::Error, $category, $($arg)+)
+    };
+}
+
+/// Sink writing formatted messages to the standard error output
+pub struct StderrSink {}
+
+impl StderrSink {
+    pub fn new() -> StderrSink {
+        StderrSink {}
+    }
+}
+
+impl Default for StderrSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogSink for StderrSink {
+    fn write(&self, record: &LogRecord) {
+        let _ = writeln!(io::stderr().lock(), "{}", record.format());
+    }
+}
+
+/// Sink keeping the last formatted messages in memory, useful for in-game consoles and tests
+///
+/// Clones of a ring buffer sink share the same messages.
+///
+/// ```
+/// use nazara_core::{log, nz_debug, nz_info, nz_warning};
+/// use nazara_core::log::{LogLevel, RingBufferSink};
+///
+/// let buffer = RingBufferSink::new(2);
+/// log::clear_sinks();
+/// log::add_sink(buffer.clone());
+/// log::set_category_level("audio", Some(LogLevel::Warning));
+///
+/// nz_debug!("core", "filtered by the default level");
+/// nz_info!("audio", "filtered by the category level");
+/// nz_info!("core", "first");
+/// nz_info!("core", "second");
+/// nz_warning!("audio", "third");
+///
+/// let messages = buffer.get_messages();
+/// assert_eq!(messages.len(), 2);
+/// assert!(messages[0].ends_with("[INFO] [core] (main) second"));
+/// assert!(messages[1].ends_with("[WARNING] [audio] (main) third"));
+/// ```
+#[derive(Clone)]
+pub struct RingBufferSink {
+    capacity: usize,
+    messages: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl RingBufferSink {
+    /// Creates a new ring buffer sink
+    ///
+    /// # Arguments
+    /// * `capacity` - Number of messages kept, older ones being dropped
+    pub fn new(capacity: usize) -> RingBufferSink {
+        RingBufferSink {
+            capacity,
+            messages: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    /// Returns the kept messages, from the oldest to the newest
+    pub fn get_messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Removes all kept messages
+    pub fn clear(&self) {
+        self.messages.lock().unwrap().clear();
+    }
+}
+
+impl LogSink for RingBufferSink {
+    fn write(&self, record: &LogRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut messages = self.messages.lock().unwrap();
+        if messages.len() == self.capacity {
+            messages.pop_front();
+        }
+        messages.push_back(record.format());
+    }
+}
+
+struct RotatingFile {
+    file: File,
+    size: u64,
+}
+
+/// Sink writing formatted messages to a file, which is rotated when it becomes too big
+///
+/// When rotating, `game.log` is renamed to `game.log.1`, `game.log.1` to `game.log.2` and so on,
+/// the oldest file being removed.
+///
+/// ```
+/// use std::fs;
+/// use nazara_core::{log, nz_info};
+/// use nazara_core::log::RotatingFileSink;
+///
+/// let dir = std::env::temp_dir().join(format!("nazara_log_{}", std::process::id()));
+/// fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("game.log");
+///
+/// log::clear_sinks();
+/// log::add_sink(RotatingFileSink::new(&path, 100, 2).unwrap());
+/// for i in 0..10 {
+///     nz_info!("core", "message {}", i);
+/// }
+/// log::flush();
+///
+/// assert!(fs::read_to_string(&path).unwrap().contains("message 9"));
+/// assert!(dir.join("game.log.1").exists());
+/// assert!(dir.join("game.log.2").exists());
+/// assert!(!dir.join("game.log.3").exists());
+/// fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct RotatingFileSink {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    current: Mutex<RotatingFile>,
+}
+
+impl RotatingFileSink {
+    /// Opens (or creates) a log file, appending to it
+    ///
+    /// # Arguments
+    /// * `path` - [`std::path::Path`] of the log file
+    /// * `max_size` - Size in bytes above which the file is rotated
+    /// * `max_files` - Number of rotated files kept
+    pub fn new(path: &Path, max_size: u64, max_files: usize) -> io::Result<RotatingFileSink> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(RotatingFileSink {
+            path: path.to_path_buf(),
+            max_size,
+            max_files,
+            current: Mutex::new(RotatingFile { file, size }),
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+
+    fn rotate(&self, current: &mut RotatingFile) -> io::Result<()> {
+        current.file.flush()?;
+
+        if self.max_files == 0 {
+            current.file = File::create(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+            current.file = File::create(&self.path)?;
+        }
+
+        current.size = 0;
+        Ok(())
+    }
+}
+
+impl LogSink for RotatingFileSink {
+    fn write(&self, record: &LogRecord) {
+        let line = format!("{}\n", record.format());
+        let mut current = self.current.lock().unwrap();
+
+        if current.size > 0 && current.size + line.len() as u64 > self.max_size {
+            // If the rotation fails we keep writing to the current file
+            let _ = self.rotate(&mut current);
+        }
+
+        if current.file.write_all(line.as_bytes()).is_ok() {
+            current.size += line.len() as u64;
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.current.lock().unwrap().file.flush();
+    }
+}
+
+struct LogBridge {}
+
+impl ::log::Log for LogBridge {
+    fn enabled(&self, metadata: &::log::Metadata) -> bool {
+        is_enabled(LogLevel::from(metadata.level()), metadata.target())
+    }
+
+    fn log(&self, record: &::log::Record) {
+        log(
+            LogLevel::from(record.level()),
+            record.target(),
+            *record.args(),
+        );
+    }
+
+    fn flush(&self) {
+        flush();
+    }
+}
+
+impl From<::log::Level> for LogLevel {
+    fn from(level: ::log::Level) -> Self {
+        match level {
+            ::log::Level::Trace => LogLevel::Trace,
+            ::log::Level::Debug => LogLevel::Debug,
+            ::log::Level::Info => LogLevel::Info,
+            ::log::Level::Warn => LogLevel::Warning,
+            ::log::Level::Error => LogLevel::Error,
+        }
+    }
+}
+
+/// Forwards the messages of the `log` crate to the Nazara sinks, using their target as category
+///
+/// Returns false if another logger was already installed for the `log` crate.
+///
+/// ```
+/// use nazara_core::log::{self, RingBufferSink};
+///
+/// let buffer = RingBufferSink::new(8);
+/// log::clear_sinks();
+/// log::add_sink(buffer.clone());
+/// assert!(log::init_log_bridge());
+///
+/// ::log::warn!(target: "physics", "body {} is sleeping", 7);
+///
+/// assert!(buffer.get_messages()[0].ends_with("[WARNING] [physics] (main) body 7 is sleeping"));
+/// ```
+pub fn init_log_bridge() -> bool {
+    static BRIDGE: LogBridge = LogBridge {};
+
+    if ::log::set_logger(&BRIDGE).is_err() {
+        return false;
+    }
+
+    ::log::set_max_level(::log::LevelFilter::Trace);
+    true
+}
+
+/// Formats a time as `YYYY-MM-DD hh:mm:ss.mmm` (UTC)
+fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (days, day_seconds) = (seconds / 86400, seconds % 86400);
+
+    // Converts days since epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        day_seconds / 3600,
+        day_seconds / 60 % 60,
+        day_seconds % 60,
+        since_epoch.subsec_millis()
+    )
+}
@@ -0,0 +1,18 @@
+//! Geometry types used for bounding volumes, culling and picking
+//!
+//! [`Rect`] and [`Box3`] are generic over their component type so they can describe image
+//! regions (`Box3<usize>`, matching [`Image::get_dims`](crate::image::Image::get_dims)) as well
+//! as world space volumes (`Box3<f32>`), the other types use `f32`.
+
+mod box3;
+mod frustum;
+mod oriented_box;
+mod plane;
+mod ray;
+mod rect;
+mod sphere;
+
+pub use self::{
+    box3::Box3, frustum::Frustum, oriented_box::OrientedBox, plane::Plane, ray::Ray, rect::Rect,
+    sphere::Sphere,
+};
@@ -0,0 +1,314 @@
+use cgmath::{BaseNum, Matrix4, Point3, Transform, Vector3};
+
+use super::rect::{partial_max, partial_min};
+
+/// Axis-aligned box defined by its minimum corner and its size
+///
+/// With integer components it describes a region of an image, with `f32` components an
+/// axis-aligned bounding box. A box with a negative size is empty: it contains nothing and
+/// intersects nothing.
+///
+/// ```
+/// use cgmath::{Point3, Vector3};
+/// use nazara_core::image::Image;
+/// use nazara_core::enums::PixelFormatType;
+/// use nazara_core::math::Box3;
+///
+/// let image = Image::new_3d(PixelFormatType::RGBA8, 64, 64, 4);
+/// let whole = Box3::from_size(image.get_dims());
+/// let region = Box3::new(32, 32, 0, 64, 16, 1);
+///
+/// assert!(whole.contains_point(Point3::new(63, 63, 3)));
+/// assert!(!whole.contains(&region));
+/// assert_eq!(whole.intersection(&region), Some(Box3::new(32, 32, 0, 32, 16, 1)));
+/// assert_eq!(whole.get_volume(), 64 * 64 * 4);
+///
+/// let aabb = Box3::new(0.0, 0.0, 0.0, 2.0, 2.0, 2.0);
+/// let negative = Box3::new(2.0, 2.0, 2.0, -2.0, -2.0, -2.0);
+/// assert!(negative.is_empty());
+/// assert!(!negative.contains_point(Point3::new(1.0, 1.0, 1.0)));
+/// assert!(!aabb.contains(&negative));
+/// assert_eq!(aabb.intersection(&negative), None);
+/// assert_eq!(negative.merge(&aabb), aabb);
+/// assert_eq!(negative.get_volume(), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Box3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub width: T,
+    pub height: T,
+    pub depth: T,
+}
+
+impl<T: BaseNum> Box3<T> {
+    /// Creates a new box
+    ///
+    /// # Arguments
+    /// * `x` - Minimum X coordinate
+    /// * `y` - Minimum Y coordinate
+    /// * `z` - Minimum Z coordinate
+    /// * `width` - Size along the X axis
+    /// * `height` - Size along the Y axis
+    /// * `depth` - Size along the Z axis
+    pub fn new(x: T, y: T, z: T, width: T, height: T, depth: T) -> Box3<T> {
+        Box3 {
+            x,
+            y,
+            z,
+            width,
+            height,
+            depth,
+        }
+    }
+
+    /// Creates a box starting at the origin
+    ///
+    /// # Arguments
+    /// * `size` - Size of the box along each axis
+    pub fn from_size(size: Vector3<T>) -> Box3<T> {
+        Box3::new(T::zero(), T::zero(), T::zero(), size.x, size.y, size.z)
+    }
+
+    /// Creates the smallest box containing two points
+    ///
+    /// # Arguments
+    /// * `a` - First point
+    /// * `b` - Second point
+    pub fn from_extents(a: Point3<T>, b: Point3<T>) -> Box3<T> {
+        let min = Point3::new(
+            partial_min(a.x, b.x),
+            partial_min(a.y, b.y),
+            partial_min(a.z, b.z),
+        );
+        let max = Point3::new(
+            partial_max(a.x, b.x),
+            partial_max(a.y, b.y),
+            partial_max(a.z, b.z),
+        );
+
+        Box3::new(
+            min.x,
+            min.y,
+            min.z,
+            max.x - min.x,
+            max.y - min.y,
+            max.z - min.z,
+        )
+    }
+
+    /// Creates the smallest box containing a set of points, `None` if there is no point
+    ///
+    /// # Arguments
+    /// * `points` - Points to include
+    pub fn from_points<I: IntoIterator<Item = Point3<T>>>(points: I) -> Option<Box3<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut result = Box3::from_extents(first, first);
+        for point in points {
+            result.extend_to(point);
+        }
+
+        Some(result)
+    }
+
+    /// Returns the minimum corner
+    pub fn get_min(&self) -> Point3<T> {
+        Point3::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the maximum corner
+    pub fn get_max(&self) -> Point3<T> {
+        Point3::new(
+            self.x + self.width,
+            self.y + self.height,
+            self.z + self.depth,
+        )
+    }
+
+    /// Returns the size of the box
+    pub fn get_size(&self) -> Vector3<T> {
+        Vector3::new(self.width, self.height, self.depth)
+    }
+
+    /// Returns the center of the box, rounded down for integer types
+    pub fn get_center(&self) -> Point3<T> {
+        let two = T::one() + T::one();
+        Point3::new(
+            self.x + self.width / two,
+            self.y + self.height / two,
+            self.z + self.depth / two,
+        )
+    }
+
+    /// Returns the eight corners of the box
+    pub fn get_corners(&self) -> [Point3<T>; 8] {
+        let (min, max) = (self.get_min(), self.get_max());
+        [
+            Point3::new(min.x, min.y, min.z),
+            Point3::new(max.x, min.y, min.z),
+            Point3::new(min.x, max.y, min.z),
+            Point3::new(max.x, max.y, min.z),
+            Point3::new(min.x, min.y, max.z),
+            Point3::new(max.x, min.y, max.z),
+            Point3::new(min.x, max.y, max.z),
+            Point3::new(max.x, max.y, max.z),
+        ]
+    }
+
+    /// Returns the volume of the box, zero if it has a negative size
+    pub fn get_volume(&self) -> T {
+        if self.has_negative_size() {
+            return T::zero();
+        }
+
+        self.width * self.height * self.depth
+    }
+
+    /// Returns true if the box has no volume
+    pub fn is_empty(&self) -> bool {
+        self.width <= T::zero() || self.height <= T::zero() || self.depth <= T::zero()
+    }
+
+    /// Returns true if the size is negative along an axis, making the box empty
+    pub(crate) fn has_negative_size(&self) -> bool {
+        self.width < T::zero() || self.height < T::zero() || self.depth < T::zero()
+    }
+
+    /// Returns true if a point is inside the box, the maximum faces being excluded
+    ///
+    /// # Arguments
+    /// * `point` - Point to test
+    pub fn contains_point(&self, point: Point3<T>) -> bool {
+        let max = self.get_max();
+        point.x >= self.x
+            && point.y >= self.y
+            && point.z >= self.z
+            && point.x < max.x
+            && point.y < max.y
+            && point.z < max.z
+    }
+
+    /// Returns true if another box is entirely inside this one
+    ///
+    /// # Arguments
+    /// * `other` - Box to test
+    pub fn contains(&self, other: &Box3<T>) -> bool {
+        if self.has_negative_size() || other.has_negative_size() {
+            return false;
+        }
+
+        let (max, other_max) = (self.get_max(), other.get_max());
+        other.x >= self.x
+            && other.y >= self.y
+            && other.z >= self.z
+            && other_max.x <= max.x
+            && other_max.y <= max.y
+            && other_max.z <= max.z
+    }
+
+    /// Returns true if the boxes overlap
+    ///
+    /// # Arguments
+    /// * `other` - Box to test
+    pub fn intersects(&self, other: &Box3<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the overlapping part of the boxes, `None` if they don't overlap
+    ///
+    /// # Arguments
+    /// * `other` - Box to intersect with
+    pub fn intersection(&self, other: &Box3<T>) -> Option<Box3<T>> {
+        let (max, other_max) = (self.get_max(), other.get_max());
+        let min = Point3::new(
+            partial_max(self.x, other.x),
+            partial_max(self.y, other.y),
+            partial_max(self.z, other.z),
+        );
+        let max = Point3::new(
+            partial_min(max.x, other_max.x),
+            partial_min(max.y, other_max.y),
+            partial_min(max.z, other_max.z),
+        );
+
+        if min.x < max.x && min.y < max.y && min.z < max.z {
+            Some(Box3::from_extents(min, max))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest box containing both boxes
+    ///
+    /// # Arguments
+    /// * `other` - Box to merge with
+    pub fn merge(&self, other: &Box3<T>) -> Box3<T> {
+        if self.has_negative_size() {
+            return *other;
+        }
+        if other.has_negative_size() {
+            return *self;
+        }
+
+        let mut result = *self;
+        result.extend_to(other.get_min());
+        result.extend_to(other.get_max());
+
+        result
+    }
+
+    /// Grows the box so it contains a point
+    ///
+    /// # Arguments
+    /// * `point` - Point to include
+    pub fn extend_to(&mut self, point: Point3<T>) {
+        if self.has_negative_size() {
+            *self = Box3::from_extents(point, point);
+            return;
+        }
+
+        let (min, max) = (self.get_min(), self.get_max());
+        *self = Box3::from_extents(
+            Point3::new(
+                partial_min(min.x, point.x),
+                partial_min(min.y, point.y),
+                partial_min(min.z, point.z),
+            ),
+            Point3::new(
+                partial_max(max.x, point.x),
+                partial_max(max.y, point.y),
+                partial_max(max.z, point.z),
+            ),
+        );
+    }
+}
+
+impl Box3<f32> {
+    /// Returns the axis-aligned box containing this box once transformed, a box with a negative
+    /// size being returned as is
+    ///
+    /// ```
+    /// use cgmath::{assert_relative_eq, Deg, Matrix4, Point3};
+    /// use nazara_core::math::Box3;
+    ///
+    /// let aabb = Box3::new(0.0, 0.0, 0.0, 2.0, 1.0, 1.0);
+    /// let rotated = aabb.transform(&Matrix4::from_angle_z(Deg(90.0)));
+    ///
+    /// assert_relative_eq!(rotated.get_min(), Point3::new(-1.0, 0.0, 0.0), epsilon = 1e-5);
+    /// assert_relative_eq!(rotated.get_max(), Point3::new(0.0, 2.0, 1.0), epsilon = 1e-5);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `matrix` - Affine transformation to apply
+    pub fn transform(&self, matrix: &Matrix4<f32>) -> Box3<f32> {
+        if self.has_negative_size() {
+            return *self;
+        }
+
+        let corners = self.get_corners();
+        Box3::from_points(corners.iter().map(|&c| matrix.transform_point(c))).unwrap()
+    }
+}
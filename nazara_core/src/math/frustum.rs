@@ -0,0 +1,147 @@
+use cgmath::{Matrix, Matrix4, Point3, Vector4};
+
+use super::{box3::Box3, oriented_box::OrientedBox, plane::Plane, sphere::Sphere};
+use crate::enums::{FrustumPlane, IntersectionSide};
+
+/// Volume seen by a camera, bounded by six planes facing inward
+///
+/// ```
+/// use cgmath::{Deg, Matrix4, Point3, Vector3, perspective};
+/// use nazara_core::enums::IntersectionSide;
+/// use nazara_core::math::{Box3, Frustum, Sphere};
+///
+/// let projection = perspective(Deg(90.0), 1.0, 0.1, 100.0);
+/// let view = Matrix4::look_at(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(0.0, 0.0, -1.0),
+///     Vector3::unit_y(),
+/// );
+/// let frustum = Frustum::from_matrix(&(projection * view));
+///
+/// assert!(frustum.contains_point(Point3::new(0.0, 0.0, -10.0)));
+/// assert!(!frustum.contains_point(Point3::new(0.0, 0.0, 10.0)));
+/// assert!(!frustum.contains_point(Point3::new(0.0, 0.0, -200.0)));
+///
+/// let visible = Sphere::new(Point3::new(0.0, 0.0, -50.0), 1.0);
+/// let clipped = Sphere::new(Point3::new(10.0, 0.0, -10.0), 1.0);
+/// let hidden = Sphere::new(Point3::new(20.0, 0.0, -10.0), 1.0);
+/// assert_eq!(frustum.get_sphere_side(&visible), IntersectionSide::Inside);
+/// assert_eq!(frustum.get_sphere_side(&clipped), IntersectionSide::Intersecting);
+/// assert_eq!(frustum.get_sphere_side(&hidden), IntersectionSide::Outside);
+///
+/// let behind = Box3::new(-1.0, -1.0, 5.0, 2.0, 2.0, 2.0);
+/// assert_eq!(frustum.get_box_side(&behind), IntersectionSide::Outside);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frustum {
+    planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Creates a frustum from its planes, whose normals must point inside the frustum
+    ///
+    /// # Arguments
+    /// * `planes` - Planes indexed by [`Frustum::get_plane_index`]
+    pub fn new(planes: [Plane; 6]) -> Frustum {
+        Frustum { planes }
+    }
+
+    /// Extracts the frustum of a view-projection matrix
+    ///
+    /// The matrix is expected to map the visible volume to the OpenGL clip space (`z` between
+    /// `-w` and `w`), like the matrices built by [`cgmath::perspective`] and [`cgmath::ortho`].
+    /// Planes are given in the space the matrix transforms from: world space for a
+    /// view-projection matrix, view space for a projection matrix.
+    ///
+    /// # Arguments
+    /// * `matrix` - Matrix transforming to clip space
+    pub fn from_matrix(matrix: &Matrix4<f32>) -> Frustum {
+        let row = |i: usize| matrix.row(i);
+        let plane = |v: Vector4<f32>| Plane::new(v.truncate(), -v.w);
+
+        let mut planes = [plane(row(3)); 6];
+        planes[Frustum::get_plane_index(FrustumPlane::Left)] = plane(row(3) + row(0));
+        planes[Frustum::get_plane_index(FrustumPlane::Right)] = plane(row(3) - row(0));
+        planes[Frustum::get_plane_index(FrustumPlane::Bottom)] = plane(row(3) + row(1));
+        planes[Frustum::get_plane_index(FrustumPlane::Top)] = plane(row(3) - row(1));
+        planes[Frustum::get_plane_index(FrustumPlane::Near)] = plane(row(3) + row(2));
+        planes[Frustum::get_plane_index(FrustumPlane::Far)] = plane(row(3) - row(2));
+
+        Frustum { planes }
+    }
+
+    /// Returns the index of a plane in the array given to [`Frustum::new`]
+    ///
+    /// # Arguments
+    /// * `plane` - Plane to get the index of
+    pub fn get_plane_index(plane: FrustumPlane) -> usize {
+        match plane {
+            FrustumPlane::Left => 0,
+            FrustumPlane::Right => 1,
+            FrustumPlane::Bottom => 2,
+            FrustumPlane::Top => 3,
+            FrustumPlane::Near => 4,
+            FrustumPlane::Far => 5,
+        }
+    }
+
+    /// Returns one of the planes of the frustum
+    ///
+    /// # Arguments
+    /// * `plane` - Plane to get
+    pub fn get_plane(&self, plane: FrustumPlane) -> &Plane {
+        &self.planes[Frustum::get_plane_index(plane)]
+    }
+
+    /// Returns true if a point is inside the frustum
+    ///
+    /// # Arguments
+    /// * `point` - Point to test
+    pub fn contains_point(&self, point: Point3<f32>) -> bool {
+        self.planes.iter().all(|p| p.get_distance(point) >= 0.0)
+    }
+
+    /// Returns the position of a sphere relative to the frustum
+    ///
+    /// # Arguments
+    /// * `sphere` - Sphere to test
+    pub fn get_sphere_side(&self, sphere: &Sphere) -> IntersectionSide {
+        Frustum::combine(self.planes.iter().map(|p| p.get_sphere_side(sphere)))
+    }
+
+    /// Returns the position of a box relative to the frustum
+    ///
+    /// # Arguments
+    /// * `aabb` - Box to test
+    pub fn get_box_side(&self, aabb: &Box3<f32>) -> IntersectionSide {
+        Frustum::combine(self.planes.iter().map(|p| p.get_box_side(aabb)))
+    }
+
+    /// Returns the position of an oriented box relative to the frustum
+    ///
+    /// # Arguments
+    /// * `obb` - Oriented box to test
+    pub fn get_oriented_box_side(&self, obb: &OrientedBox) -> IntersectionSide {
+        let axes = obb.get_axes();
+        Frustum::combine(self.planes.iter().map(|p| {
+            let radius = obb.get_projected_radius(&axes, p.normal);
+            Plane::side(p.get_distance(obb.center), radius)
+        }))
+    }
+
+    // Volumes close to the corners of the frustum can be reported as intersecting while
+    // being outside, which is fine for culling
+    fn combine<I: Iterator<Item = IntersectionSide>>(sides: I) -> IntersectionSide {
+        let mut result = IntersectionSide::Inside;
+        for side in sides {
+            match side {
+                IntersectionSide::Outside => return IntersectionSide::Outside,
+                IntersectionSide::Intersecting => result = IntersectionSide::Intersecting,
+                IntersectionSide::Inside => (),
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,158 @@
+use cgmath::{InnerSpace, Matrix4, Point3, Quaternion, Rotation, Transform as _, Vector3};
+
+use super::box3::Box3;
+use crate::scene::Transform;
+
+/// Box rotated in space, defined by its center, half size and rotation
+///
+/// ```
+/// use cgmath::{Deg, Matrix4, Point3, Vector3};
+/// use nazara_core::math::{Box3, OrientedBox};
+///
+/// let local = Box3::new(-1.0, -0.5, -0.5, 2.0, 1.0, 1.0);
+/// let a = OrientedBox::from_box(&local, &Matrix4::from_angle_z(Deg(45.0)));
+/// let b = OrientedBox::from_box(&local, &Matrix4::from_translation(Vector3::new(1.5, 1.5, 0.0)));
+///
+/// assert!(a.contains_point(Point3::new(0.6, 0.6, 0.0)));
+/// assert!(!a.contains_point(Point3::new(0.9, 0.0, 0.0)));
+/// // Their bounding boxes overlap, but not the boxes themselves
+/// assert!(a.get_bounding_box().intersects(&b.get_bounding_box()));
+/// assert!(!a.intersects(&b));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrientedBox {
+    pub center: Point3<f32>,
+    pub half_extents: Vector3<f32>,
+    pub rotation: Quaternion<f32>,
+}
+
+impl OrientedBox {
+    /// Creates a new oriented box
+    ///
+    /// # Arguments
+    /// * `center` - Center of the box
+    /// * `half_extents` - Half of the size of the box along each of its axes
+    /// * `rotation` - Rotation of the box, expected to be normalized
+    pub fn new(
+        center: Point3<f32>,
+        half_extents: Vector3<f32>,
+        rotation: Quaternion<f32>,
+    ) -> OrientedBox {
+        OrientedBox {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// Creates the oriented box covering a transformed axis-aligned box
+    ///
+    /// Shearing can't be represented and is lost.
+    ///
+    /// # Arguments
+    /// * `aabb` - Box in local space
+    /// * `matrix` - Affine transformation from local to world space
+    pub fn from_box(aabb: &Box3<f32>, matrix: &Matrix4<f32>) -> OrientedBox {
+        let transform = Transform::from_matrix(matrix);
+        let half_size = aabb.get_size() / 2.0;
+
+        OrientedBox {
+            center: matrix.transform_point(aabb.get_center()),
+            half_extents: Vector3::new(
+                half_size.x * transform.scale.x.abs(),
+                half_size.y * transform.scale.y.abs(),
+                half_size.z * transform.scale.z.abs(),
+            ),
+            rotation: transform.rotation,
+        }
+    }
+
+    /// Returns the directions of the local X, Y and Z axes of the box
+    pub fn get_axes(&self) -> [Vector3<f32>; 3] {
+        [
+            self.rotation.rotate_vector(Vector3::unit_x()),
+            self.rotation.rotate_vector(Vector3::unit_y()),
+            self.rotation.rotate_vector(Vector3::unit_z()),
+        ]
+    }
+
+    /// Returns the eight corners of the box
+    pub fn get_corners(&self) -> [Point3<f32>; 8] {
+        let local = Box3::from_extents(
+            Point3::new(0.0, 0.0, 0.0) - self.half_extents,
+            Point3::new(0.0, 0.0, 0.0) + self.half_extents,
+        );
+
+        let mut corners = local.get_corners();
+        for corner in corners.iter_mut() {
+            *corner = self.center
+                + self
+                    .rotation
+                    .rotate_vector(corner.to_homogeneous().truncate());
+        }
+
+        corners
+    }
+
+    /// Returns the smallest axis-aligned box containing this box
+    pub fn get_bounding_box(&self) -> Box3<f32> {
+        Box3::from_points(self.get_corners().iter().cloned()).unwrap()
+    }
+
+    /// Converts a point to the space of the box, where the box is centered on the origin and
+    /// aligned with the axes
+    ///
+    /// # Arguments
+    /// * `point` - Point to convert
+    pub fn to_local_point(&self, point: Point3<f32>) -> Point3<f32> {
+        Point3::new(0.0, 0.0, 0.0) + self.rotation.invert().rotate_vector(point - self.center)
+    }
+
+    /// Returns true if a point is inside the box
+    ///
+    /// # Arguments
+    /// * `point` - Point to test
+    pub fn contains_point(&self, point: Point3<f32>) -> bool {
+        let local = self.to_local_point(point);
+        local.x.abs() <= self.half_extents.x
+            && local.y.abs() <= self.half_extents.y
+            && local.z.abs() <= self.half_extents.z
+    }
+
+    /// Returns the radius of the box projected on an axis
+    pub(crate) fn get_projected_radius(&self, axes: &[Vector3<f32>; 3], axis: Vector3<f32>) -> f32 {
+        self.half_extents.x * axes[0].dot(axis).abs()
+            + self.half_extents.y * axes[1].dot(axis).abs()
+            + self.half_extents.z * axes[2].dot(axis).abs()
+    }
+
+    /// Returns true if the boxes overlap
+    ///
+    /// # Arguments
+    /// * `other` - Box to test
+    pub fn intersects(&self, other: &OrientedBox) -> bool {
+        let (axes, other_axes) = (self.get_axes(), other.get_axes());
+        let offset = other.center - self.center;
+
+        // Separating axis theorem: the boxes are apart if their projections are disjoint on
+        // one of their face normals or on one of the cross products of their edges
+        let mut candidates = Vec::with_capacity(15);
+        candidates.extend_from_slice(&axes);
+        candidates.extend_from_slice(&other_axes);
+        for axis in axes.iter() {
+            for other_axis in other_axes.iter() {
+                let cross = axis.cross(*other_axis);
+                if cross.magnitude2() > 1e-6 {
+                    candidates.push(cross.normalize());
+                }
+            }
+        }
+
+        candidates.into_iter().all(|axis| {
+            offset.dot(axis).abs()
+                <= self.get_projected_radius(&axes, axis)
+                    + other.get_projected_radius(&other_axes, axis)
+        })
+    }
+}
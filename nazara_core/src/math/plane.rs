@@ -0,0 +1,150 @@
+use cgmath::{InnerSpace, Matrix, Matrix4, Point3, SquareMatrix, Transform, Vector3};
+
+use super::{box3::Box3, sphere::Sphere};
+use crate::enums::IntersectionSide;
+
+/// Infinite plane containing the points `p` such as `normal · p = distance`
+///
+/// The side the normal points to is the positive side, considered inside by intersection tests.
+///
+/// ```
+/// use cgmath::{Point3, Vector3};
+/// use nazara_core::enums::IntersectionSide;
+/// use nazara_core::math::{Box3, Plane, Sphere};
+///
+/// let ground = Plane::from_points(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(1.0, 0.0, 0.0),
+/// )
+/// .unwrap();
+/// assert_eq!(ground.normal, Vector3::new(0.0, 1.0, 0.0));
+/// assert_eq!(ground.get_distance(Point3::new(3.0, 2.0, 1.0)), 2.0);
+///
+/// let crate_box = Box3::new(0.0, -0.5, 0.0, 1.0, 1.0, 1.0);
+/// assert_eq!(ground.get_box_side(&crate_box), IntersectionSide::Intersecting);
+///
+/// let negative = Box3::new(1.0, 2.0, 1.0, -1.0, -1.0, -1.0);
+/// assert_eq!(ground.get_box_side(&negative), IntersectionSide::Outside);
+///
+/// let ball = Sphere::new(Point3::new(0.0, -3.0, 0.0), 1.0);
+/// assert_eq!(ground.get_sphere_side(&ball), IntersectionSide::Outside);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    pub normal: Vector3<f32>,
+    pub distance: f32,
+}
+
+impl Plane {
+    /// Creates a new plane, normalizing its equation
+    ///
+    /// # Arguments
+    /// * `normal` - Normal of the plane, doesn't have to be normalized
+    /// * `distance` - Signed distance from the origin to the plane, along the normal
+    pub fn new(normal: Vector3<f32>, distance: f32) -> Plane {
+        let length = normal.magnitude();
+        Plane {
+            normal: normal / length,
+            distance: distance / length,
+        }
+    }
+
+    /// Creates a plane going through a point
+    ///
+    /// # Arguments
+    /// * `point` - Point of the plane
+    /// * `normal` - Normal of the plane, doesn't have to be normalized
+    pub fn from_point_normal(point: Point3<f32>, normal: Vector3<f32>) -> Plane {
+        let normal = normal.normalize();
+        Plane {
+            normal,
+            distance: normal.dot(point - Point3::new(0.0, 0.0, 0.0)),
+        }
+    }
+
+    /// Creates the plane going through three points, `None` if they are aligned
+    ///
+    /// The normal points toward the side the points are seen in counter-clockwise order from.
+    ///
+    /// # Arguments
+    /// * `a` - First point
+    /// * `b` - Second point
+    /// * `c` - Third point
+    pub fn from_points(a: Point3<f32>, b: Point3<f32>, c: Point3<f32>) -> Option<Plane> {
+        let normal = (b - a).cross(c - a);
+        if normal.magnitude2() <= f32::EPSILON {
+            return None;
+        }
+
+        Some(Plane::from_point_normal(a, normal))
+    }
+
+    /// Returns the signed distance of a point to the plane, positive on the normal side
+    ///
+    /// # Arguments
+    /// * `point` - Point to measure
+    pub fn get_distance(&self, point: Point3<f32>) -> f32 {
+        self.normal.dot(point - Point3::new(0.0, 0.0, 0.0)) - self.distance
+    }
+
+    /// Returns the point of the plane closest to a point
+    ///
+    /// # Arguments
+    /// * `point` - Point to project
+    pub fn project_point(&self, point: Point3<f32>) -> Point3<f32> {
+        point - self.normal * self.get_distance(point)
+    }
+
+    /// Returns the side of the plane a box is on, a box with a negative size being outside
+    ///
+    /// # Arguments
+    /// * `aabb` - Box to test
+    pub fn get_box_side(&self, aabb: &Box3<f32>) -> IntersectionSide {
+        if aabb.has_negative_size() {
+            return IntersectionSide::Outside;
+        }
+
+        let half_size = aabb.get_size() / 2.0;
+        let radius = half_size.x * self.normal.x.abs()
+            + half_size.y * self.normal.y.abs()
+            + half_size.z * self.normal.z.abs();
+
+        Plane::side(self.get_distance(aabb.get_min() + half_size), radius)
+    }
+
+    /// Returns the side of the plane a sphere is on
+    ///
+    /// # Arguments
+    /// * `sphere` - Sphere to test
+    pub fn get_sphere_side(&self, sphere: &Sphere) -> IntersectionSide {
+        Plane::side(self.get_distance(sphere.center), sphere.radius)
+    }
+
+    /// Returns the plane once transformed
+    ///
+    /// # Arguments
+    /// * `matrix` - Invertible affine transformation to apply
+    pub fn transform(&self, matrix: &Matrix4<f32>) -> Plane {
+        let point =
+            matrix.transform_point(Point3::new(0.0, 0.0, 0.0) + self.normal * self.distance);
+        // Normals are transformed by the inverse transpose to stay orthogonal to the plane
+        let normal_matrix = matrix
+            .invert()
+            .unwrap_or_else(Matrix4::identity)
+            .transpose();
+
+        Plane::from_point_normal(point, normal_matrix.transform_vector(self.normal))
+    }
+
+    pub(crate) fn side(distance: f32, radius: f32) -> IntersectionSide {
+        if distance > radius {
+            IntersectionSide::Inside
+        } else if distance < -radius {
+            IntersectionSide::Outside
+        } else {
+            IntersectionSide::Intersecting
+        }
+    }
+}
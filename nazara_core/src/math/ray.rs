@@ -0,0 +1,183 @@
+use cgmath::{InnerSpace, Matrix4, Point3, Rotation, Transform, Vector3};
+
+use super::{box3::Box3, oriented_box::OrientedBox, plane::Plane, sphere::Sphere};
+
+/// Half-line starting at an origin and going in a direction, used for picking
+///
+/// Intersection tests return the distance along the ray to the first hit point, zero if the
+/// origin is inside the volume.
+///
+/// ```
+/// use cgmath::{Point3, Vector3};
+/// use nazara_core::math::{Box3, Plane, Ray, Sphere};
+///
+/// let ray = Ray::new(Point3::new(0.0, 0.0, -10.0), Vector3::new(0.0, 0.0, 2.0));
+/// assert_eq!(ray.direction, Vector3::new(0.0, 0.0, 1.0));
+///
+/// let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 2.0);
+/// assert_eq!(ray.intersect_sphere(&sphere), Some(8.0));
+///
+/// let aabb = Box3::new(-1.0, -1.0, 5.0, 2.0, 2.0, 2.0);
+/// assert_eq!(ray.intersect_box(&aabb), Some(15.0));
+/// assert_eq!(ray.get_point(15.0), Point3::new(0.0, 0.0, 5.0));
+///
+/// let behind = Plane::from_point_normal(Point3::new(0.0, 0.0, -20.0), Vector3::unit_z());
+/// assert_eq!(ray.intersect_plane(&behind), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray {
+    pub origin: Point3<f32>,
+    pub direction: Vector3<f32>,
+}
+
+impl Ray {
+    /// Creates a new ray, normalizing its direction
+    ///
+    /// # Arguments
+    /// * `origin` - Starting point of the ray
+    /// * `direction` - Direction of the ray, doesn't have to be normalized
+    pub fn new(origin: Point3<f32>, direction: Vector3<f32>) -> Ray {
+        Ray {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Creates a ray starting at a point and going through another one
+    ///
+    /// # Arguments
+    /// * `from` - Starting point of the ray
+    /// * `to` - Point the ray goes through
+    pub fn from_points(from: Point3<f32>, to: Point3<f32>) -> Ray {
+        Ray::new(from, to - from)
+    }
+
+    /// Returns the point at a distance along the ray
+    ///
+    /// # Arguments
+    /// * `distance` - Distance from the origin
+    pub fn get_point(&self, distance: f32) -> Point3<f32> {
+        self.origin + self.direction * distance
+    }
+
+    /// Returns the ray once transformed
+    ///
+    /// # Arguments
+    /// * `matrix` - Affine transformation to apply
+    pub fn transform(&self, matrix: &Matrix4<f32>) -> Ray {
+        Ray::new(
+            matrix.transform_point(self.origin),
+            matrix.transform_vector(self.direction),
+        )
+    }
+
+    /// Returns the distance to the plane, `None` if the ray is parallel to the plane or goes
+    /// away from it
+    ///
+    /// # Arguments
+    /// * `plane` - Plane to test
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let alignment = plane.normal.dot(self.direction);
+        if alignment.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        let distance = -plane.get_distance(self.origin) / alignment;
+        if distance >= 0.0 {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the distance to the sphere, `None` if the ray misses it
+    ///
+    /// # Arguments
+    /// * `sphere` - Sphere to test
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f32> {
+        let offset = self.origin - sphere.center;
+        let b = offset.dot(self.direction);
+        let c = offset.magnitude2() - sphere.radius * sphere.radius;
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+
+        let discriminant = b * b - c;
+        if b > 0.0 || discriminant < 0.0 {
+            return None;
+        }
+
+        Some(-b - discriminant.sqrt())
+    }
+
+    /// Returns the distance to the box, `None` if the ray misses it
+    ///
+    /// # Arguments
+    /// * `aabb` - Box to test
+    pub fn intersect_box(&self, aabb: &Box3<f32>) -> Option<f32> {
+        if aabb.has_negative_size() {
+            return None;
+        }
+
+        let (min, max) = (aabb.get_min(), aabb.get_max());
+        let mut near = 0.0f32;
+        let mut far = f32::INFINITY;
+
+        for axis in 0..3 {
+            let (origin, direction) = (self.origin[axis], self.direction[axis]);
+            if direction.abs() <= f32::EPSILON {
+                if origin < min[axis] || origin > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (min[axis] - origin) / direction;
+            let t2 = (max[axis] - origin) / direction;
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+            if near > far {
+                return None;
+            }
+        }
+
+        Some(near)
+    }
+
+    /// Returns the distance to the oriented box, `None` if the ray misses it
+    ///
+    /// ```
+    /// use cgmath::{assert_relative_eq, Deg, Point3, Quaternion, Rotation3, Vector3};
+    /// use nazara_core::math::{OrientedBox, Ray};
+    ///
+    /// let diamond = OrientedBox::new(
+    ///     Point3::new(5.0, 0.0, 0.0),
+    ///     Vector3::new(1.0, 1.0, 1.0),
+    ///     Quaternion::from_angle_z(Deg(45.0)),
+    /// );
+    ///
+    /// let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::unit_x());
+    /// let distance = ray.intersect_oriented_box(&diamond).unwrap();
+    /// assert_relative_eq!(distance, 5.0 - 2.0f32.sqrt(), epsilon = 1e-5);
+    ///
+    /// let above = Ray::new(Point3::new(0.0, 1.5, 0.0), Vector3::unit_x());
+    /// assert_eq!(above.intersect_oriented_box(&diamond), None);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `obb` - Oriented box to test
+    pub fn intersect_oriented_box(&self, obb: &OrientedBox) -> Option<f32> {
+        // Distances are kept as the box space is only rotated and translated
+        let local = Ray {
+            origin: obb.to_local_point(self.origin),
+            direction: obb.rotation.invert().rotate_vector(self.direction),
+        };
+        let extents = Box3::from_extents(
+            Point3::new(0.0, 0.0, 0.0) - obb.half_extents,
+            Point3::new(0.0, 0.0, 0.0) + obb.half_extents,
+        );
+
+        local.intersect_box(&extents)
+    }
+}
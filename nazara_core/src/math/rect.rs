@@ -0,0 +1,182 @@
+use cgmath::{BaseNum, Point2, Vector2};
+
+/// Axis-aligned rectangle defined by its top-left corner and its size
+///
+/// ```
+/// use cgmath::Point2;
+/// use nazara_core::math::Rect;
+///
+/// let screen = Rect::new(0, 0, 800, 600);
+/// let button = Rect::new(750, 10, 100, 40);
+///
+/// assert!(screen.contains_point(Point2::new(799, 599)));
+/// assert!(!screen.contains_point(Point2::new(800, 600)));
+/// assert!(!screen.contains(&button));
+/// assert_eq!(screen.intersection(&button), Some(Rect::new(750, 10, 50, 40)));
+/// assert_eq!(screen.merge(&button), Rect::new(0, 0, 850, 600));
+/// assert_eq!(screen.intersection(&Rect::new(900, 0, 10, 10)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect<T> {
+    pub x: T,
+    pub y: T,
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: BaseNum> Rect<T> {
+    /// Creates a new rectangle
+    ///
+    /// # Arguments
+    /// * `x` - Left coordinate
+    /// * `y` - Top coordinate
+    /// * `width` - Width of the rectangle
+    /// * `height` - Height of the rectangle
+    pub fn new(x: T, y: T, width: T, height: T) -> Rect<T> {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a rectangle starting at the origin
+    ///
+    /// # Arguments
+    /// * `size` - Width and height of the rectangle
+    pub fn from_size(size: Vector2<T>) -> Rect<T> {
+        Rect::new(T::zero(), T::zero(), size.x, size.y)
+    }
+
+    /// Creates the smallest rectangle containing two points
+    ///
+    /// # Arguments
+    /// * `a` - First point
+    /// * `b` - Second point
+    pub fn from_extents(a: Point2<T>, b: Point2<T>) -> Rect<T> {
+        let min = Point2::new(partial_min(a.x, b.x), partial_min(a.y, b.y));
+        let max = Point2::new(partial_max(a.x, b.x), partial_max(a.y, b.y));
+
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Returns the top-left corner
+    pub fn get_min(&self) -> Point2<T> {
+        Point2::new(self.x, self.y)
+    }
+
+    /// Returns the bottom-right corner
+    pub fn get_max(&self) -> Point2<T> {
+        Point2::new(self.x + self.width, self.y + self.height)
+    }
+
+    /// Returns the size of the rectangle
+    pub fn get_size(&self) -> Vector2<T> {
+        Vector2::new(self.width, self.height)
+    }
+
+    /// Returns the center of the rectangle, rounded down for integer types
+    pub fn get_center(&self) -> Point2<T> {
+        let two = T::one() + T::one();
+        Point2::new(self.x + self.width / two, self.y + self.height / two)
+    }
+
+    /// Returns the area of the rectangle
+    pub fn get_area(&self) -> T {
+        self.width * self.height
+    }
+
+    /// Returns true if the rectangle has no area
+    pub fn is_empty(&self) -> bool {
+        self.width <= T::zero() || self.height <= T::zero()
+    }
+
+    /// Returns true if a point is inside the rectangle, the bottom-right edges being excluded
+    ///
+    /// # Arguments
+    /// * `point` - Point to test
+    pub fn contains_point(&self, point: Point2<T>) -> bool {
+        let max = self.get_max();
+        point.x >= self.x && point.y >= self.y && point.x < max.x && point.y < max.y
+    }
+
+    /// Returns true if another rectangle is entirely inside this one
+    ///
+    /// # Arguments
+    /// * `other` - Rectangle to test
+    pub fn contains(&self, other: &Rect<T>) -> bool {
+        let (max, other_max) = (self.get_max(), other.get_max());
+        other.x >= self.x && other.y >= self.y && other_max.x <= max.x && other_max.y <= max.y
+    }
+
+    /// Returns true if the rectangles overlap
+    ///
+    /// # Arguments
+    /// * `other` - Rectangle to test
+    pub fn intersects(&self, other: &Rect<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the overlapping part of the rectangles, `None` if they don't overlap
+    ///
+    /// # Arguments
+    /// * `other` - Rectangle to intersect with
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let (max, other_max) = (self.get_max(), other.get_max());
+        let left = partial_max(self.x, other.x);
+        let top = partial_max(self.y, other.y);
+        let right = partial_min(max.x, other_max.x);
+        let bottom = partial_min(max.y, other_max.y);
+
+        if left < right && top < bottom {
+            Some(Rect::new(left, top, right - left, bottom - top))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rectangle containing both rectangles
+    ///
+    /// # Arguments
+    /// * `other` - Rectangle to merge with
+    pub fn merge(&self, other: &Rect<T>) -> Rect<T> {
+        let (max, other_max) = (self.get_max(), other.get_max());
+        Rect::from_extents(
+            Point2::new(partial_min(self.x, other.x), partial_min(self.y, other.y)),
+            Point2::new(
+                partial_max(max.x, other_max.x),
+                partial_max(max.y, other_max.y),
+            ),
+        )
+    }
+
+    /// Grows the rectangle so it contains a point
+    ///
+    /// # Arguments
+    /// * `point` - Point to include
+    pub fn extend_to(&mut self, point: Point2<T>) {
+        let max = self.get_max();
+        *self = Rect::from_extents(
+            Point2::new(partial_min(self.x, point.x), partial_min(self.y, point.y)),
+            Point2::new(partial_max(max.x, point.x), partial_max(max.y, point.y)),
+        );
+    }
+}
+
+pub(crate) fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+pub(crate) fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
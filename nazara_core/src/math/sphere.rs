@@ -0,0 +1,133 @@
+use cgmath::{InnerSpace, Matrix4, MetricSpace, Point3, Transform, Vector3};
+
+use super::box3::Box3;
+
+/// Sphere defined by its center and radius
+///
+/// ```
+/// use cgmath::Point3;
+/// use nazara_core::math::{Box3, Sphere};
+///
+/// let a = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0);
+/// let b = Sphere::new(Point3::new(4.0, 0.0, 0.0), 1.0);
+/// assert!(!a.intersects(&b));
+///
+/// let both = a.merge(&b);
+/// assert_eq!(both, Sphere::new(Point3::new(2.0, 0.0, 0.0), 3.0));
+/// assert!(both.contains(&a) && both.contains(&b));
+///
+/// assert!(a.intersects_box(&Box3::new(0.5, 0.5, -1.0, 2.0, 2.0, 2.0)));
+/// assert!(!a.intersects_box(&Box3::new(0.8, 0.8, 0.8, 2.0, 2.0, 2.0)));
+/// assert!(!a.intersects_box(&Box3::new(2.5, 2.5, 1.0, -2.0, -2.0, -2.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    pub center: Point3<f32>,
+    pub radius: f32,
+}
+
+impl Sphere {
+    /// Creates a new sphere
+    ///
+    /// # Arguments
+    /// * `center` - Center of the sphere
+    /// * `radius` - Radius of the sphere
+    pub fn new(center: Point3<f32>, radius: f32) -> Sphere {
+        Sphere { center, radius }
+    }
+
+    /// Creates the smallest sphere containing a box
+    ///
+    /// # Arguments
+    /// * `aabb` - Box to enclose
+    pub fn from_box(aabb: &Box3<f32>) -> Sphere {
+        Sphere::new(aabb.get_center(), aabb.get_size().magnitude() / 2.0)
+    }
+
+    /// Returns the smallest axis-aligned box containing the sphere
+    pub fn get_bounding_box(&self) -> Box3<f32> {
+        let extent = Vector3::new(self.radius, self.radius, self.radius);
+        Box3::from_extents(self.center - extent, self.center + extent)
+    }
+
+    /// Returns true if a point is inside the sphere
+    ///
+    /// # Arguments
+    /// * `point` - Point to test
+    pub fn contains_point(&self, point: Point3<f32>) -> bool {
+        self.center.distance2(point) <= self.radius * self.radius
+    }
+
+    /// Returns true if another sphere is entirely inside this one
+    ///
+    /// # Arguments
+    /// * `other` - Sphere to test
+    pub fn contains(&self, other: &Sphere) -> bool {
+        self.center.distance(other.center) + other.radius <= self.radius
+    }
+
+    /// Returns true if the spheres overlap
+    ///
+    /// # Arguments
+    /// * `other` - Sphere to test
+    pub fn intersects(&self, other: &Sphere) -> bool {
+        let radius = self.radius + other.radius;
+        self.center.distance2(other.center) <= radius * radius
+    }
+
+    /// Returns true if the sphere overlaps a box
+    ///
+    /// # Arguments
+    /// * `aabb` - Box to test
+    pub fn intersects_box(&self, aabb: &Box3<f32>) -> bool {
+        if aabb.has_negative_size() {
+            return false;
+        }
+
+        let (min, max) = (aabb.get_min(), aabb.get_max());
+        let closest = Point3::new(
+            self.center.x.clamp(min.x, max.x),
+            self.center.y.clamp(min.y, max.y),
+            self.center.z.clamp(min.z, max.z),
+        );
+
+        self.contains_point(closest)
+    }
+
+    /// Returns the smallest sphere containing both spheres
+    ///
+    /// # Arguments
+    /// * `other` - Sphere to merge with
+    pub fn merge(&self, other: &Sphere) -> Sphere {
+        let offset = other.center - self.center;
+        let distance = offset.magnitude();
+        if distance + other.radius <= self.radius {
+            return *self;
+        }
+        if distance + self.radius <= other.radius {
+            return *other;
+        }
+
+        let radius = (distance + self.radius + other.radius) / 2.0;
+        let center = self.center + offset * ((radius - self.radius) / distance);
+
+        Sphere::new(center, radius)
+    }
+
+    /// Returns the sphere once transformed, its radius being scaled by the largest scale of
+    /// the transformation
+    ///
+    /// # Arguments
+    /// * `matrix` - Affine transformation to apply
+    pub fn transform(&self, matrix: &Matrix4<f32>) -> Sphere {
+        let scale = matrix
+            .x
+            .truncate()
+            .magnitude()
+            .max(matrix.y.truncate().magnitude())
+            .max(matrix.z.truncate().magnitude());
+
+        Sphere::new(matrix.transform_point(self.center), self.radius * scale)
+    }
+}
@@ -0,0 +1,454 @@
+//! Memory instrumentation and specialized allocators
+//!
+//! [`TrackingAllocator`] counts the bytes allocated by the application per [`MemoryCategory`]
+//! once it is installed as the global allocator, [`Arena`], [`Pool`] and [`FrameAllocator`]
+//! avoid allocating for short-lived data.
+
+mod arena;
+mod frame;
+mod pool;
+
+pub use self::{
+    arena::Arena,
+    frame::FrameAllocator,
+    pool::{Pool, Pooled},
+};
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use crate::errors::{MemoryError, NazaraError, NazaraResult};
+
+/// Maximum number of categories, including the built-in ones
+pub const MAX_CATEGORIES: usize = 32;
+
+// Bytes reserved before each tracked allocation, the last one stores its category
+const HEADER_SIZE: usize = 16;
+
+struct CategoryCounters {
+    live_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    live_allocations: AtomicUsize,
+    total_allocations: AtomicUsize,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_COUNTERS: CategoryCounters = CategoryCounters {
+    live_bytes: AtomicUsize::new(0),
+    peak_bytes: AtomicUsize::new(0),
+    live_allocations: AtomicUsize::new(0),
+    total_allocations: AtomicUsize::new(0),
+};
+
+static COUNTERS: [CategoryCounters; MAX_CATEGORIES] = [EMPTY_COUNTERS; MAX_CATEGORIES];
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static CATEGORY_NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+thread_local! {
+    // Category given to the allocations made by this thread
+    static CURRENT_CATEGORY: Cell<u8> = const { Cell::new(0) };
+}
+
+/// Category memory is accounted to by the [`TrackingAllocator`]
+///
+/// ```
+/// use nazara_core::memory::MemoryCategory;
+///
+/// let audio = MemoryCategory::register("audio").unwrap();
+/// assert_eq!(audio.get_name(), "audio");
+/// assert_eq!(MemoryCategory::register("audio").unwrap(), audio);
+/// assert_eq!(MemoryCategory::IMAGE.get_name(), "image");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MemoryCategory(u8);
+
+impl MemoryCategory {
+    /// Allocations made outside of any category scope
+    pub const GENERAL: MemoryCategory = MemoryCategory(0);
+    /// Pixels of [`Image`](crate::image::Image)s
+    pub const IMAGE: MemoryCategory = MemoryCategory(1);
+
+    const BUILTIN_NAMES: [&'static str; 2] = ["general", "image"];
+
+    /// Returns the category with the given name, registering it if needed
+    ///
+    /// # Arguments
+    /// * `name` - Name of the category, displayed in statistics and leak reports
+    pub fn register(name: &'static str) -> NazaraResult<MemoryCategory> {
+        let mut names = category_names();
+        if let Some(index) = names.iter().position(|n| *n == name) {
+            return Ok(MemoryCategory(index as u8));
+        }
+
+        if names.len() >= MAX_CATEGORIES {
+            return Err(NazaraError::from(MemoryError::TooManyCategories));
+        }
+
+        names.push(name);
+
+        Ok(MemoryCategory((names.len() - 1) as u8))
+    }
+
+    /// Returns all the categories registered so far
+    pub fn get_all() -> Vec<MemoryCategory> {
+        (0..category_names().len())
+            .map(|index| MemoryCategory(index as u8))
+            .collect()
+    }
+
+    /// Returns the name of the category
+    pub fn get_name(self) -> &'static str {
+        category_names()[self.0 as usize]
+    }
+
+    /// Makes the allocations of the current thread accounted to this category until the
+    /// returned guard is dropped
+    ///
+    /// Memory stays accounted to the category it was allocated in, even when it is freed or
+    /// reallocated from another scope.
+    ///
+    /// ```
+    /// use nazara_core::memory::{self, MemoryCategory};
+    ///
+    /// {
+    ///     let _scope = MemoryCategory::IMAGE.enter();
+    ///     assert_eq!(memory::get_current_category(), MemoryCategory::IMAGE);
+    /// }
+    /// assert_eq!(memory::get_current_category(), MemoryCategory::GENERAL);
+    /// ```
+    pub fn enter(self) -> CategoryScope {
+        CategoryScope {
+            previous: CURRENT_CATEGORY.with(|current| current.replace(self.0)),
+        }
+    }
+}
+
+fn category_names() -> std::sync::MutexGuard<'static, Vec<&'static str>> {
+    let mut names = CATEGORY_NAMES.lock().unwrap();
+    if names.is_empty() {
+        names.extend_from_slice(&MemoryCategory::BUILTIN_NAMES);
+    }
+
+    names
+}
+
+/// Guard returned by [`MemoryCategory::enter`], restores the previous category when dropped
+pub struct CategoryScope {
+    previous: u8,
+}
+
+impl Drop for CategoryScope {
+    fn drop(&mut self) {
+        CURRENT_CATEGORY.with(|current| current.set(self.previous));
+    }
+}
+
+/// Returns the category the allocations of the current thread are accounted to
+pub fn get_current_category() -> MemoryCategory {
+    MemoryCategory(current_category())
+}
+
+fn current_category() -> u8 {
+    // The thread local may be unavailable while the thread is being destroyed
+    CURRENT_CATEGORY.try_with(Cell::get).unwrap_or(0)
+}
+
+/// Returns true if the [`TrackingAllocator`] is the global allocator and has been used
+pub fn is_tracking() -> bool {
+    TRACKING.load(Ordering::Relaxed)
+}
+
+/// Memory usage of a category
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MemoryStats {
+    /// Category of the statistics
+    pub category: MemoryCategory,
+    /// Bytes currently allocated
+    pub live_bytes: usize,
+    /// Maximum number of bytes allocated at the same time
+    pub peak_bytes: usize,
+    /// Number of allocations not freed yet
+    pub live_allocations: usize,
+    /// Number of allocations made since the start of the program
+    pub total_allocations: usize,
+}
+
+/// Returns the memory usage of a category, always zero if the [`TrackingAllocator`] is not
+/// installed
+///
+/// # Arguments
+/// * `category` - Category to query
+pub fn get_stats(category: MemoryCategory) -> MemoryStats {
+    let counters = &COUNTERS[category.0 as usize];
+
+    MemoryStats {
+        category,
+        live_bytes: counters.live_bytes.load(Ordering::Relaxed),
+        peak_bytes: counters.peak_bytes.load(Ordering::Relaxed),
+        live_allocations: counters.live_allocations.load(Ordering::Relaxed),
+        total_allocations: counters.total_allocations.load(Ordering::Relaxed),
+    }
+}
+
+/// Returns the memory usage of every registered category
+pub fn get_all_stats() -> Vec<MemoryStats> {
+    MemoryCategory::get_all()
+        .into_iter()
+        .map(get_stats)
+        .collect()
+}
+
+/// Memory still allocated in a category compared to a previous state
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Leak {
+    /// Category of the memory
+    pub category: MemoryCategory,
+    /// Number of bytes not freed
+    pub bytes: usize,
+    /// Number of allocations not freed
+    pub allocations: usize,
+}
+
+/// Categories whose memory usage grew since a baseline
+#[derive(Debug, Clone)]
+pub struct LeakReport {
+    leaks: Vec<Leak>,
+}
+
+impl LeakReport {
+    /// Compares the current memory usage to statistics returned by [`get_all_stats`]
+    ///
+    /// ```
+    /// use nazara_core::memory::{self, LeakReport};
+    ///
+    /// let baseline = memory::get_all_stats();
+    /// // The tracking allocator is not installed in this program, nothing is counted
+    /// let report = LeakReport::since(&baseline);
+    /// assert!(report.is_empty());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `baseline` - Statistics taken before the allocations to check
+    pub fn since(baseline: &[MemoryStats]) -> LeakReport {
+        let leaks = get_all_stats()
+            .into_iter()
+            .filter_map(|current| {
+                let (bytes, allocations) = baseline
+                    .iter()
+                    .find(|stats| stats.category == current.category)
+                    .map_or((0, 0), |stats| (stats.live_bytes, stats.live_allocations));
+
+                if current.live_bytes > bytes {
+                    Some(Leak {
+                        category: current.category,
+                        bytes: current.live_bytes - bytes,
+                        allocations: current.live_allocations.saturating_sub(allocations),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        LeakReport { leaks }
+    }
+
+    /// Removes a category from the report
+    ///
+    /// # Arguments
+    /// * `category` - Category whose leaks are expected
+    pub fn ignore(mut self, category: MemoryCategory) -> LeakReport {
+        self.leaks.retain(|leak| leak.category != category);
+        self
+    }
+
+    /// Returns the leaks found, one per category
+    pub fn get_leaks(&self) -> &[Leak] {
+        &self.leaks
+    }
+
+    /// Returns the leaked bytes of all categories
+    pub fn get_total_bytes(&self) -> usize {
+        self.leaks.iter().map(|leak| leak.bytes).sum()
+    }
+
+    /// Returns true if no memory leaked
+    pub fn is_empty(&self) -> bool {
+        self.leaks.is_empty()
+    }
+}
+
+impl fmt::Display for LeakReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.leaks.is_empty() {
+            return write!(fmt, "No memory leaked");
+        }
+
+        write!(fmt, "{} bytes leaked", self.get_total_bytes())?;
+        for leak in &self.leaks {
+            write!(
+                fmt,
+                "\n  {}: {} bytes in {} allocations",
+                leak.category.get_name(),
+                leak.bytes,
+                leak.allocations
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Global allocator counting the memory allocated in each [`MemoryCategory`]
+///
+/// Each allocation is prefixed by a small header storing its category, so tracking should only
+/// be enabled for development builds.
+///
+/// ```
+/// use nazara_core::enums::PixelFormatType;
+/// use nazara_core::image::Image;
+/// use nazara_core::memory::{self, MemoryCategory, TrackingAllocator};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator = TrackingAllocator::new();
+///
+/// fn main() {
+///     let before = memory::get_stats(MemoryCategory::IMAGE);
+///     let image = Image::new_2d(PixelFormatType::RGBA8, 16, 16);
+///
+///     let stats = memory::get_stats(MemoryCategory::IMAGE);
+///     assert!(memory::is_tracking());
+///     assert!(stats.live_bytes >= before.live_bytes + 16 * 16 * 4);
+///
+///     drop(image);
+///     assert_eq!(memory::get_stats(MemoryCategory::IMAGE).live_bytes, before.live_bytes);
+/// }
+/// ```
+pub struct TrackingAllocator<A = System> {
+    inner: A,
+}
+
+impl TrackingAllocator<System> {
+    /// Creates an allocator tracking the system allocator
+    pub const fn new() -> TrackingAllocator<System> {
+        TrackingAllocator { inner: System }
+    }
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Creates an allocator tracking another allocator
+    ///
+    /// # Arguments
+    /// * `inner` - Allocator doing the actual allocations
+    pub const fn with_allocator(inner: A) -> TrackingAllocator<A> {
+        TrackingAllocator { inner }
+    }
+}
+
+impl Default for TrackingAllocator<System> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn header_size(layout: Layout) -> usize {
+    layout.align().max(HEADER_SIZE)
+}
+
+fn tracked_layout(layout: Layout) -> Option<Layout> {
+    let size = layout.size().checked_add(header_size(layout))?;
+    Layout::from_size_align(size, layout.align()).ok()
+}
+
+fn record_allocation(category: u8, size: usize) {
+    let counters = &COUNTERS[category as usize];
+    let live = counters.live_bytes.fetch_add(size, Ordering::Relaxed) + size;
+    counters.peak_bytes.fetch_max(live, Ordering::Relaxed);
+    counters.live_allocations.fetch_add(1, Ordering::Relaxed);
+    counters.total_allocations.fetch_add(1, Ordering::Relaxed);
+}
+
+fn record_deallocation(category: u8, size: usize) {
+    let counters = &COUNTERS[category as usize];
+    counters.live_bytes.fetch_sub(size, Ordering::Relaxed);
+    counters.live_allocations.fetch_sub(1, Ordering::Relaxed);
+}
+
+impl<A> TrackingAllocator<A> {
+    unsafe fn finish_allocation(&self, base: *mut u8, layout: Layout) -> *mut u8 {
+        if base.is_null() {
+            return base;
+        }
+
+        let category = current_category();
+        let ptr = base.add(header_size(layout));
+        ptr.sub(1).write(category);
+        record_allocation(category, layout.size());
+        TRACKING.store(true, Ordering::Relaxed);
+
+        ptr
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match tracked_layout(layout) {
+            Some(tracked) => self.finish_allocation(self.inner.alloc(tracked), layout),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        match tracked_layout(layout) {
+            Some(tracked) => self.finish_allocation(self.inner.alloc_zeroed(tracked), layout),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let category = ptr.sub(1).read();
+        record_deallocation(category, layout.size());
+
+        let tracked =
+            Layout::from_size_align_unchecked(layout.size() + header_size(layout), layout.align());
+        self.inner.dealloc(ptr.sub(header_size(layout)), tracked);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let header = header_size(layout);
+        let new_tracked_size = match new_size.checked_add(header) {
+            Some(size) if Layout::from_size_align(size, layout.align()).is_ok() => size,
+            _ => return std::ptr::null_mut(),
+        };
+
+        // The memory stays in the category it was allocated in
+        let category = ptr.sub(1).read();
+        let tracked = Layout::from_size_align_unchecked(layout.size() + header, layout.align());
+        let base = self
+            .inner
+            .realloc(ptr.sub(header), tracked, new_tracked_size);
+        if base.is_null() {
+            return base;
+        }
+
+        let counters = &COUNTERS[category as usize];
+        if new_size >= layout.size() {
+            let grown = new_size - layout.size();
+            let live = counters.live_bytes.fetch_add(grown, Ordering::Relaxed) + grown;
+            counters.peak_bytes.fetch_max(live, Ordering::Relaxed);
+        } else {
+            counters
+                .live_bytes
+                .fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+        }
+
+        base.add(header)
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    alloc::{self, Layout},
+    cell::{Cell, RefCell},
+    ptr::{self, NonNull},
+    slice, str,
+};
+
+// Alignment of the chunks, allocations requiring more are aligned inside the chunk
+const CHUNK_ALIGN: usize = 16;
+
+struct Chunk {
+    ptr: NonNull<u8>,
+    size: usize,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Chunk {
+        let layout = Layout::from_size_align(size, CHUNK_ALIGN).expect("arena chunk too large");
+        // Safety: the layout size is never zero, see Arena::alloc_layout
+        let ptr = unsafe { alloc::alloc(layout) };
+
+        Chunk {
+            ptr: NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout)),
+            size,
+        }
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        // Safety: the chunk was allocated with this layout in Chunk::new
+        unsafe {
+            alloc::dealloc(
+                self.ptr.as_ptr(),
+                Layout::from_size_align_unchecked(self.size, CHUNK_ALIGN),
+            );
+        }
+    }
+}
+
+/// Bump allocator handing out memory from large chunks, freed all at once
+///
+/// Allocating only moves an offset in the current chunk, making arenas suited to many small
+/// allocations sharing the same lifetime. Values stored in an arena are never dropped, their
+/// memory is reclaimed by [`Arena::reset`] or when the arena is dropped.
+///
+/// ```
+/// use nazara_core::memory::Arena;
+///
+/// let mut arena = Arena::new(1024);
+/// let position = arena.alloc([1.0f32, 2.0, 3.0]);
+/// let name = arena.alloc_str("player");
+/// let indices = arena.alloc_slice_copy(&[0u16, 1, 2, 2, 1, 3]);
+///
+/// position[1] = 5.0;
+/// assert_eq!(*position, [1.0, 5.0, 3.0]);
+/// assert_eq!(name, "player");
+/// assert_eq!(indices.len(), 6);
+/// assert!(arena.get_allocated_size() >= 12 + 6 + 12);
+///
+/// arena.reset();
+/// assert_eq!(arena.get_allocated_size(), 0);
+/// assert_eq!(arena.get_capacity(), 1024);
+/// ```
+pub struct Arena {
+    chunks: RefCell<Vec<Chunk>>,
+    chunk_size: usize,
+    current: Cell<usize>,
+    offset: Cell<usize>,
+    allocated: Cell<usize>,
+}
+
+impl Arena {
+    /// Creates an empty arena, chunks are allocated when needed
+    ///
+    /// # Arguments
+    /// * `chunk_size` - Size of the chunks, bigger allocations get their own chunk
+    pub fn new(chunk_size: usize) -> Arena {
+        Arena {
+            chunks: RefCell::new(Vec::new()),
+            chunk_size: chunk_size.max(1),
+            current: Cell::new(0),
+            offset: Cell::new(0),
+            allocated: Cell::new(0),
+        }
+    }
+
+    /// Moves a value into the arena
+    ///
+    /// # Arguments
+    /// * `value` - Value to store, its destructor will not be run
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T>(&self, value: T) -> &mut T {
+        let ptr = self.alloc_layout(Layout::new::<T>()).cast::<T>();
+        // Safety: the memory is properly aligned, large enough and not used by anything else
+        unsafe {
+            ptr.as_ptr().write(value);
+            &mut *ptr.as_ptr()
+        }
+    }
+
+    /// Copies a slice into the arena
+    ///
+    /// # Arguments
+    /// * `values` - Values to copy
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, values: &[T]) -> &mut [T] {
+        let layout = Layout::array::<T>(values.len()).expect("arena allocation too large");
+        let ptr = self.alloc_layout(layout).cast::<T>();
+        // Safety: the memory is properly aligned, large enough and not used by anything else
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), ptr.as_ptr(), values.len());
+            slice::from_raw_parts_mut(ptr.as_ptr(), values.len())
+        }
+    }
+
+    /// Copies a string into the arena
+    ///
+    /// # Arguments
+    /// * `value` - String to copy
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_str(&self, value: &str) -> &mut str {
+        let bytes = self.alloc_slice_copy(value.as_bytes());
+        // Safety: the bytes were copied from a valid string
+        unsafe { str::from_utf8_unchecked_mut(bytes) }
+    }
+
+    /// Allocates uninitialized memory matching a layout
+    ///
+    /// # Arguments
+    /// * `layout` - Size and alignment of the memory
+    pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        if layout.size() == 0 {
+            // Safety: alignments are never zero
+            return unsafe { NonNull::new_unchecked(layout.align() as *mut u8) };
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        loop {
+            if let Some(chunk) = chunks.get(self.current.get()) {
+                let base = chunk.ptr.as_ptr() as usize;
+                let start = (base + self.offset.get() + layout.align() - 1) & !(layout.align() - 1);
+                let end = start + layout.size();
+                if end <= base + chunk.size {
+                    self.offset.set(end - base);
+                    self.allocated.set(self.allocated.get() + layout.size());
+
+                    // Safety: start is inside the chunk, which is not null
+                    return unsafe { NonNull::new_unchecked(chunk.ptr.as_ptr().add(start - base)) };
+                }
+
+                if self.current.get() + 1 < chunks.len() {
+                    // Chunks kept by a reset are reused before allocating new ones
+                    self.current.set(self.current.get() + 1);
+                    self.offset.set(0);
+                    continue;
+                }
+            }
+
+            let size = self.chunk_size.max(layout.size() + layout.align());
+            chunks.push(Chunk::new(size));
+            self.current.set(chunks.len() - 1);
+            self.offset.set(0);
+        }
+    }
+
+    /// Frees every allocation at once, keeping the chunks for the next allocations
+    ///
+    /// Taking `&mut self` guarantees no reference to the allocated values is alive.
+    pub fn reset(&mut self) {
+        self.current.set(0);
+        self.offset.set(0);
+        self.allocated.set(0);
+    }
+
+    /// Returns the number of bytes allocated since the creation or the last reset
+    pub fn get_allocated_size(&self) -> usize {
+        self.allocated.get()
+    }
+
+    /// Returns the total size of the chunks owned by the arena
+    pub fn get_capacity(&self) -> usize {
+        self.chunks.borrow().iter().map(|chunk| chunk.size).sum()
+    }
+
+    /// Returns the size of the chunks allocated by the arena
+    pub fn get_chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new(64 * 1024)
+    }
+}
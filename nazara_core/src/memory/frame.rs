@@ -0,0 +1,112 @@
+use super::Arena;
+
+/// [`Arena`] reset at the beginning of each frame, for temporary data living one frame
+///
+/// The [`Application`](crate::application::Application) owns one, reset by
+/// [`Application::execute`](crate::application::Application::execute).
+///
+/// ```
+/// use nazara_core::memory::FrameAllocator;
+///
+/// let mut frame = FrameAllocator::new(4096);
+/// for i in 0..3 {
+///     frame.begin_frame();
+///     let visible = frame.alloc_slice_copy(&[i; 100]);
+///     assert_eq!(visible.len(), 100);
+/// }
+///
+/// frame.begin_frame();
+/// assert_eq!(frame.get_last_frame_size(), 400);
+/// assert_eq!(frame.get_peak_frame_size(), 400);
+/// assert_eq!(frame.get_frame_count(), 4);
+/// ```
+pub struct FrameAllocator {
+    arena: Arena,
+    frame_count: u64,
+    last_frame_size: usize,
+    peak_frame_size: usize,
+}
+
+impl FrameAllocator {
+    /// Creates a frame allocator
+    ///
+    /// # Arguments
+    /// * `chunk_size` - Size of the chunks of the underlying [`Arena`]
+    pub fn new(chunk_size: usize) -> FrameAllocator {
+        FrameAllocator {
+            arena: Arena::new(chunk_size),
+            frame_count: 0,
+            last_frame_size: 0,
+            peak_frame_size: 0,
+        }
+    }
+
+    /// Frees the allocations of the previous frame
+    pub fn begin_frame(&mut self) {
+        if self.frame_count > 0 {
+            self.last_frame_size = self.arena.get_allocated_size();
+            self.peak_frame_size = self.peak_frame_size.max(self.last_frame_size);
+        }
+
+        self.arena.reset();
+        self.frame_count += 1;
+    }
+
+    /// Moves a value into the allocator until the next frame
+    ///
+    /// # Arguments
+    /// * `value` - Value to store, its destructor will not be run
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T>(&self, value: T) -> &mut T {
+        self.arena.alloc(value)
+    }
+
+    /// Copies a slice into the allocator until the next frame
+    ///
+    /// # Arguments
+    /// * `values` - Values to copy
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, values: &[T]) -> &mut [T] {
+        self.arena.alloc_slice_copy(values)
+    }
+
+    /// Copies a string into the allocator until the next frame
+    ///
+    /// # Arguments
+    /// * `value` - String to copy
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_str(&self, value: &str) -> &mut str {
+        self.arena.alloc_str(value)
+    }
+
+    /// Returns the [`Arena`] holding the allocations of the current frame
+    pub fn get_arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    /// Returns the number of frames begun
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Returns the number of bytes allocated during the current frame
+    pub fn get_frame_size(&self) -> usize {
+        self.arena.get_allocated_size()
+    }
+
+    /// Returns the number of bytes allocated during the previous frame
+    pub fn get_last_frame_size(&self) -> usize {
+        self.last_frame_size
+    }
+
+    /// Returns the maximum number of bytes allocated during a frame
+    pub fn get_peak_frame_size(&self) -> usize {
+        self.peak_frame_size
+    }
+}
+
+impl Default for FrameAllocator {
+    fn default() -> Self {
+        Self::new(64 * 1024)
+    }
+}
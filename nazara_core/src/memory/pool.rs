@@ -0,0 +1,150 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+type ResetFn<T> = Box<dyn Fn(&mut T) + Send + Sync>;
+
+/// Pool of reusable objects, such as buffers, avoiding an allocation each time one is needed
+///
+/// Objects are returned to the pool when the [`Pooled`] guard is dropped, keeping their
+/// allocated memory for the next use.
+///
+/// ```
+/// use nazara_core::memory::Pool;
+///
+/// let pool = Pool::new(|| Vec::<u8>::with_capacity(4096)).with_reset(Vec::clear);
+///
+/// {
+///     let mut buffer = pool.acquire();
+///     buffer.extend_from_slice(&[1, 2, 3]);
+///     assert_eq!(pool.get_created_count(), 1);
+/// }
+///
+/// let buffer = pool.acquire();
+/// assert!(buffer.is_empty());
+/// assert!(buffer.capacity() >= 4096);
+/// assert_eq!(pool.get_created_count(), 1);
+/// ```
+pub struct Pool<T> {
+    free: Mutex<Vec<T>>,
+    create: Box<dyn Fn() -> T + Send + Sync>,
+    reset: Option<ResetFn<T>>,
+    created: AtomicUsize,
+}
+
+impl<T> Pool<T> {
+    /// Creates an empty pool
+    ///
+    /// # Arguments
+    /// * `create` - Function creating a new object when the pool is empty
+    pub fn new<F>(create: F) -> Pool<T>
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Pool {
+            free: Mutex::new(Vec::new()),
+            create: Box::new(create),
+            reset: None,
+            created: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets a function called on objects returned to the pool
+    ///
+    /// # Arguments
+    /// * `reset` - Function restoring an object to its initial state
+    pub fn with_reset<F>(mut self, reset: F) -> Pool<T>
+    where
+        F: Fn(&mut T) + Send + Sync + 'static,
+    {
+        self.reset = Some(Box::new(reset));
+        self
+    }
+
+    /// Creates objects in advance so the next acquisitions don't have to
+    ///
+    /// # Arguments
+    /// * `count` - Number of objects to create
+    pub fn reserve(&self, count: usize) {
+        let objects: Vec<T> = (0..count).map(|_| (self.create)()).collect();
+        self.created.fetch_add(count, Ordering::Relaxed);
+        self.free.lock().unwrap().extend(objects);
+    }
+
+    /// Takes an object from the pool, creating one if none is available
+    pub fn acquire(&self) -> Pooled<'_, T> {
+        let value = self.free.lock().unwrap().pop().unwrap_or_else(|| {
+            self.created.fetch_add(1, Ordering::Relaxed);
+            (self.create)()
+        });
+
+        Pooled {
+            pool: self,
+            value: Some(value),
+        }
+    }
+
+    /// Returns the number of objects available in the pool
+    pub fn get_free_count(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+
+    /// Returns the number of objects created by the pool
+    pub fn get_created_count(&self) -> usize {
+        self.created.load(Ordering::Relaxed)
+    }
+
+    /// Drops the objects available in the pool, freeing their memory
+    pub fn shrink(&self) {
+        let objects = std::mem::take(&mut *self.free.lock().unwrap());
+        self.created.fetch_sub(objects.len(), Ordering::Relaxed);
+    }
+
+    fn release(&self, mut value: T) {
+        if let Some(reset) = &self.reset {
+            reset(&mut value);
+        }
+
+        self.free.lock().unwrap().push(value);
+    }
+}
+
+/// Object taken from a [`Pool`], given back to it when dropped
+pub struct Pooled<'a, T> {
+    pool: &'a Pool<T>,
+    value: Option<T>,
+}
+
+impl<'a, T> Pooled<'a, T> {
+    /// Takes the object out of the pool for good
+    pub fn detach(mut self) -> T {
+        self.pool.created.fetch_sub(1, Ordering::Relaxed);
+        self.value.take().unwrap()
+    }
+}
+
+impl<'a, T> Deref for Pooled<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<'a, T> DerefMut for Pooled<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
+    }
+}
+
+impl<'a, T> Drop for Pooled<'a, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.pool.release(value);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Coherent noise functions for procedural generation
+//!
+//! Every noise is built from a seed and returns the same values for the same seed, on every run.
+//!
+//! ```
+//! use cgmath::assert_relative_eq;
+//! use nazara_core::noise::{Fbm, Noise, Perlin, Simplex, Worley};
+//!
+//! assert_relative_eq!(Perlin::new(42).get_2d(0.3, 0.7), -0.136_92, epsilon = 1e-5);
+//! assert_relative_eq!(Perlin::new(42).get_3d(0.5, 1.25, 2.75), 0.489_284_5, epsilon = 1e-5);
+//! assert_relative_eq!(Simplex::new(42).get_2d(0.3, 0.7), 0.895_048, epsilon = 1e-5);
+//! assert_relative_eq!(Simplex::new(42).get_3d(0.5, 1.25, 2.75), -0.835_009, epsilon = 1e-5);
+//! assert_relative_eq!(Worley::new(42).get_2d(0.3, 0.7), -0.359_252, epsilon = 1e-5);
+//! assert_relative_eq!(Worley::new(42).get_3d(0.5, 1.25, 2.75), 0.078_744, epsilon = 1e-5);
+//! assert_relative_eq!(Fbm::new(Perlin::new(42)).get_2d(0.3, 0.7), -0.021_977, epsilon = 1e-5);
+//!
+//! // Coordinates beyond the range of the lattice don't overflow it
+//! for &c in &[3.0e9, -3.0e9, f32::MAX] {
+//!     Perlin::new(42).get_3d(c, c, c);
+//!     Simplex::new(42).get_3d(c, c, c);
+//!     Worley::new(42).get_3d(c, c, c);
+//! }
+//! ```
+
+mod fbm;
+mod perlin;
+mod simplex;
+mod worley;
+
+pub use self::{fbm::Fbm, perlin::Perlin, simplex::Simplex, worley::Worley};
+
+use crate::{
+    enums::PixelFormatType,
+    errors::{ImageError, NazaraError, NazaraResult},
+    image::Image,
+    random::{Pcg32, RandomGenerator},
+};
+
+/// Noise function sampled in two or three dimensions
+pub trait Noise {
+    /// Returns the noise at a 2D position, roughly in `[-1, 1]`
+    ///
+    /// # Arguments
+    /// * `x` - Position on the X axis
+    /// * `y` - Position on the Y axis
+    fn get_2d(&self, x: f32, y: f32) -> f32;
+
+    /// Returns the noise at a 3D position, roughly in `[-1, 1]`
+    ///
+    /// # Arguments
+    /// * `x` - Position on the X axis
+    /// * `y` - Position on the Y axis
+    /// * `z` - Position on the Z axis
+    fn get_3d(&self, x: f32, y: f32, z: f32) -> f32;
+}
+
+// Shuffled table of the values 0 to 255, repeated to avoid wrapping indices
+#[derive(Clone)]
+struct Permutation {
+    values: [u8; 512],
+}
+
+impl Permutation {
+    fn new(seed: u64) -> Permutation {
+        let mut table = [0u8; 256];
+        for (i, value) in table.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+        Pcg32::from_seed(seed).shuffle(&mut table);
+
+        let mut values = [0u8; 512];
+        values[..256].copy_from_slice(&table);
+        values[256..].copy_from_slice(&table);
+
+        Permutation { values }
+    }
+
+    fn hash(&self, index: i32) -> usize {
+        self.values[(index & 255) as usize] as usize
+    }
+
+    fn hash_2d(&self, x: i32, y: i32) -> usize {
+        self.hash((self.hash(x) as i32).wrapping_add(y))
+    }
+
+    fn hash_3d(&self, x: i32, y: i32, z: i32) -> usize {
+        self.hash((self.hash_2d(x, y) as i32).wrapping_add(z))
+    }
+}
+
+fn floor_to_int(value: f32) -> i32 {
+    value.floor() as i32
+}
+
+/// Fills the first level of an image with noise
+///
+/// Two dimensional images are sampled with [`Noise::get_2d`], three dimensional ones with
+/// [`Noise::get_3d`]. `R32F` images store the noise values as is, `L8` images map `[-1, 1]` to
+/// `[0, 255]`.
+///
+/// ```
+/// use nazara_core::enums::PixelFormatType;
+/// use nazara_core::image::Image;
+/// use nazara_core::noise::{self, Fbm, Noise, Perlin};
+///
+/// let mut image = Image::new_2d(PixelFormatType::L8, 64, 64);
+/// noise::fill_image(&mut image, &Fbm::new(Perlin::new(7)), 1.0 / 16.0).unwrap();
+///
+/// let mut other = Image::new_2d(PixelFormatType::L8, 64, 64);
+/// noise::fill_image(&mut other, &Fbm::new(Perlin::new(7)), 1.0 / 16.0).unwrap();
+/// assert_eq!(image.get_mipmap_content(0), other.get_mipmap_content(0));
+///
+/// let mut heights = Image::new_3d(PixelFormatType::R32F, 8, 8, 8);
+/// noise::fill_image(&mut heights, &Perlin::new(7), 0.25).unwrap();
+/// let second = &heights.get_mipmap_content(0)[4..8];
+/// let value = f32::from_ne_bytes([second[0], second[1], second[2], second[3]]);
+/// assert_eq!(value, Perlin::new(7).get_3d(0.25, 0.0, 0.0));
+///
+/// let mut rgba = Image::new_2d(PixelFormatType::RGBA8, 4, 4);
+/// assert!(noise::fill_image(&mut rgba, &Perlin::new(7), 1.0).is_err());
+/// ```
+///
+/// # Arguments
+/// * `image` - Image to fill, in the `R32F` or `L8` format
+/// * `noise` - Noise to sample
+/// * `frequency` - Factor applied to the pixel coordinates before sampling the noise
+pub fn fill_image<N: Noise + ?Sized>(
+    image: &mut Image,
+    noise: &N,
+    frequency: f32,
+) -> NazaraResult<()> {
+    let format = image.get_pixel_format();
+    let bytes_per_pixel = match format {
+        PixelFormatType::L8 => 1,
+        PixelFormatType::R32F => 4,
+        _ => {
+            return Err(NazaraError::from(ImageError::UnsupportedPixelFormat(
+                format,
+            )))
+        }
+    };
+
+    let dims = image.get_dims();
+    let mut content = Vec::with_capacity(dims.x * dims.y * dims.z * bytes_per_pixel);
+    for z in 0..dims.z {
+        for y in 0..dims.y {
+            for x in 0..dims.x {
+                let (sx, sy) = (x as f32 * frequency, y as f32 * frequency);
+                let value = if dims.z > 1 {
+                    noise.get_3d(sx, sy, z as f32 * frequency)
+                } else {
+                    noise.get_2d(sx, sy)
+                };
+
+                match format {
+                    PixelFormatType::L8 => {
+                        content.push(((value * 0.5 + 0.5).clamp(0.0, 1.0) * 255.0).round() as u8)
+                    }
+                    _ => content.extend_from_slice(&value.to_ne_bytes()),
+                }
+            }
+        }
+    }
+
+    image.update_mipmap_content(0, content);
+
+    Ok(())
+}
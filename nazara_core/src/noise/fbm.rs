@@ -0,0 +1,113 @@
+use super::Noise;
+
+/// Fractal Brownian motion, summing octaves of another noise at increasing frequencies
+///
+/// ```
+/// use nazara_core::noise::{Fbm, Noise, Simplex};
+///
+/// let terrain = Fbm::new(Simplex::new(3))
+///     .with_octaves(6)
+///     .with_lacunarity(2.0)
+///     .with_gain(0.45);
+///
+/// let height = terrain.get_2d(12.5, 3.75);
+/// assert!(height >= -1.0 && height <= 1.0);
+/// assert_eq!(terrain.get_octaves(), 6);
+/// ```
+#[derive(Clone)]
+pub struct Fbm<N> {
+    noise: N,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+}
+
+impl<N: Noise> Fbm<N> {
+    /// Creates a fractal noise with 5 octaves, a lacunarity of 2 and a gain of 0.5
+    ///
+    /// # Arguments
+    /// * `noise` - Noise summed at each octave
+    pub fn new(noise: N) -> Fbm<N> {
+        Fbm {
+            noise,
+            octaves: 5,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    /// Sets the number of octaves summed
+    ///
+    /// # Arguments
+    /// * `octaves` - Number of octaves, at least one
+    pub fn with_octaves(mut self, octaves: u32) -> Fbm<N> {
+        self.octaves = octaves.max(1);
+        self
+    }
+
+    /// Sets the factor applied to the frequency between two octaves
+    ///
+    /// # Arguments
+    /// * `lacunarity` - Frequency factor
+    pub fn with_lacunarity(mut self, lacunarity: f32) -> Fbm<N> {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Sets the factor applied to the amplitude between two octaves
+    ///
+    /// # Arguments
+    /// * `gain` - Amplitude factor, usually below one
+    pub fn with_gain(mut self, gain: f32) -> Fbm<N> {
+        self.gain = gain;
+        self
+    }
+
+    /// Returns the number of octaves summed
+    pub fn get_octaves(&self) -> u32 {
+        self.octaves
+    }
+
+    /// Returns the factor applied to the frequency between two octaves
+    pub fn get_lacunarity(&self) -> f32 {
+        self.lacunarity
+    }
+
+    /// Returns the factor applied to the amplitude between two octaves
+    pub fn get_gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Returns the noise summed at each octave
+    pub fn get_noise(&self) -> &N {
+        &self.noise
+    }
+
+    fn sum<F: Fn(f32) -> f32>(&self, sample: F) -> f32 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut max = 0.0;
+        for _ in 0..self.octaves {
+            total += sample(frequency) * amplitude;
+            max += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+
+        total / max
+    }
+}
+
+impl<N: Noise> Noise for Fbm<N> {
+    fn get_2d(&self, x: f32, y: f32) -> f32 {
+        self.sum(|frequency| self.noise.get_2d(x * frequency, y * frequency))
+    }
+
+    fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.sum(|frequency| {
+            self.noise
+                .get_3d(x * frequency, y * frequency, z * frequency)
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use super::{floor_to_int, Noise, Permutation};
+
+/// Improved Perlin gradient noise
+///
+/// ```
+/// use nazara_core::noise::{Noise, Perlin};
+///
+/// let noise = Perlin::new(42);
+/// // Gradient noise is zero on the lattice points
+/// assert_eq!(noise.get_2d(3.0, 5.0), 0.0);
+///
+/// let value = noise.get_3d(0.5, 1.25, 2.75);
+/// assert!(value >= -1.0 && value <= 1.0);
+/// assert_eq!(value, Perlin::new(42).get_3d(0.5, 1.25, 2.75));
+/// assert_ne!(value, Perlin::new(43).get_3d(0.5, 1.25, 2.75));
+/// ```
+#[derive(Clone)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    /// Creates a Perlin noise
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the gradients
+    pub fn new(seed: u64) -> Perlin {
+        Perlin {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + t * (b - a)
+}
+
+fn gradient_2d(hash: usize, x: f32, y: f32) -> f32 {
+    match hash & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+fn gradient_3d(hash: usize, x: f32, y: f32, z: f32) -> f32 {
+    // The 12 edges of a cube, four of them being repeated
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+impl Noise for Perlin {
+    fn get_2d(&self, x: f32, y: f32) -> f32 {
+        let (xi, yi) = (floor_to_int(x), floor_to_int(y));
+        let (xf, yf) = (x - xi as f32, y - yi as f32);
+        let (u, v) = (fade(xf), fade(yf));
+
+        let p = &self.permutation;
+        let n00 = gradient_2d(p.hash_2d(xi, yi), xf, yf);
+        let n10 = gradient_2d(p.hash_2d(xi.wrapping_add(1), yi), xf - 1.0, yf);
+        let n01 = gradient_2d(p.hash_2d(xi, yi.wrapping_add(1)), xf, yf - 1.0);
+        let n11 = gradient_2d(
+            p.hash_2d(xi.wrapping_add(1), yi.wrapping_add(1)),
+            xf - 1.0,
+            yf - 1.0,
+        );
+
+        lerp(v, lerp(u, n00, n10), lerp(u, n01, n11))
+    }
+
+    fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        let (xi, yi, zi) = (floor_to_int(x), floor_to_int(y), floor_to_int(z));
+        let (xf, yf, zf) = (x - xi as f32, y - yi as f32, z - zi as f32);
+        let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+
+        let p = &self.permutation;
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            gradient_3d(
+                p.hash_3d(
+                    xi.wrapping_add(dx),
+                    yi.wrapping_add(dy),
+                    zi.wrapping_add(dz),
+                ),
+                xf - dx as f32,
+                yf - dy as f32,
+                zf - dz as f32,
+            )
+        };
+
+        let x00 = lerp(u, corner(0, 0, 0), corner(1, 0, 0));
+        let x10 = lerp(u, corner(0, 1, 0), corner(1, 1, 0));
+        let x01 = lerp(u, corner(0, 0, 1), corner(1, 0, 1));
+        let x11 = lerp(u, corner(0, 1, 1), corner(1, 1, 1));
+
+        lerp(w, lerp(v, x00, x10), lerp(v, x01, x11))
+    }
+}
@@ -0,0 +1,153 @@
+use super::{floor_to_int, Noise, Permutation};
+
+// Directions towards the edges of a cube
+const GRADIENTS: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// Simplex noise, cheaper than [`Perlin`](super::Perlin) noise in three dimensions and without
+/// its axis-aligned artifacts
+///
+/// ```
+/// use nazara_core::noise::{Noise, Simplex};
+///
+/// let noise = Simplex::new(42);
+/// for i in 0..100 {
+///     let value = noise.get_2d(i as f32 * 0.37, i as f32 * 0.11);
+///     assert!(value >= -1.0 && value <= 1.0);
+/// }
+///
+/// assert_eq!(noise.get_3d(0.5, 1.25, 2.75), Simplex::new(42).get_3d(0.5, 1.25, 2.75));
+/// ```
+#[derive(Clone)]
+pub struct Simplex {
+    permutation: Permutation,
+}
+
+impl Simplex {
+    /// Creates a simplex noise
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the gradients
+    pub fn new(seed: u64) -> Simplex {
+        Simplex {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+fn contribution(gradient: usize, offset: [f32; 3], radius: f32) -> f32 {
+    let t = radius - offset[0] * offset[0] - offset[1] * offset[1] - offset[2] * offset[2];
+    if t < 0.0 {
+        return 0.0;
+    }
+
+    let g = GRADIENTS[gradient % 12];
+    let t2 = t * t;
+
+    t2 * t2 * (g[0] * offset[0] + g[1] * offset[1] + g[2] * offset[2])
+}
+
+impl Noise for Simplex {
+    fn get_2d(&self, x: f32, y: f32) -> f32 {
+        let skew = 0.5 * (3.0f32.sqrt() - 1.0);
+        let unskew = (3.0 - 3.0f32.sqrt()) / 6.0;
+
+        // Cell of the skewed grid containing the point
+        let s = (x + y) * skew;
+        let (i, j) = (floor_to_int(x + s), floor_to_int(y + s));
+        let t = i.wrapping_add(j) as f32 * unskew;
+        let (x0, y0) = (x - (i as f32 - t), y - (j as f32 - t));
+
+        // Each cell is made of two triangles
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let (x1, y1) = (x0 - i1 as f32 + unskew, y0 - j1 as f32 + unskew);
+        let (x2, y2) = (x0 - 1.0 + 2.0 * unskew, y0 - 1.0 + 2.0 * unskew);
+
+        let p = &self.permutation;
+        let n0 = contribution(p.hash_2d(i, j), [x0, y0, 0.0], 0.5);
+        let n1 = contribution(
+            p.hash_2d(i.wrapping_add(i1), j.wrapping_add(j1)),
+            [x1, y1, 0.0],
+            0.5,
+        );
+        let n2 = contribution(
+            p.hash_2d(i.wrapping_add(1), j.wrapping_add(1)),
+            [x2, y2, 0.0],
+            0.5,
+        );
+
+        70.0 * (n0 + n1 + n2)
+    }
+
+    fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        let skew = 1.0 / 3.0;
+        let unskew = 1.0 / 6.0;
+
+        let s = (x + y + z) * skew;
+        let (i, j, k) = (
+            floor_to_int(x + s),
+            floor_to_int(y + s),
+            floor_to_int(z + s),
+        );
+        let t = i.wrapping_add(j).wrapping_add(k) as f32 * unskew;
+        let (x0, y0, z0) = (x - (i as f32 - t), y - (j as f32 - t), z - (k as f32 - t));
+
+        // Each cell is made of six tetrahedra, selected by the order of the coordinates
+        let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+            if y0 >= z0 {
+                ((1, 0, 0), (1, 1, 0))
+            } else if x0 >= z0 {
+                ((1, 0, 0), (1, 0, 1))
+            } else {
+                ((0, 0, 1), (1, 0, 1))
+            }
+        } else if y0 < z0 {
+            ((0, 0, 1), (0, 1, 1))
+        } else if x0 < z0 {
+            ((0, 1, 0), (0, 1, 1))
+        } else {
+            ((0, 1, 0), (1, 1, 0))
+        };
+
+        let offset = |di: i32, dj: i32, dk: i32, factor: f32| {
+            [
+                x0 - di as f32 + factor * unskew,
+                y0 - dj as f32 + factor * unskew,
+                z0 - dk as f32 + factor * unskew,
+            ]
+        };
+
+        let p = &self.permutation;
+        let n0 = contribution(p.hash_3d(i, j, k), [x0, y0, z0], 0.6);
+        let n1 = contribution(
+            p.hash_3d(i.wrapping_add(i1), j.wrapping_add(j1), k.wrapping_add(k1)),
+            offset(i1, j1, k1, 1.0),
+            0.6,
+        );
+        let n2 = contribution(
+            p.hash_3d(i.wrapping_add(i2), j.wrapping_add(j2), k.wrapping_add(k2)),
+            offset(i2, j2, k2, 2.0),
+            0.6,
+        );
+        let n3 = contribution(
+            p.hash_3d(i.wrapping_add(1), j.wrapping_add(1), k.wrapping_add(1)),
+            offset(1, 1, 1, 3.0),
+            0.6,
+        );
+
+        32.0 * (n0 + n1 + n2 + n3)
+    }
+}
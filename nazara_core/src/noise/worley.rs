@@ -0,0 +1,85 @@
+use super::{floor_to_int, Noise};
+
+/// Worley (cellular) noise, based on the distance to the closest of randomly placed points
+///
+/// Each unit cell contains one point, the distance to the closest one is mapped from `[0, 1]`
+/// to `[-1, 1]`, giving a pattern of cells suited to stones, scales or caustics.
+///
+/// ```
+/// use nazara_core::noise::{Noise, Worley};
+///
+/// let noise = Worley::new(42);
+/// let value = noise.get_2d(4.5, 7.25);
+/// assert!(value >= -1.0 && value <= 1.0);
+/// assert_eq!(value, Worley::new(42).get_2d(4.5, 7.25));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Worley {
+    seed: u64,
+}
+
+impl Worley {
+    /// Creates a Worley noise
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the point positions
+    pub fn new(seed: u64) -> Worley {
+        Worley { seed }
+    }
+
+    // Three coordinates in [0, 1) of the point of a cell
+    fn cell_point(&self, x: i32, y: i32, z: i32) -> [f32; 3] {
+        let mut hash = self.seed
+            ^ (x as u32 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+            ^ (y as u32 as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+            ^ (z as u32 as u64).wrapping_mul(0x1656_67b1_9e37_79f9);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^= hash >> 31;
+
+        let unit = |bits: u64| (bits & 0x1f_ffff) as f32 / (1u32 << 21) as f32;
+
+        [unit(hash), unit(hash >> 21), unit(hash >> 42)]
+    }
+}
+
+fn to_range(distance: f32) -> f32 {
+    distance.min(1.0) * 2.0 - 1.0
+}
+
+impl Noise for Worley {
+    fn get_2d(&self, x: f32, y: f32) -> f32 {
+        let (xi, yi) = (floor_to_int(x), floor_to_int(y));
+
+        let mut closest = f32::MAX;
+        for cy in (-1..=1).map(|offset| yi.wrapping_add(offset)) {
+            for cx in (-1..=1).map(|offset| xi.wrapping_add(offset)) {
+                let point = self.cell_point(cx, cy, 0);
+                let dx = cx as f32 + point[0] - x;
+                let dy = cy as f32 + point[1] - y;
+                closest = closest.min(dx * dx + dy * dy);
+            }
+        }
+
+        to_range(closest.sqrt())
+    }
+
+    fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        let (xi, yi, zi) = (floor_to_int(x), floor_to_int(y), floor_to_int(z));
+
+        let mut closest = f32::MAX;
+        for cz in (-1..=1).map(|offset| zi.wrapping_add(offset)) {
+            for cy in (-1..=1).map(|offset| yi.wrapping_add(offset)) {
+                for cx in (-1..=1).map(|offset| xi.wrapping_add(offset)) {
+                    let point = self.cell_point(cx, cy, cz);
+                    let dx = cx as f32 + point[0] - x;
+                    let dy = cy as f32 + point[1] - y;
+                    let dz = cz as f32 + point[2] - z;
+                    closest = closest.min(dx * dx + dy * dy + dz * dz);
+                }
+            }
+        }
+
+        to_range(closest.sqrt())
+    }
+}
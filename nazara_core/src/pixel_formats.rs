@@ -75,7 +75,7 @@ impl PixelFormatType {
     /// # Arguments
     /// * `pixel_count` - Number of pixel
     pub fn compute_size(self, pixel_count: usize) -> usize {
-        (self.info().bits_per_pixel as usize * pixel_count + 7) / 8
+        (self.info().bits_per_pixel as usize * pixel_count).div_ceil(8)
     }
 
     /// Returns informations about a pixel format
@@ -105,6 +105,18 @@ impl PixelFormatType {
     pub fn info(self) -> PixelFormatInfo {
         // TODO make it static
         match self {
+            PixelFormatType::L8 => {
+                PixelFormatInfo::new("L8", 8, PixelFormatContent::ColorRGBA, 0xFF, 0xFF, 0xFF, 0)
+            }
+            PixelFormatType::R32F => PixelFormatInfo::new(
+                "R32F",
+                32,
+                PixelFormatContent::ColorRGBA,
+                0xFFFF_FFFF,
+                0,
+                0,
+                0,
+            ),
             PixelFormatType::RGB8 => PixelFormatInfo::new(
                 "RGB8",
                 24,
@@ -0,0 +1,606 @@
+//! Scoped timers aggregated per frame, with export to the Chrome trace format
+//!
+//! Scopes are recorded by every thread once profiling is enabled with [`set_enabled`], they are
+//! collected when [`begin_frame`] is called, which the
+//! [`Application`](crate::application::Application) does at the beginning of each frame.
+//!
+//! ```
+//! use nazara_core::{nz_profile_function, nz_profile_scope};
+//! use nazara_core::profiler;
+//!
+//! fn update_physics() {
+//!     nz_profile_function!("physics");
+//!     nz_profile_scope!("broadphase", "physics");
+//! }
+//!
+//! profiler::set_enabled(true);
+//! profiler::begin_frame();
+//! update_physics();
+//! update_physics();
+//! profiler::begin_frame();
+//!
+//! let frame = profiler::get_last_frame().unwrap();
+//! assert_eq!(frame.get_scope("broadphase").unwrap().call_count, 2);
+//! let function = frame.scopes.iter().find(|scope| scope.name.ends_with("::update_physics"));
+//! assert_eq!(function.unwrap().category, "physics");
+//! ```
+
+use std::{
+    cell::Cell,
+    collections::{HashMap, VecDeque},
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Number of frames kept by default in the history
+pub const DEFAULT_HISTORY_SIZE: usize = 120;
+
+/// A scope which was timed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ScopeEvent {
+    /// Name of the scope
+    pub name: &'static str,
+    /// Subsystem of the scope (`core`, `render`, ...)
+    pub category: &'static str,
+    /// Identifier of the thread which ran the scope
+    pub thread_id: u64,
+    /// Time at which the scope was entered, relative to the first use of the profiler
+    pub start: Duration,
+    /// Time spent in the scope
+    pub duration: Duration,
+    /// Number of scopes the scope was nested in
+    pub depth: u32,
+}
+
+/// Time spent in the scopes sharing a name and a category during a frame
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ScopeStats {
+    /// Name of the scopes
+    pub name: &'static str,
+    /// Subsystem of the scopes
+    pub category: &'static str,
+    /// Number of times a scope was run
+    pub call_count: usize,
+    /// Time spent in the scopes, on all threads
+    pub total_time: Duration,
+    /// Longest time spent in one of the scopes
+    pub max_time: Duration,
+}
+
+/// Statistics of a frame, between two calls of [`begin_frame`]
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    /// Index of the frame, starting from zero
+    pub index: u64,
+    /// Time at which the frame began, relative to the first use of the profiler
+    pub start: Duration,
+    /// Duration of the frame
+    pub duration: Duration,
+    /// Scopes run during the frame, the most expensive first
+    pub scopes: Vec<ScopeStats>,
+}
+
+impl FrameStats {
+    /// Returns the statistics of the scopes with the given name
+    ///
+    /// # Arguments
+    /// * `name` - Name of the scopes
+    pub fn get_scope(&self, name: &str) -> Option<&ScopeStats> {
+        self.scopes.iter().find(|scope| scope.name == name)
+    }
+}
+
+impl fmt::Display for FrameStats {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "Frame {}: {:.3} ms",
+            self.index,
+            self.duration.as_secs_f64() * 1000.0
+        )?;
+        for scope in &self.scopes {
+            write!(
+                fmt,
+                "\n  [{}] {}: {:.3} ms in {} calls (max {:.3} ms)",
+                scope.category,
+                scope.name,
+                scope.total_time.as_secs_f64() * 1000.0,
+                scope.call_count,
+                scope.max_time.as_secs_f64() * 1000.0
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Events recorded between [`start_capture`] and [`stop_capture`]
+#[derive(Debug, Clone, Default)]
+pub struct Capture {
+    events: Vec<ScopeEvent>,
+    frames: Vec<FrameStats>,
+    threads: Vec<(u64, String)>,
+}
+
+impl Capture {
+    /// Returns the scopes recorded, grouped by frame
+    pub fn get_events(&self) -> &[ScopeEvent] {
+        &self.events
+    }
+
+    /// Returns the statistics of the frames recorded
+    pub fn get_frames(&self) -> &[FrameStats] {
+        &self.frames
+    }
+
+    /// Returns the name of a thread which recorded events
+    ///
+    /// ```
+    /// use std::thread;
+    /// use nazara_core::{nz_profile_scope, profiler};
+    ///
+    /// profiler::set_enabled(true);
+    /// profiler::start_capture();
+    /// profiler::begin_frame();
+    /// thread::Builder::new()
+    ///     .name(String::from("streaming"))
+    ///     .spawn(|| {
+    ///         nz_profile_scope!("decode");
+    ///     })
+    ///     .unwrap()
+    ///     .join()
+    ///     .unwrap();
+    /// profiler::begin_frame();
+    ///
+    /// let capture = profiler::stop_capture().unwrap();
+    /// let event = capture.get_events()[0];
+    /// assert_eq!(event.name, "decode");
+    /// assert_eq!(capture.get_thread_name(event.thread_id), Some("streaming"));
+    /// ```
+    ///
+    /// # Arguments
+    /// * `thread_id` - Identifier of the thread, as found in [`ScopeEvent::thread_id`]
+    pub fn get_thread_name(&self, thread_id: u64) -> Option<&str> {
+        self.threads
+            .iter()
+            .find(|(id, _)| *id == thread_id)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Writes the capture in the Chrome trace event format
+    ///
+    /// The output can be opened with `chrome://tracing`, Perfetto or Speedscope. Scopes are
+    /// written as complete events and frames as global instant events.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the JSON document
+    pub fn write_chrome_trace<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(b"{\"traceEvents\":[")?;
+
+        let mut first = true;
+        let mut separator = |writer: &mut W| -> io::Result<()> {
+            if !first {
+                writer.write_all(b",")?;
+            }
+            first = false;
+            writer.write_all(b"\n")
+        };
+
+        for (id, name) in &self.threads {
+            separator(&mut writer)?;
+            write!(
+                writer,
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{},\"args\":{{\"name\":\"{}\"}}}}",
+                id,
+                escape_json(name)
+            )?;
+        }
+
+        for frame in &self.frames {
+            separator(&mut writer)?;
+            write!(
+                writer,
+                "{{\"name\":\"Frame {}\",\"cat\":\"frame\",\"ph\":\"i\",\"s\":\"g\",\"ts\":{},\"pid\":1,\"tid\":0}}",
+                frame.index,
+                format_micros(frame.start)
+            )?;
+        }
+
+        for event in &self.events {
+            separator(&mut writer)?;
+            write!(
+                writer,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":{}}}",
+                escape_json(event.name),
+                escape_json(event.category),
+                format_micros(event.start),
+                format_micros(event.duration),
+                event.thread_id
+            )?;
+        }
+
+        writer.write_all(b"\n],\"displayTimeUnit\":\"ms\"}\n")?;
+        writer.flush()
+    }
+
+    /// Returns the capture in the Chrome trace event format
+    ///
+    /// ```
+    /// use nazara_core::{nz_profile_scope, profiler};
+    ///
+    /// profiler::set_enabled(true);
+    /// profiler::start_capture();
+    /// profiler::begin_frame();
+    /// {
+    ///     nz_profile_scope!("load \"level\"", "resources");
+    /// }
+    /// profiler::begin_frame();
+    ///
+    /// let trace = profiler::stop_capture().unwrap().to_chrome_trace();
+    /// assert!(trace.starts_with("{\"traceEvents\":["));
+    /// assert!(trace.contains("\"name\":\"load \\\"level\\\"\",\"cat\":\"resources\",\"ph\":\"X\""));
+    /// assert!(trace.contains("\"name\":\"thread_name\""));
+    /// ```
+    pub fn to_chrome_trace(&self) -> String {
+        let mut output = Vec::new();
+        self.write_chrome_trace(&mut output)
+            .expect("writing to a vector cannot fail");
+
+        String::from_utf8(output).expect("the trace is valid UTF-8")
+    }
+
+    /// Saves the capture to a file in the Chrome trace event format
+    ///
+    /// # Arguments
+    /// * `path` - Path of the file, usually with the `.json` extension
+    pub fn save_chrome_trace(&self, path: &Path) -> io::Result<()> {
+        self.write_chrome_trace(BufWriter::new(File::create(path)?))
+    }
+}
+
+fn format_micros(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1_000_000.0)
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+struct ThreadEvents {
+    id: u64,
+    name: String,
+    events: Mutex<Vec<ScopeEvent>>,
+}
+
+struct FrameState {
+    started: bool,
+    index: u64,
+    start: Duration,
+    history: VecDeque<FrameStats>,
+    history_size: usize,
+    capture: Option<Capture>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+static THREADS: Mutex<Vec<Arc<ThreadEvents>>> = Mutex::new(Vec::new());
+static STATE: Mutex<FrameState> = Mutex::new(FrameState {
+    started: false,
+    index: 0,
+    start: Duration::ZERO,
+    history: VecDeque::new(),
+    history_size: DEFAULT_HISTORY_SIZE,
+    capture: None,
+});
+
+thread_local! {
+    static LOCAL_EVENTS: Arc<ThreadEvents> = register_thread();
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+fn register_thread() -> Arc<ThreadEvents> {
+    let id = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+    let name = match thread::current().name() {
+        Some(name) => name.to_string(),
+        None => format!("thread #{}", id),
+    };
+
+    let events = Arc::new(ThreadEvents {
+        id,
+        name,
+        events: Mutex::new(Vec::new()),
+    });
+    THREADS.lock().unwrap().push(events.clone());
+
+    events
+}
+
+fn elapsed() -> Duration {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed()
+}
+
+/// Enables or disables the recording of scopes, disabled by default
+///
+/// # Arguments
+/// * `enabled` - True to record scopes
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true if scopes are recorded
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Sets the number of frames kept by [`get_frame_history`]
+///
+/// # Arguments
+/// * `size` - Number of frames, [`DEFAULT_HISTORY_SIZE`] by default
+pub fn set_history_size(size: usize) {
+    let mut state = STATE.lock().unwrap();
+    state.history_size = size;
+    while state.history.len() > size {
+        state.history.pop_front();
+    }
+}
+
+/// Ends the current frame and begins a new one
+///
+/// The scopes recorded by all threads since the previous call are aggregated in the
+/// [`FrameStats`] of the frame which ended.
+pub fn begin_frame() {
+    if !is_enabled() {
+        return;
+    }
+
+    let now = elapsed();
+    let mut events = Vec::new();
+    let mut threads = Vec::new();
+    {
+        let mut registered = THREADS.lock().unwrap();
+        for thread in registered.iter() {
+            let mut thread_events = thread.events.lock().unwrap();
+            if !thread_events.is_empty() {
+                threads.push((thread.id, thread.name.clone()));
+                events.append(&mut thread_events);
+            }
+        }
+
+        // Threads which exited only remain referenced by the registry
+        registered.retain(|thread| Arc::strong_count(thread) > 1);
+    }
+
+    let mut state = STATE.lock().unwrap();
+    if !state.started {
+        // Scopes recorded before the first frame are discarded
+        state.started = true;
+        state.start = now;
+        return;
+    }
+
+    let frame = FrameStats {
+        index: state.index,
+        start: state.start,
+        duration: now - state.start,
+        scopes: aggregate(&events),
+    };
+
+    if let Some(capture) = &mut state.capture {
+        for thread in threads {
+            if capture.threads.iter().all(|(id, _)| *id != thread.0) {
+                capture.threads.push(thread);
+            }
+        }
+        capture.frames.push(frame.clone());
+        capture.events.append(&mut events);
+    }
+
+    state.history.push_back(frame);
+    while state.history.len() > state.history_size {
+        state.history.pop_front();
+    }
+
+    state.index += 1;
+    state.start = now;
+}
+
+fn aggregate(events: &[ScopeEvent]) -> Vec<ScopeStats> {
+    let mut indices = HashMap::new();
+    let mut scopes: Vec<ScopeStats> = Vec::new();
+    for event in events {
+        let index = *indices
+            .entry((event.name, event.category))
+            .or_insert_with(|| {
+                scopes.push(ScopeStats {
+                    name: event.name,
+                    category: event.category,
+                    call_count: 0,
+                    total_time: Duration::ZERO,
+                    max_time: Duration::ZERO,
+                });
+                scopes.len() - 1
+            });
+
+        let scope = &mut scopes[index];
+        scope.call_count += 1;
+        scope.total_time += event.duration;
+        scope.max_time = scope.max_time.max(event.duration);
+    }
+
+    scopes.sort_by_key(|scope| std::cmp::Reverse(scope.total_time));
+    scopes
+}
+
+/// Returns the statistics of the last frame which ended
+pub fn get_last_frame() -> Option<FrameStats> {
+    STATE.lock().unwrap().history.back().cloned()
+}
+
+/// Returns the statistics of the last frames, the oldest first
+///
+/// ```
+/// use std::{thread, time::Duration};
+/// use nazara_core::{nz_profile_scope, profiler};
+///
+/// profiler::set_enabled(true);
+/// profiler::set_history_size(2);
+/// for _ in 0..4 {
+///     profiler::begin_frame();
+///     nz_profile_scope!("sleep");
+///     thread::sleep(Duration::from_millis(1));
+/// }
+/// profiler::begin_frame();
+///
+/// let frames = profiler::get_frame_history();
+/// assert_eq!(frames.len(), 2);
+/// assert_eq!(frames[1].index, 3);
+/// assert!(frames[1].get_scope("sleep").unwrap().total_time >= Duration::from_millis(1));
+/// ```
+pub fn get_frame_history() -> Vec<FrameStats> {
+    STATE.lock().unwrap().history.iter().cloned().collect()
+}
+
+/// Starts recording every scope and frame, until [`stop_capture`] is called
+///
+/// Events are kept in memory, captures should be limited to a few seconds.
+pub fn start_capture() {
+    STATE.lock().unwrap().capture = Some(Capture::default());
+}
+
+/// Stops recording and returns the capture, `None` if no capture was started
+///
+/// The scopes of the frame in progress are not part of the capture.
+pub fn stop_capture() -> Option<Capture> {
+    STATE.lock().unwrap().capture.take()
+}
+
+/// Returns true if a capture is in progress
+pub fn is_capturing() -> bool {
+    STATE.lock().unwrap().capture.is_some()
+}
+
+/// Times a scope, from its creation until it is dropped
+///
+/// Usually created by the [`nz_profile_scope`](crate::nz_profile_scope) and
+/// [`nz_profile_function`](crate::nz_profile_function) macros.
+pub struct ProfileScope {
+    name: &'static str,
+    category: &'static str,
+    start: Option<Duration>,
+}
+
+impl ProfileScope {
+    /// Starts timing a scope, does nothing if the profiler is disabled
+    ///
+    /// # Arguments
+    /// * `name` - Name of the scope
+    /// * `category` - Subsystem of the scope
+    pub fn new(name: &'static str, category: &'static str) -> ProfileScope {
+        let start = if is_enabled() {
+            DEPTH.with(|depth| depth.set(depth.get() + 1));
+            Some(elapsed())
+        } else {
+            None
+        };
+
+        ProfileScope {
+            name,
+            category,
+            start,
+        }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let start = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+
+        let duration = elapsed() - start;
+        let depth = DEPTH.with(|depth| {
+            depth.set(depth.get() - 1);
+            depth.get()
+        });
+
+        // The thread locals may be unavailable while the thread is being destroyed
+        let _ = LOCAL_EVENTS.try_with(|thread| {
+            thread.events.lock().unwrap().push(ScopeEvent {
+                name: self.name,
+                category: self.category,
+                thread_id: thread.id,
+                start,
+                duration,
+                depth,
+            });
+        });
+    }
+}
+
+#[doc(hidden)]
+pub fn type_name_of<T>(_: T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+/// Times the rest of the enclosing block
+///
+/// ```
+/// use nazara_core::nz_profile_scope;
+///
+/// fn render() {
+///     nz_profile_scope!("render");
+///     {
+///         nz_profile_scope!("shadows", "render");
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! nz_profile_scope {
+    ($name:expr) => {
+        $crate::nz_profile_scope!($name, "default")
+    };
+    ($name:expr, $category:expr) => {
+        let _nz_profile_scope = $crate::profiler::ProfileScope::new($name, $category);
+    };
+}
+
+/// Times the rest of the enclosing function, named after its path
+#[macro_export]
+macro_rules! nz_profile_function {
+    () => {
+        $crate::nz_profile_function!("default")
+    };
+    ($category:expr) => {
+        let _nz_profile_scope = $crate::profiler::ProfileScope::new(
+            {
+                fn f() {}
+                let name = $crate::profiler::type_name_of(f);
+                &name[..name.len() - 3]
+            },
+            $category,
+        );
+    };
+}
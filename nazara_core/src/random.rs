@@ -0,0 +1,254 @@
+//! Seedable pseudo-random number generators
+//!
+//! The generators are deterministic: the same seed always produces the same sequence, on every
+//! platform, which makes them suited to procedural generation and replays. They are not
+//! cryptographically secure.
+
+/// Common interface of the pseudo-random number generators
+pub trait RandomGenerator {
+    /// Returns the next 32 bits random number
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns the next 64 bits random number
+    fn next_u64(&mut self) -> u64 {
+        (u64::from(self.next_u32()) << 32) | u64::from(self.next_u32())
+    }
+
+    /// Returns a random number in `[0, 1)`
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns a random number in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns true or false with the same probability
+    fn next_bool(&mut self) -> bool {
+        self.next_u32() & 1 == 1
+    }
+
+    /// Returns a random number in `[min, max)`, without bias
+    ///
+    /// # Arguments
+    /// * `min` - Lowest value which can be returned
+    /// * `max` - Upper bound, must be greater than `min`
+    fn range_u32(&mut self, min: u32, max: u32) -> u32 {
+        assert!(min < max, "empty range");
+
+        let span = max - min;
+        // Values above the last multiple of the span would favor the lowest results
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let value = self.next_u32();
+            if value >= threshold {
+                return min + value % span;
+            }
+        }
+    }
+
+    /// Returns a random number in `[min, max)`
+    ///
+    /// # Arguments
+    /// * `min` - Lowest value which can be returned
+    /// * `max` - Upper bound
+    fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Shuffles a slice in place
+    ///
+    /// # Arguments
+    /// * `values` - Values to shuffle
+    fn shuffle<T>(&mut self, values: &mut [T])
+    where
+        Self: Sized,
+    {
+        for i in (1..values.len()).rev() {
+            let j = self.range_u32(0, i as u32 + 1) as usize;
+            values.swap(i, j);
+        }
+    }
+}
+
+/// PCG32 generator (PCG-XSH-RR with 64 bits state), small and fast
+///
+/// Generators with the same seed but different streams produce independent sequences.
+///
+/// ```
+/// use nazara_core::random::{Pcg32, RandomGenerator};
+///
+/// // Reference values of the PCG implementation
+/// let mut rng = Pcg32::new(42, 54);
+/// assert_eq!(rng.next_u32(), 0xa15c02b7);
+/// assert_eq!(rng.next_u32(), 0x7b47f409);
+/// assert_eq!(rng.next_u32(), 0xba1d3330);
+///
+/// assert_eq!(Pcg32::from_seed(1234).next_u32(), 1134688154);
+///
+/// let mut a = Pcg32::from_seed(1234);
+/// let mut b = Pcg32::from_seed(1234);
+/// for _ in 0..100 {
+///     let value = a.range_u32(10, 20);
+///     assert!(value >= 10 && value < 20);
+///     assert_eq!(value, b.range_u32(10, 20));
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    const DEFAULT_STREAM: u64 = 0xda3e_39cb_94b9_5bdb;
+
+    /// Creates a generator
+    ///
+    /// # Arguments
+    /// * `seed` - Initial state
+    /// * `stream` - Sequence selector, only its 63 lowest bits are used
+    pub fn new(seed: u64, stream: u64) -> Pcg32 {
+        let mut rng = Pcg32 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+
+        rng
+    }
+
+    /// Creates a generator using the default stream
+    ///
+    /// # Arguments
+    /// * `seed` - Initial state
+    pub fn from_seed(seed: u64) -> Pcg32 {
+        Pcg32::new(seed, Pcg32::DEFAULT_STREAM)
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(Pcg32::MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl RandomGenerator for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let state = self.state;
+        self.step();
+
+        let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+        xorshifted.rotate_right((state >> 59) as u32)
+    }
+}
+
+/// Xoshiro256** generator, with a large period and fast 64 bits output
+///
+/// ```
+/// use nazara_core::random::{RandomGenerator, Xoshiro256};
+///
+/// // Reference values of the xoshiro implementation
+/// let mut rng = Xoshiro256::new([1, 2, 3, 4]);
+/// assert_eq!(rng.next_u64(), 11520);
+/// assert_eq!(rng.next_u64(), 0);
+/// assert_eq!(rng.next_u64(), 1509978240);
+///
+/// // Same seed, same sequence, on every run
+/// let mut rng = Xoshiro256::from_seed(42);
+/// let first: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+/// assert_eq!(first[0], 0x1578_0b2e_0c2e_c716);
+///
+/// let mut other = Xoshiro256::from_seed(42);
+/// assert_eq!(first, (0..3).map(|_| other.next_u64()).collect::<Vec<_>>());
+///
+/// // Jumping provides a sequence which doesn't overlap with the original one
+/// let mut jumped = rng.clone();
+/// jumped.jump();
+/// assert_ne!(rng.next_u64(), jumped.next_u64());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Xoshiro256 {
+    state: [u64; 4],
+}
+
+impl Xoshiro256 {
+    /// Creates a generator from its full state
+    ///
+    /// # Arguments
+    /// * `state` - Initial state, must not be all zeros
+    pub fn new(state: [u64; 4]) -> Xoshiro256 {
+        assert!(state != [0; 4], "xoshiro state must not be all zeros");
+
+        Xoshiro256 { state }
+    }
+
+    /// Creates a generator whose state is expanded from a seed with SplitMix64
+    ///
+    /// # Arguments
+    /// * `seed` - Any value, zero included
+    pub fn from_seed(seed: u64) -> Xoshiro256 {
+        let mut splitmix = seed;
+        let mut state = [0; 4];
+        for value in state.iter_mut() {
+            splitmix = splitmix.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = splitmix;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *value = z ^ (z >> 31);
+        }
+
+        Xoshiro256 { state }
+    }
+
+    /// Advances the generator by 2^128 steps
+    ///
+    /// Jumping copies of a generator gives each thread its own non-overlapping sequence.
+    pub fn jump(&mut self) {
+        const JUMP: [u64; 4] = [
+            0x180e_c6d3_3cfd_0aba,
+            0xd5a6_1266_f0c9_392c,
+            0xa958_2618_e03f_c9aa,
+            0x39ab_dc45_29b1_661c,
+        ];
+
+        let mut state = [0; 4];
+        for jump in JUMP.iter() {
+            for bit in 0..64 {
+                if jump & (1 << bit) != 0 {
+                    for (value, current) in state.iter_mut().zip(self.state.iter()) {
+                        *value ^= current;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+
+        self.state = state;
+    }
+}
+
+impl RandomGenerator for Xoshiro256 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+}
@@ -0,0 +1,512 @@
+use cgmath::{Matrix4, Point3, Quaternion, SquareMatrix, Transform as _, Vector3};
+
+use crate::errors::{NazaraError, NazaraResult, SceneError};
+
+mod node;
+mod transform;
+
+pub use self::{
+    node::{Node, NodeId},
+    transform::Transform,
+};
+
+struct NodeSlot {
+    generation: u32,
+    node: Option<Node>,
+}
+
+/// Hierarchy of nodes, each one placed relative to its parent
+///
+/// World matrices are computed lazily and cached: changing the transform of a node only marks
+/// it and its descendants as dirty, their matrices being computed again when requested.
+///
+/// ```
+/// use cgmath::{assert_relative_eq, Deg, Point3, Quaternion, Rotation3, Vector3};
+/// use nazara_core::scene::{Scene, Transform};
+///
+/// let mut scene = Scene::new();
+/// let ship = scene.create_node("ship", Transform::from_position(Vector3::new(100.0, 0.0, 0.0)));
+/// let turret = scene
+///     .create_child(ship, "turret", Transform::from_position(Vector3::new(0.0, 5.0, 0.0)))
+///     .unwrap();
+///
+/// assert_relative_eq!(
+///     scene.get_world_position(turret).unwrap(),
+///     Vector3::new(100.0, 5.0, 0.0)
+/// );
+///
+/// // Turning the ship moves the turret with it
+/// scene.set_rotation(ship, Quaternion::from_angle_z(Deg(90.0))).unwrap();
+/// assert_relative_eq!(
+///     scene.get_world_position(turret).unwrap(),
+///     Vector3::new(95.0, 0.0, 0.0),
+///     epsilon = 1e-4
+/// );
+///
+/// let target = scene.to_local_point(turret, Point3::new(95.0, 10.0, 0.0)).unwrap();
+/// assert_relative_eq!(target, Point3::new(10.0, 0.0, 0.0), epsilon = 1e-4);
+/// ```
+pub struct Scene {
+    slots: Vec<NodeSlot>,
+    free_slots: Vec<u32>,
+    node_count: usize,
+}
+
+impl Scene {
+    /// Creates a new empty scene
+    pub fn new() -> Scene {
+        Scene {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            node_count: 0,
+        }
+    }
+
+    /// Creates a root node
+    ///
+    /// # Arguments
+    /// * `name` - Name of the node, which doesn't have to be unique
+    /// * `transform` - Transform of the node in world space
+    pub fn create_node(&mut self, name: &str, transform: Transform) -> NodeId {
+        let node = Node::new(name, transform);
+        self.node_count += 1;
+
+        match self.free_slots.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.node = Some(node);
+
+                NodeId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(NodeSlot {
+                    generation: 0,
+                    node: Some(node),
+                });
+
+                NodeId {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Creates a node attached to a parent
+    ///
+    /// # Arguments
+    /// * `parent` - Parent of the new node
+    /// * `name` - Name of the node, which doesn't have to be unique
+    /// * `transform` - Transform of the node relative to its parent
+    pub fn create_child(
+        &mut self,
+        parent: NodeId,
+        name: &str,
+        transform: Transform,
+    ) -> NazaraResult<NodeId> {
+        self.node(parent)?;
+
+        let id = self.create_node(name, transform);
+        self.node_mut(id)?.parent = Some(parent);
+        self.node_mut(parent)?.children.push(id);
+
+        Ok(id)
+    }
+
+    /// Removes a node along with all its descendants
+    ///
+    /// # Arguments
+    /// * `id` - Node to remove
+    pub fn remove_node(&mut self, id: NodeId) -> NazaraResult<()> {
+        if let Some(parent) = self.node(id)?.parent {
+            self.node_mut(parent)?.children.retain(|&c| c != id);
+        }
+
+        let mut removed = vec![id];
+        while let Some(id) = removed.pop() {
+            let slot = &mut self.slots[id.index as usize];
+            let node = slot.node.take().unwrap();
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free_slots.push(id.index);
+            self.node_count -= 1;
+
+            removed.extend(node.children);
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the node exists in this scene
+    ///
+    /// # Arguments
+    /// * `id` - Node to check
+    pub fn is_valid(&self, id: NodeId) -> bool {
+        self.node(id).is_ok()
+    }
+
+    /// Returns how many nodes the scene contains
+    pub fn get_node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Returns a node, `None` if it was removed
+    ///
+    /// # Arguments
+    /// * `id` - Node to get
+    pub fn get_node(&self, id: NodeId) -> Option<&Node> {
+        self.node(id).ok()
+    }
+
+    /// Returns the nodes without parent
+    pub fn get_roots(&self) -> Vec<NodeId> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot.node {
+                Some(ref node) if node.parent.is_none() => Some(NodeId {
+                    index: index as u32,
+                    generation: slot.generation,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Changes the parent of a node
+    ///
+    /// ```
+    /// use cgmath::{assert_relative_eq, Vector3};
+    /// use nazara_core::scene::{Scene, Transform};
+    ///
+    /// let mut scene = Scene::new();
+    /// let left = scene.create_node("left", Transform::from_position(Vector3::new(-10.0, 0.0, 0.0)));
+    /// let right = scene.create_node("right", Transform::from_position(Vector3::new(10.0, 0.0, 0.0)));
+    /// let item = scene
+    ///     .create_child(left, "item", Transform::from_position(Vector3::new(1.0, 0.0, 0.0)))
+    ///     .unwrap();
+    ///
+    /// // Keeping the world transform moves the item to the same place in its new parent space
+    /// scene.set_parent(item, Some(right), true).unwrap();
+    /// assert_relative_eq!(scene.get_world_position(item).unwrap(), Vector3::new(-9.0, 0.0, 0.0));
+    /// assert_relative_eq!(
+    ///     scene.get_node(item).unwrap().get_transform().position,
+    ///     Vector3::new(-19.0, 0.0, 0.0)
+    /// );
+    ///
+    /// // Otherwise the local transform is kept
+    /// scene.set_parent(item, Some(left), false).unwrap();
+    /// assert_relative_eq!(scene.get_world_position(item).unwrap(), Vector3::new(-29.0, 0.0, 0.0));
+    ///
+    /// // A node can't be attached to itself or to one of its descendants
+    /// assert!(scene.set_parent(left, Some(item), false).is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `id` - Node to move in the hierarchy
+    /// * `parent` - New parent of the node, `None` to make it a root node
+    /// * `keep_world_transform` - If true the local transform of the node is changed so it
+    ///   stays in place, otherwise it moves with its new parent
+    pub fn set_parent(
+        &mut self,
+        id: NodeId,
+        parent: Option<NodeId>,
+        keep_world_transform: bool,
+    ) -> NazaraResult<()> {
+        let old_parent = self.node(id)?.parent;
+        if let Some(parent) = parent {
+            let mut ancestor = Some(parent);
+            while let Some(current) = ancestor {
+                if current == id {
+                    return Err(NazaraError::from(SceneError::CyclicHierarchy));
+                }
+                ancestor = self.node(current)?.parent;
+            }
+        }
+
+        let world_matrix = if keep_world_transform {
+            Some(self.get_world_matrix(id)?)
+        } else {
+            None
+        };
+
+        if let Some(old_parent) = old_parent {
+            self.node_mut(old_parent)?.children.retain(|&c| c != id);
+        }
+        if let Some(parent) = parent {
+            self.node_mut(parent)?.children.push(id);
+        }
+        self.node_mut(id)?.parent = parent;
+
+        match world_matrix {
+            Some(world_matrix) => self.set_world_matrix(id, &world_matrix),
+            None => {
+                self.invalidate(id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the transform of a node relative to its parent
+    ///
+    /// # Arguments
+    /// * `id` - Node to move
+    /// * `transform` - New local transform
+    pub fn set_transform(&mut self, id: NodeId, transform: Transform) -> NazaraResult<()> {
+        self.update_transform(id, |t| *t = transform)
+    }
+
+    /// Changes the transform of a node relative to its parent through a function
+    ///
+    /// # Arguments
+    /// * `id` - Node to move
+    /// * `update` - Function modifying the local transform
+    pub fn update_transform<F>(&mut self, id: NodeId, update: F) -> NazaraResult<()>
+    where
+        F: FnOnce(&mut Transform),
+    {
+        update(&mut self.node_mut(id)?.transform);
+        self.invalidate(id);
+
+        Ok(())
+    }
+
+    /// Changes the position of a node relative to its parent
+    ///
+    /// # Arguments
+    /// * `id` - Node to move
+    /// * `position` - New local position
+    pub fn set_position(&mut self, id: NodeId, position: Vector3<f32>) -> NazaraResult<()> {
+        self.update_transform(id, |t| t.position = position)
+    }
+
+    /// Changes the rotation of a node relative to its parent
+    ///
+    /// # Arguments
+    /// * `id` - Node to rotate
+    /// * `rotation` - New local rotation
+    pub fn set_rotation(&mut self, id: NodeId, rotation: Quaternion<f32>) -> NazaraResult<()> {
+        self.update_transform(id, |t| t.rotation = rotation)
+    }
+
+    /// Changes the scale of a node relative to its parent
+    ///
+    /// # Arguments
+    /// * `id` - Node to scale
+    /// * `scale` - New local scale
+    pub fn set_scale(&mut self, id: NodeId, scale: Vector3<f32>) -> NazaraResult<()> {
+        self.update_transform(id, |t| t.scale = scale)
+    }
+
+    /// Returns the matrix transforming from the space of a node to world space
+    ///
+    /// ```
+    /// use cgmath::{assert_relative_eq, Deg, Point3, Quaternion, Rotation3, Vector3};
+    /// use nazara_core::scene::{Scene, Transform};
+    ///
+    /// let mut scene = Scene::new();
+    /// let arm = scene.create_node(
+    ///     "arm",
+    ///     Transform::new(
+    ///         Vector3::new(0.0, 1.0, 0.0),
+    ///         Quaternion::from_angle_y(Deg(90.0)),
+    ///         Vector3::new(2.0, 2.0, 2.0),
+    ///     ),
+    /// );
+    /// let hand = scene
+    ///     .create_child(arm, "hand", Transform::from_position(Vector3::new(1.0, 0.0, 0.0)))
+    ///     .unwrap();
+    /// let finger = scene
+    ///     .create_child(hand, "finger", Transform::from_position(Vector3::new(0.0, 0.5, 0.0)))
+    ///     .unwrap();
+    ///
+    /// let check = |scene: &Scene| {
+    ///     // The cached matrix must always match the product of the local matrices
+    ///     let mut expected = scene.get_node(finger).unwrap().get_transform().get_matrix();
+    ///     let mut parent = scene.get_node(finger).unwrap().get_parent();
+    ///     while let Some(id) = parent {
+    ///         let node = scene.get_node(id).unwrap();
+    ///         expected = node.get_transform().get_matrix() * expected;
+    ///         parent = node.get_parent();
+    ///     }
+    ///     assert_relative_eq!(scene.get_world_matrix(finger).unwrap(), expected, epsilon = 1e-4);
+    /// };
+    ///
+    /// check(&scene);
+    /// assert_relative_eq!(
+    ///     scene.to_world_point(finger, Point3::new(0.0, 0.0, 0.0)).unwrap(),
+    ///     Point3::new(0.0, 2.0, -2.0),
+    ///     epsilon = 1e-4
+    /// );
+    ///
+    /// // Changes of any ancestor are seen by cached descendants
+    /// scene.set_scale(arm, Vector3::new(1.0, 1.0, 1.0)).unwrap();
+    /// check(&scene);
+    ///
+    /// let body = scene.create_node("body", Transform::from_position(Vector3::new(5.0, 0.0, 0.0)));
+    /// scene.set_parent(hand, Some(body), false).unwrap();
+    /// check(&scene);
+    /// assert_eq!(scene.get_node(arm).unwrap().get_children().len(), 0);
+    ///
+    /// let before = scene.get_world_matrix(finger).unwrap();
+    /// scene.set_parent(hand, Some(arm), true).unwrap();
+    /// check(&scene);
+    /// assert_relative_eq!(scene.get_world_matrix(finger).unwrap(), before, epsilon = 1e-4);
+    ///
+    /// scene.set_parent(hand, None, true).unwrap();
+    /// check(&scene);
+    /// assert_relative_eq!(scene.get_world_matrix(finger).unwrap(), before, epsilon = 1e-4);
+    ///
+    /// scene.remove_node(hand).unwrap();
+    /// assert!(scene.get_world_matrix(finger).is_err());
+    /// assert_eq!(scene.get_node_count(), 2);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `id` - Node to get the matrix of
+    pub fn get_world_matrix(&self, id: NodeId) -> NazaraResult<Matrix4<f32>> {
+        // Ancestors are updated first, stopping at the first one which is up to date
+        let mut dirty_nodes = Vec::new();
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let node = self.node(node_id)?;
+            if !node.dirty.get() {
+                break;
+            }
+            dirty_nodes.push(node);
+            current = node.parent;
+        }
+
+        for node in dirty_nodes.into_iter().rev() {
+            let local = node.transform.get_matrix();
+            let world = match node.parent {
+                Some(parent) => self.node(parent)?.world_matrix.get() * local,
+                None => local,
+            };
+            node.world_matrix.set(world);
+            node.dirty.set(false);
+        }
+
+        Ok(self.node(id)?.world_matrix.get())
+    }
+
+    /// Returns the transform of a node in world space
+    ///
+    /// Shearing caused by non-uniform scales of rotated ancestors can't be represented and
+    /// is lost, use [`Scene::get_world_matrix`] when it matters.
+    ///
+    /// # Arguments
+    /// * `id` - Node to get the transform of
+    pub fn get_world_transform(&self, id: NodeId) -> NazaraResult<Transform> {
+        Ok(Transform::from_matrix(&self.get_world_matrix(id)?))
+    }
+
+    /// Returns the position of a node in world space
+    ///
+    /// # Arguments
+    /// * `id` - Node to get the position of
+    pub fn get_world_position(&self, id: NodeId) -> NazaraResult<Vector3<f32>> {
+        Ok(self.get_world_matrix(id)?.w.truncate())
+    }
+
+    /// Moves a node so it has the given transform in world space
+    ///
+    /// # Arguments
+    /// * `id` - Node to move
+    /// * `transform` - Transform of the node in world space
+    pub fn set_world_transform(&mut self, id: NodeId, transform: Transform) -> NazaraResult<()> {
+        self.set_world_matrix(id, &transform.get_matrix())
+    }
+
+    /// Converts a point from the space of a node to world space
+    ///
+    /// # Arguments
+    /// * `id` - Node whose space the point is in
+    /// * `point` - Point to convert
+    pub fn to_world_point(&self, id: NodeId, point: Point3<f32>) -> NazaraResult<Point3<f32>> {
+        Ok(self.get_world_matrix(id)?.transform_point(point))
+    }
+
+    /// Converts a point from world space to the space of a node
+    ///
+    /// # Arguments
+    /// * `id` - Node whose space the point is converted to
+    /// * `point` - Point to convert
+    pub fn to_local_point(&self, id: NodeId, point: Point3<f32>) -> NazaraResult<Point3<f32>> {
+        Ok(self.get_inverse_world_matrix(id)?.transform_point(point))
+    }
+
+    /// Converts a direction from the space of a node to world space
+    ///
+    /// # Arguments
+    /// * `id` - Node whose space the direction is in
+    /// * `vector` - Direction to convert
+    pub fn to_world_vector(&self, id: NodeId, vector: Vector3<f32>) -> NazaraResult<Vector3<f32>> {
+        Ok(self.get_world_matrix(id)?.transform_vector(vector))
+    }
+
+    /// Converts a direction from world space to the space of a node
+    ///
+    /// # Arguments
+    /// * `id` - Node whose space the direction is converted to
+    /// * `vector` - Direction to convert
+    pub fn to_local_vector(&self, id: NodeId, vector: Vector3<f32>) -> NazaraResult<Vector3<f32>> {
+        Ok(self.get_inverse_world_matrix(id)?.transform_vector(vector))
+    }
+
+    fn get_inverse_world_matrix(&self, id: NodeId) -> NazaraResult<Matrix4<f32>> {
+        // A zero scale collapses the space, nothing can be converted back from it
+        Ok(self
+            .get_world_matrix(id)?
+            .invert()
+            .unwrap_or_else(Matrix4::identity))
+    }
+
+    fn set_world_matrix(&mut self, id: NodeId, world_matrix: &Matrix4<f32>) -> NazaraResult<()> {
+        let local_matrix = match self.node(id)?.parent {
+            Some(parent) => {
+                let parent_matrix = self.get_world_matrix(parent)?;
+                parent_matrix.invert().unwrap_or_else(Matrix4::identity) * world_matrix
+            }
+            None => *world_matrix,
+        };
+
+        self.set_transform(id, Transform::from_matrix(&local_matrix))
+    }
+
+    fn invalidate(&self, id: NodeId) {
+        let mut pending = vec![id];
+        while let Some(id) = pending.pop() {
+            if let Ok(node) = self.node(id) {
+                // Descendants of a dirty node are already dirty
+                if !node.dirty.replace(true) {
+                    pending.extend_from_slice(&node.children);
+                }
+            }
+        }
+    }
+
+    fn node(&self, id: NodeId) -> NazaraResult<&Node> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.node.as_ref())
+            .ok_or(NazaraError::from(SceneError::InvalidNode))
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> NazaraResult<&mut Node> {
+        self.slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.node.as_mut())
+            .ok_or(NazaraError::from(SceneError::InvalidNode))
+    }
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
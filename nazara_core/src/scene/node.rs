@@ -0,0 +1,59 @@
+use std::cell::Cell;
+
+use cgmath::Matrix4;
+
+use super::transform::Transform;
+
+/// Handle to a node of a [`Scene`](super::Scene)
+///
+/// Handles stay unique after their node is removed, a handle to a removed node is rejected by
+/// the scene instead of pointing to a newer node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    pub(crate) index: u32,
+    pub(crate) generation: u32,
+}
+
+/// Element of a [`Scene`](super::Scene), placed relative to its parent
+pub struct Node {
+    pub(crate) name: String,
+    pub(crate) transform: Transform,
+    pub(crate) parent: Option<NodeId>,
+    pub(crate) children: Vec<NodeId>,
+    // A dirty node always has dirty descendants, as world matrices are updated from the root
+    pub(crate) world_matrix: Cell<Matrix4<f32>>,
+    pub(crate) dirty: Cell<bool>,
+}
+
+impl Node {
+    pub(crate) fn new(name: &str, transform: Transform) -> Node {
+        Node {
+            name: name.to_string(),
+            transform,
+            parent: None,
+            children: Vec::new(),
+            world_matrix: Cell::new(transform.get_matrix()),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Returns the name of the node
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the transform of the node relative to its parent
+    pub fn get_transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    /// Returns the parent of the node, `None` for a root node
+    pub fn get_parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Returns the children of the node, in attachment order
+    pub fn get_children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
@@ -0,0 +1,170 @@
+use cgmath::{
+    InnerSpace, Matrix3, Matrix4, One, Point3, Quaternion, SquareMatrix, Transform as _, Vector3,
+};
+
+/// Position, rotation and scale of an object relative to its parent
+///
+/// The transformation applies the scale first, then the rotation, then the translation.
+///
+/// ```
+/// use cgmath::{assert_relative_eq, Deg, Point3, Quaternion, Rotation3, Vector3};
+/// use nazara_core::scene::Transform;
+///
+/// let transform = Transform::new(
+///     Vector3::new(10.0, 0.0, 0.0),
+///     Quaternion::from_angle_z(Deg(90.0)),
+///     Vector3::new(2.0, 2.0, 2.0),
+/// );
+///
+/// let point = transform.transform_point(Point3::new(1.0, 0.0, 0.0));
+/// assert_relative_eq!(point, Point3::new(10.0, 2.0, 0.0), epsilon = 1e-5);
+///
+/// let decomposed = Transform::from_matrix(&transform.get_matrix());
+/// assert_relative_eq!(decomposed.position, transform.position, epsilon = 1e-5);
+/// assert_relative_eq!(decomposed.rotation, transform.rotation, epsilon = 1e-5);
+/// assert_relative_eq!(decomposed.scale, transform.scale, epsilon = 1e-5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    pub position: Vector3<f32>,
+    pub rotation: Quaternion<f32>,
+    pub scale: Vector3<f32>,
+}
+
+impl Transform {
+    /// Creates a new transform
+    ///
+    /// # Arguments
+    /// * `position` - Translation
+    /// * `rotation` - Rotation, expected to be normalized
+    /// * `scale` - Scale along each axis
+    pub fn new(
+        position: Vector3<f32>,
+        rotation: Quaternion<f32>,
+        scale: Vector3<f32>,
+    ) -> Transform {
+        Transform {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Creates a transform which doesn't change anything
+    pub fn identity() -> Transform {
+        Transform::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Quaternion::one(),
+            Vector3::new(1.0, 1.0, 1.0),
+        )
+    }
+
+    /// Creates a transform which only translates
+    ///
+    /// # Arguments
+    /// * `position` - Translation
+    pub fn from_position(position: Vector3<f32>) -> Transform {
+        Transform {
+            position,
+            ..Transform::identity()
+        }
+    }
+
+    /// Decomposes an affine matrix into a transform
+    ///
+    /// Shearing, which can come from rotated non-uniform scales in a hierarchy, can't be
+    /// represented and is lost.
+    ///
+    /// # Arguments
+    /// * `matrix` - Affine matrix to decompose
+    pub fn from_matrix(matrix: &Matrix4<f32>) -> Transform {
+        let position = matrix.w.truncate();
+        let mut x = matrix.x.truncate();
+        let y = matrix.y.truncate();
+        let z = matrix.z.truncate();
+
+        let mut scale = Vector3::new(x.magnitude(), y.magnitude(), z.magnitude());
+        if x.cross(y).dot(z) < 0.0 {
+            // Mirrored matrix, flipping one axis is enough to get back a rotation
+            scale.x = -scale.x;
+            x = -x;
+        }
+
+        let rotation = if scale.x == 0.0 || scale.y == 0.0 || scale.z == 0.0 {
+            Quaternion::one()
+        } else {
+            let rotation = Matrix3::from_cols(x / scale.x.abs(), y / scale.y, z / scale.z);
+            Quaternion::from(rotation).normalize()
+        };
+
+        Transform {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Returns the matrix applying this transform
+    pub fn get_matrix(&self) -> Matrix4<f32> {
+        Matrix4::from_translation(self.position)
+            * Matrix4::from(self.rotation)
+            * Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z)
+    }
+
+    /// Returns the matrix undoing this transform, `None` if the scale has a zero component
+    pub fn get_inverse_matrix(&self) -> Option<Matrix4<f32>> {
+        self.get_matrix().invert()
+    }
+
+    /// Applies this transform to a point
+    ///
+    /// # Arguments
+    /// * `point` - Point to transform
+    pub fn transform_point(&self, point: Point3<f32>) -> Point3<f32> {
+        self.get_matrix().transform_point(point)
+    }
+
+    /// Applies this transform to a direction, ignoring the translation
+    ///
+    /// # Arguments
+    /// * `vector` - Direction to transform
+    pub fn transform_vector(&self, vector: Vector3<f32>) -> Vector3<f32> {
+        self.rotation
+            * Vector3::new(
+                vector.x * self.scale.x,
+                vector.y * self.scale.y,
+                vector.z * self.scale.z,
+            )
+    }
+
+    /// Moves the transform
+    ///
+    /// # Arguments
+    /// * `offset` - Translation to add, in the parent space
+    pub fn translate(&mut self, offset: Vector3<f32>) {
+        self.position += offset;
+    }
+
+    /// Rotates the transform around its origin
+    ///
+    /// # Arguments
+    /// * `rotation` - Rotation to apply after the current one, in the parent space
+    pub fn rotate(&mut self, rotation: Quaternion<f32>) {
+        self.rotation = (rotation * self.rotation).normalize();
+    }
+
+    /// Returns the transform of the child expressed in the space of the parent of this transform
+    ///
+    /// # Arguments
+    /// * `child` - Transform relative to this one
+    pub fn concat(&self, child: &Transform) -> Transform {
+        Transform::from_matrix(&(self.get_matrix() * child.get_matrix()))
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
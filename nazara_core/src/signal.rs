@@ -0,0 +1,355 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::{Rc, Weak},
+};
+
+type SlotCallback<Args> = Rc<RefCell<dyn FnMut(&Args)>>;
+
+struct Slot<Args> {
+    id: u64,
+    connected: Rc<Cell<bool>>,
+    callback: SlotCallback<Args>,
+}
+
+struct SignalInner<Args> {
+    slots: RefCell<Vec<Slot<Args>>>,
+    next_id: Cell<u64>,
+}
+
+/// Lets a [`Connection`] disconnect itself without knowing the arguments of its signal
+trait Disconnect {
+    fn disconnect(&self, id: u64);
+}
+
+impl<Args> Disconnect for SignalInner<Args> {
+    fn disconnect(&self, id: u64) {
+        let mut slots = self.slots.borrow_mut();
+        if let Some(index) = slots.iter().position(|s| s.id == id) {
+            let slot = slots.remove(index);
+            slot.connected.set(false);
+        }
+    }
+}
+
+/// Calls every connected slot when emitted
+///
+/// Slots can be connected or disconnected at any time, including from a slot while the signal
+/// is being emitted: a slot connected during an emission is called starting from the next one,
+/// a slot disconnected during an emission is not called anymore.
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use nazara_core::signal::Signal;
+///
+/// let on_damage: Signal<(u32, &'static str)> = Signal::new();
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// let output = log.clone();
+/// let connection = on_damage.connect(move |&(amount, source)| {
+///     output.borrow_mut().push(format!("{} damage from {}", amount, source));
+/// });
+///
+/// let output = log.clone();
+/// on_damage.connect(move |&(amount, _)| output.borrow_mut().push(format!("hp -{}", amount)));
+///
+/// on_damage.emit(&(10, "lava"));
+/// connection.disconnect();
+/// on_damage.emit(&(5, "spikes"));
+///
+/// assert_eq!(*log.borrow(), vec!["10 damage from lava", "hp -10", "hp -5"]);
+/// ```
+pub struct Signal<Args> {
+    inner: Rc<SignalInner<Args>>,
+}
+
+impl<Args: 'static> Signal<Args> {
+    /// Creates a new signal without any slot
+    pub fn new() -> Signal<Args> {
+        Signal {
+            inner: Rc::new(SignalInner {
+                slots: RefCell::new(Vec::new()),
+                next_id: Cell::new(0),
+            }),
+        }
+    }
+
+    /// Connects a slot, which stays connected until the [`Connection`] is disconnected or the
+    /// signal is dropped
+    ///
+    /// # Arguments
+    /// * `slot` - Function called with the arguments of each emission
+    pub fn connect<F>(&self, slot: F) -> Connection
+    where
+        F: FnMut(&Args) + 'static,
+    {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+
+        let connected = Rc::new(Cell::new(true));
+        self.inner.slots.borrow_mut().push(Slot {
+            id,
+            connected: connected.clone(),
+            callback: Rc::new(RefCell::new(slot)),
+        });
+
+        let signal: Rc<dyn Disconnect> = self.inner.clone();
+        Connection {
+            signal: Rc::downgrade(&signal),
+            id,
+            connected,
+        }
+    }
+
+    /// Calls every connected slot with the given arguments
+    ///
+    /// A slot emitting the signal which called it is not called recursively.
+    ///
+    /// ```
+    /// use std::{cell::{Cell, RefCell}, rc::Rc};
+    /// use nazara_core::signal::{ConnectionGuard, Signal};
+    ///
+    /// let signal = Rc::new(Signal::<u32>::new());
+    /// let calls = Rc::new(Cell::new(0));
+    /// let guard: Rc<RefCell<Option<ConnectionGuard>>> = Rc::new(RefCell::new(None));
+    ///
+    /// // A slot which disconnects itself and connects another slot while being emitted
+    /// let (s, c, g) = (signal.clone(), calls.clone(), guard.clone());
+    /// let connection = signal.connect(move |_| {
+    ///     c.set(c.get() + 1);
+    ///     g.borrow_mut().take();
+    ///     let c = c.clone();
+    ///     s.connect(move |value| c.set(c.get() + value));
+    /// });
+    /// *guard.borrow_mut() = Some(connection.into_guard());
+    ///
+    /// signal.emit(&10);
+    /// assert_eq!(calls.get(), 1);
+    /// assert_eq!(signal.get_slot_count(), 1);
+    ///
+    /// signal.emit(&10);
+    /// assert_eq!(calls.get(), 11);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `args` - Arguments given to the slots
+    pub fn emit(&self, args: &Args) {
+        // The slots are copied so they may connect or disconnect slots while being called
+        let slots: Vec<(Rc<Cell<bool>>, SlotCallback<Args>)> = self
+            .inner
+            .slots
+            .borrow()
+            .iter()
+            .map(|s| (s.connected.clone(), s.callback.clone()))
+            .collect();
+
+        for (connected, callback) in slots {
+            if !connected.get() {
+                continue;
+            }
+
+            if let Ok(mut callback) = callback.try_borrow_mut() {
+                callback(args);
+            }
+        }
+    }
+
+    /// Disconnects all slots
+    pub fn disconnect_all(&self) {
+        for slot in self.inner.slots.borrow_mut().drain(..) {
+            slot.connected.set(false);
+        }
+    }
+
+    /// Returns how many slots are connected
+    pub fn get_slot_count(&self) -> usize {
+        self.inner.slots.borrow().len()
+    }
+}
+
+impl<Args: 'static> Default for Signal<Args> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Args> Drop for Signal<Args> {
+    fn drop(&mut self) {
+        for slot in self.inner.slots.borrow().iter() {
+            slot.connected.set(false);
+        }
+    }
+}
+
+/// Connection between a [`Signal`] and one of its slots
+///
+/// Dropping a connection keeps the slot connected, use [`Connection::into_guard`] to
+/// disconnect it automatically.
+pub struct Connection {
+    signal: Weak<dyn Disconnect>,
+    id: u64,
+    connected: Rc<Cell<bool>>,
+}
+
+impl Connection {
+    /// Disconnects the slot from its signal
+    pub fn disconnect(&self) {
+        if let Some(signal) = self.signal.upgrade() {
+            signal.disconnect(self.id);
+        }
+        self.connected.set(false);
+    }
+
+    /// Returns true if the slot is still connected to its signal
+    pub fn is_connected(&self) -> bool {
+        self.connected.get()
+    }
+
+    /// Turns this connection into a guard which disconnects the slot when dropped
+    pub fn into_guard(self) -> ConnectionGuard {
+        ConnectionGuard {
+            connection: Some(self),
+        }
+    }
+}
+
+/// Disconnects its slot when dropped, used to tie a slot to the lifetime of an object
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+/// use nazara_core::signal::Signal;
+///
+/// let signal = Signal::<()>::new();
+/// let calls = Rc::new(Cell::new(0));
+///
+/// {
+///     let c = calls.clone();
+///     let _guard = signal.connect(move |_| c.set(c.get() + 1)).into_guard();
+///     signal.emit(&());
+/// }
+///
+/// signal.emit(&());
+/// assert_eq!(calls.get(), 1);
+/// assert_eq!(signal.get_slot_count(), 0);
+/// ```
+pub struct ConnectionGuard {
+    // Only taken by release, as the guard is consumed
+    connection: Option<Connection>,
+}
+
+impl ConnectionGuard {
+    /// Returns true if the slot is still connected to its signal
+    pub fn is_connected(&self) -> bool {
+        self.connection
+            .as_ref()
+            .is_some_and(|connection| connection.is_connected())
+    }
+
+    /// Releases the connection without disconnecting the slot
+    ///
+    /// ```
+    /// use nazara_core::signal::Signal;
+    ///
+    /// let signal = Signal::<()>::new();
+    /// let connection = signal.connect(|_| ()).into_guard().release();
+    /// assert!(connection.is_connected());
+    /// assert_eq!(signal.get_slot_count(), 1);
+    /// ```
+    pub fn release(mut self) -> Connection {
+        self.connection
+            .take()
+            .expect("the connection is only taken when releasing the guard")
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        if let Some(connection) = &self.connection {
+            connection.disconnect();
+        }
+    }
+}
+
+/// Queue of events which are emitted through a [`Signal`] only when dispatched
+///
+/// Useful to publish events from places where listeners can't run yet (while iterating on some
+/// data, in the middle of a frame...).
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use nazara_core::signal::EventQueue;
+///
+/// let queue = Rc::new(EventQueue::<String>::new());
+/// let received = Rc::new(RefCell::new(Vec::new()));
+///
+/// let (q, r) = (queue.clone(), received.clone());
+/// queue.get_signal().connect(move |event: &String| {
+///     r.borrow_mut().push(event.clone());
+///     // Events pushed while dispatching are kept for the next dispatch
+///     if event == "spawn" {
+///         q.push(String::from("spawned"));
+///     }
+/// });
+///
+/// queue.push(String::from("spawn"));
+/// assert!(received.borrow().is_empty());
+///
+/// assert_eq!(queue.dispatch(), 1);
+/// assert_eq!(*received.borrow(), vec!["spawn"]);
+/// assert_eq!(queue.dispatch(), 1);
+/// assert_eq!(*received.borrow(), vec!["spawn", "spawned"]);
+/// ```
+pub struct EventQueue<Args> {
+    events: RefCell<VecDeque<Args>>,
+    signal: Signal<Args>,
+}
+
+impl<Args: 'static> EventQueue<Args> {
+    /// Creates a new empty queue
+    pub fn new() -> EventQueue<Args> {
+        EventQueue {
+            events: RefCell::new(VecDeque::new()),
+            signal: Signal::new(),
+        }
+    }
+
+    /// Returns the signal emitted for each dispatched event
+    pub fn get_signal(&self) -> &Signal<Args> {
+        &self.signal
+    }
+
+    /// Queues an event
+    ///
+    /// # Arguments
+    /// * `event` - Event to emit on next dispatch
+    pub fn push(&self, event: Args) {
+        self.events.borrow_mut().push_back(event);
+    }
+
+    /// Returns how many events are waiting to be dispatched
+    pub fn len(&self) -> usize {
+        self.events.borrow().len()
+    }
+
+    /// Returns true if no event is waiting to be dispatched
+    pub fn is_empty(&self) -> bool {
+        self.events.borrow().is_empty()
+    }
+
+    /// Emits all queued events in order, returns how many events were dispatched
+    pub fn dispatch(&self) -> usize {
+        let events = std::mem::take(&mut *self.events.borrow_mut());
+        let count = events.len();
+        for event in events {
+            self.signal.emit(&event);
+        }
+
+        count
+    }
+}
+
+impl<Args: 'static> Default for EventQueue<Args> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
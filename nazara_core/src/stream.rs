@@ -0,0 +1,307 @@
+use std::io::{Read, Write};
+
+use crate::{
+    enums::Endianness,
+    errors::{NazaraError, NazaraResult, StreamError},
+};
+
+const LITTLE_ENDIAN_MARKER: u8 = 0;
+const BIG_ENDIAN_MARKER: u8 = 1;
+
+macro_rules! write_methods {
+    ($($name:ident: $type:ty),+) => {
+        $(
+            #[doc = concat!("Writes a `", stringify!($type), "` using the endianness of the stream")]
+            pub fn $name(&mut self, value: $type) -> NazaraResult<()> {
+                let bytes = match self.endianness {
+                    Endianness::BigEndian => value.to_be_bytes(),
+                    Endianness::LittleEndian => value.to_le_bytes(),
+                };
+
+                self.write_raw(&bytes)
+            }
+        )+
+    };
+}
+
+macro_rules! read_methods {
+    ($($name:ident: $type:ty),+) => {
+        $(
+            #[doc = concat!("Reads a `", stringify!($type), "` using the endianness of the stream")]
+            pub fn $name(&mut self) -> NazaraResult<$type> {
+                let mut bytes = [0u8; std::mem::size_of::<$type>()];
+                self.read_raw(&mut bytes)?;
+
+                Ok(match self.endianness {
+                    Endianness::BigEndian => <$type>::from_be_bytes(bytes),
+                    Endianness::LittleEndian => <$type>::from_le_bytes(bytes),
+                })
+            }
+        )+
+    };
+}
+
+/// Writes binary data with a chosen endianness
+///
+/// Streams can start with a header made of a format identifier, the endianness and a version,
+/// which [`BinaryReader::read_header`] checks and uses to configure itself.
+///
+/// ```
+/// use nazara_core::enums::Endianness;
+/// use nazara_core::stream::{BinaryReader, BinaryWriter};
+///
+/// let mut writer = BinaryWriter::new(Vec::new(), Endianness::BigEndian);
+/// writer.write_header(*b"NLVL", 2).unwrap();
+/// writer.write_string("first level").unwrap();
+/// writer.write_u16(0x1234).unwrap();
+/// writer.write_f32(1.5).unwrap();
+/// let data = writer.into_inner();
+/// assert_eq!(&data[9..13], &[0, 0, 0, 11]);
+///
+/// // The reader gets the endianness from the header
+/// let mut reader = BinaryReader::new(&data[..], Endianness::LittleEndian);
+/// let version = reader.read_header(*b"NLVL", 3).unwrap();
+/// assert_eq!(version, 2);
+/// assert_eq!(reader.get_endianness(), Endianness::BigEndian);
+/// assert_eq!(reader.read_string().unwrap(), "first level");
+/// assert_eq!(reader.read_u16().unwrap(), 0x1234);
+/// assert_eq!(reader.read_f32().unwrap(), 1.5);
+/// assert!(reader.read_u8().is_err());
+///
+/// // Streams from newer versions or other formats are rejected
+/// assert!(BinaryReader::new(&data[..], Endianness::native()).read_header(*b"NLVL", 1).is_err());
+/// assert!(BinaryReader::new(&data[..], Endianness::native()).read_header(*b"NSAV", 3).is_err());
+/// ```
+pub struct BinaryWriter<W: Write> {
+    writer: W,
+    endianness: Endianness,
+}
+
+impl<W: Write> BinaryWriter<W> {
+    /// Creates a new binary writer
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the data
+    /// * `endianness` - Byte order used to write values
+    pub fn new(writer: W, endianness: Endianness) -> BinaryWriter<W> {
+        BinaryWriter { writer, endianness }
+    }
+
+    /// Returns the byte order used to write values
+    pub fn get_endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Changes the byte order used to write values
+    ///
+    /// # Arguments
+    /// * `endianness` - New byte order
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Writes a header identifying the format, endianness and version of the stream
+    ///
+    /// # Arguments
+    /// * `magic` - Identifier of the format
+    /// * `version` - Version of the format
+    pub fn write_header(&mut self, magic: [u8; 4], version: u32) -> NazaraResult<()> {
+        self.write_raw(&magic)?;
+        self.write_u8(match self.endianness {
+            Endianness::BigEndian => BIG_ENDIAN_MARKER,
+            Endianness::LittleEndian => LITTLE_ENDIAN_MARKER,
+        })?;
+
+        self.write_u32(version)
+    }
+
+    write_methods!(
+        write_u8: u8,
+        write_u16: u16,
+        write_u32: u32,
+        write_u64: u64,
+        write_i8: i8,
+        write_i16: i16,
+        write_i32: i32,
+        write_i64: i64,
+        write_f32: f32,
+        write_f64: f64
+    );
+
+    /// Writes a boolean as one byte
+    ///
+    /// # Arguments
+    /// * `value` - Value to write
+    pub fn write_bool(&mut self, value: bool) -> NazaraResult<()> {
+        self.write_u8(value as u8)
+    }
+
+    /// Writes a byte array prefixed by its length
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes to write
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> NazaraResult<()> {
+        if bytes.len() > u32::MAX as usize {
+            return Err(NazaraError::from(StreamError::InvalidData(format!(
+                "{} bytes can't be written in a single block",
+                bytes.len()
+            ))));
+        }
+
+        self.write_u32(bytes.len() as u32)?;
+        self.write_raw(bytes)
+    }
+
+    /// Writes an UTF-8 string prefixed by its length
+    ///
+    /// # Arguments
+    /// * `value` - String to write
+    pub fn write_string(&mut self, value: &str) -> NazaraResult<()> {
+        self.write_bytes(value.as_bytes())
+    }
+
+    /// Writes bytes as is
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes to write
+    pub fn write_raw(&mut self, bytes: &[u8]) -> NazaraResult<()> {
+        self.writer
+            .write_all(bytes)
+            .map_err(|e| NazaraError::from(StreamError::from(e)))
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads binary data written by a [`BinaryWriter`]
+pub struct BinaryReader<R: Read> {
+    reader: R,
+    endianness: Endianness,
+}
+
+impl<R: Read> BinaryReader<R> {
+    /// Creates a new binary reader
+    ///
+    /// # Arguments
+    /// * `reader` - Source of the data
+    /// * `endianness` - Byte order used to read values, until a header is read
+    pub fn new(reader: R, endianness: Endianness) -> BinaryReader<R> {
+        BinaryReader { reader, endianness }
+    }
+
+    /// Returns the byte order used to read values
+    pub fn get_endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Changes the byte order used to read values
+    ///
+    /// # Arguments
+    /// * `endianness` - New byte order
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Reads a header written by [`BinaryWriter::write_header`], switches to the endianness
+    /// of the stream and returns its version
+    ///
+    /// # Arguments
+    /// * `magic` - Expected identifier of the format
+    /// * `max_version` - Latest version of the format this reader can read
+    pub fn read_header(&mut self, magic: [u8; 4], max_version: u32) -> NazaraResult<u32> {
+        let mut found = [0u8; 4];
+        self.read_raw(&mut found)?;
+        if found != magic {
+            return Err(NazaraError::from(StreamError::InvalidMagic));
+        }
+
+        self.endianness = match self.read_u8()? {
+            LITTLE_ENDIAN_MARKER => Endianness::LittleEndian,
+            BIG_ENDIAN_MARKER => Endianness::BigEndian,
+            marker => {
+                return Err(NazaraError::from(StreamError::InvalidData(format!(
+                    "unknown endianness marker {}",
+                    marker
+                ))))
+            }
+        };
+
+        let version = self.read_u32()?;
+        if version > max_version {
+            return Err(NazaraError::from(StreamError::UnsupportedVersion(version)));
+        }
+
+        Ok(version)
+    }
+
+    read_methods!(
+        read_u8: u8,
+        read_u16: u16,
+        read_u32: u32,
+        read_u64: u64,
+        read_i8: i8,
+        read_i16: i16,
+        read_i32: i32,
+        read_i64: i64,
+        read_f32: f32,
+        read_f64: f64
+    );
+
+    /// Reads a boolean written as one byte
+    pub fn read_bool(&mut self) -> NazaraResult<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(NazaraError::from(StreamError::InvalidData(format!(
+                "{} is not a boolean",
+                value
+            )))),
+        }
+    }
+
+    /// Reads a byte array prefixed by its length
+    pub fn read_bytes(&mut self) -> NazaraResult<Vec<u8>> {
+        let len = self.read_u32()? as usize;
+
+        // The length isn't trusted to allocate, a corrupted stream just ends early
+        let mut bytes = Vec::new();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|e| NazaraError::from(StreamError::from(e)))?;
+
+        if bytes.len() != len {
+            return Err(NazaraError::from(StreamError::InvalidData(format!(
+                "expected {} bytes, the stream ended after {}",
+                len,
+                bytes.len()
+            ))));
+        }
+
+        Ok(bytes)
+    }
+
+    /// Reads an UTF-8 string prefixed by its length
+    pub fn read_string(&mut self) -> NazaraResult<String> {
+        String::from_utf8(self.read_bytes()?)
+            .map_err(|e| NazaraError::from(StreamError::InvalidData(e.to_string())))
+    }
+
+    /// Reads bytes as is, filling the whole buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Buffer receiving the bytes
+    pub fn read_raw(&mut self, bytes: &mut [u8]) -> NazaraResult<()> {
+        self.reader
+            .read_exact(bytes)
+            .map_err(|e| NazaraError::from(StreamError::from(e)))
+    }
+
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
@@ -0,0 +1,442 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Condvar, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    errors::{NazaraError, NazaraResult, TaskError},
+    nz_profile_scope,
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+const STATUS_PENDING: u8 = 0;
+const STATUS_RUNNING: u8 = 1;
+const STATUS_COMPLETED: u8 = 2;
+const STATUS_CANCELLED: u8 = 3;
+const STATUS_PANICKED: u8 = 4;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents the state of a task
+pub enum TaskStatus {
+    /// Task is waiting for a worker thread
+    Pending,
+    /// Task is being executed by a worker thread
+    Running,
+    /// Task has finished and its result is available
+    Completed,
+    /// Task was cancelled before being executed
+    Cancelled,
+    /// Task panicked while being executed, it has no result
+    Panicked,
+}
+
+struct TaskState<T> {
+    status: AtomicU8,
+    result: Mutex<Option<T>>,
+    finished: Condvar,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl<T> TaskState<T> {
+    fn finish(&self, status: u8, result: Option<T>) {
+        let mut guard = self.result.lock().unwrap();
+        *guard = result;
+        self.status.store(status, Ordering::Release);
+        self.finished.notify_all();
+        drop(guard);
+
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Gives a running task access to its progress and cancellation state
+pub struct TaskContext {
+    progress: Arc<AtomicU32>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskContext {
+    /// Reports the progress of the task, between 0.0 and 1.0
+    pub fn set_progress(&self, progress: f32) {
+        let progress = progress.clamp(0.0, 1.0);
+        self.progress.store(progress.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns true if the task was asked to stop, long tasks should check it regularly
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Handle to a task running on a [`TaskScheduler`]
+///
+/// The handle can be polled each frame, waited on, or awaited as a [`std::future::Future`]
+/// (resolving to the same value as [`TaskHandle::wait`]).
+pub struct TaskHandle<T> {
+    state: Arc<TaskState<T>>,
+    // Shared with the TaskContext, kept apart so the context doesn't depend on T
+    progress: Arc<AtomicU32>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> TaskHandle<T> {
+    /// Returns the current status of the task
+    pub fn get_status(&self) -> TaskStatus {
+        match self.state.status.load(Ordering::Acquire) {
+            STATUS_PENDING => TaskStatus::Pending,
+            STATUS_RUNNING => TaskStatus::Running,
+            STATUS_COMPLETED => TaskStatus::Completed,
+            STATUS_CANCELLED => TaskStatus::Cancelled,
+            _ => TaskStatus::Panicked,
+        }
+    }
+
+    /// Returns true if the task is either completed, cancelled or panicked
+    pub fn is_finished(&self) -> bool {
+        match self.get_status() {
+            TaskStatus::Completed | TaskStatus::Cancelled | TaskStatus::Panicked => true,
+            TaskStatus::Pending | TaskStatus::Running => false,
+        }
+    }
+
+    /// Returns the last progress reported by the task, between 0.0 and 1.0
+    pub fn get_progress(&self) -> f32 {
+        if self.get_status() == TaskStatus::Completed {
+            return 1.0;
+        }
+
+        f32::from_bits(self.progress.load(Ordering::Relaxed))
+    }
+
+    /// Asks the task to stop
+    ///
+    /// A pending task won't be executed, a running task has to check
+    /// [`TaskContext::is_cancelled`] by itself.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Takes the result of the task if it is completed, without blocking
+    pub fn try_take(&self) -> Option<T> {
+        self.state.result.lock().unwrap().take()
+    }
+
+    /// Blocks until the task is finished and returns its result
+    ///
+    /// Returns a [`TaskError`] if the task was cancelled before running, if it panicked, or if
+    /// its result was already taken by [`TaskHandle::try_take`], a previous wait or a
+    /// completion callback.
+    pub fn wait(&self) -> NazaraResult<T> {
+        let mut result = self.state.result.lock().unwrap();
+        while !self.is_finished() {
+            result = self.state.finished.wait(result).unwrap();
+        }
+
+        self.take_finished(result.take())
+    }
+
+    fn take_finished(&self, result: Option<T>) -> NazaraResult<T> {
+        let error = match (result, self.get_status()) {
+            (Some(result), _) => return Ok(result),
+            (None, TaskStatus::Cancelled) => TaskError::Cancelled,
+            (None, TaskStatus::Panicked) => TaskError::Panicked,
+            (None, _) => TaskError::ResultTaken,
+        };
+
+        Err(NazaraError::from(error))
+    }
+}
+
+impl<T> Clone for TaskHandle<T> {
+    fn clone(&self) -> Self {
+        TaskHandle {
+            state: self.state.clone(),
+            progress: self.progress.clone(),
+            cancelled: self.cancelled.clone(),
+        }
+    }
+}
+
+impl<T> Future for TaskHandle<T> {
+    type Output = NazaraResult<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        *self.state.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        if self.is_finished() {
+            Poll::Ready(self.take_finished(self.try_take()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+trait Completion {
+    /// Runs the callback if the task is finished, returns false if it is still running
+    fn try_complete(&mut self) -> bool;
+}
+
+struct PendingCallback<T, F> {
+    handle: TaskHandle<T>,
+    callback: Option<F>,
+}
+
+impl<T, F: FnOnce(T)> Completion for PendingCallback<T, F> {
+    fn try_complete(&mut self) -> bool {
+        if !self.handle.is_finished() {
+            return false;
+        }
+
+        if let (Some(result), Some(callback)) = (self.handle.try_take(), self.callback.take()) {
+            callback(result);
+        }
+
+        true
+    }
+}
+
+/// Thread pool running tasks in the background
+///
+/// Worker threads are started when the first task is spawned. Completion callbacks are run on
+/// the thread owning the scheduler when calling [`TaskScheduler::process_completions`], which
+/// [`Application::execute`](crate::application::Application::execute) does each frame.
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+/// use nazara_core::task::{TaskScheduler, TaskStatus};
+///
+/// let mut scheduler = TaskScheduler::new(2);
+/// let handle = scheduler.spawn(|ctx| {
+///     let mut sum = 0u64;
+///     for i in 0..1000u64 {
+///         sum += i;
+///         ctx.set_progress(i as f32 / 1000.0);
+///     }
+///     sum
+/// });
+///
+/// // Callbacks don't need to be Send as they are run on this thread
+/// let result = Rc::new(Cell::new(0));
+/// let output = result.clone();
+/// scheduler.on_complete(&handle, move |sum| output.set(sum));
+///
+/// while !handle.is_finished() {
+///     std::thread::yield_now();
+/// }
+/// assert_eq!(handle.get_status(), TaskStatus::Completed);
+/// assert_eq!(handle.get_progress(), 1.0);
+///
+/// scheduler.process_completions();
+/// assert_eq!(result.get(), 499500);
+/// ```
+pub struct TaskScheduler {
+    thread_count: usize,
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+    shutdown: Arc<AtomicBool>,
+    callbacks: Vec<Box<dyn Completion>>,
+}
+
+impl TaskScheduler {
+    /// Creates a new scheduler
+    ///
+    /// # Arguments
+    /// * `thread_count` - Number of worker threads, 0 to use the number of available cores
+    pub fn new(thread_count: usize) -> TaskScheduler {
+        let thread_count = if thread_count == 0 {
+            thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        } else {
+            thread_count
+        };
+
+        TaskScheduler {
+            thread_count,
+            sender: None,
+            workers: Vec::new(),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Returns how many worker threads this scheduler uses
+    pub fn get_thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    /// Runs a task on a worker thread
+    ///
+    /// ```
+    /// use std::{thread, time::Duration};
+    /// use nazara_core::task::{TaskScheduler, TaskStatus};
+    ///
+    /// let mut scheduler = TaskScheduler::new(1);
+    ///
+    /// // Keeps the only worker busy until cancelled
+    /// let busy = scheduler.spawn(|ctx| {
+    ///     while !ctx.is_cancelled() {
+    ///         thread::sleep(Duration::from_millis(1));
+    ///     }
+    ///     "stopped"
+    /// });
+    /// let queued = scheduler.spawn(|_| "never run");
+    ///
+    /// while busy.get_status() == TaskStatus::Pending {
+    ///     thread::yield_now();
+    /// }
+    /// queued.cancel();
+    /// busy.cancel();
+    ///
+    /// assert_eq!(busy.wait().unwrap(), "stopped");
+    /// assert!(busy.wait().is_err(), "the result was already taken");
+    /// assert!(queued.wait().is_err());
+    /// assert_eq!(queued.get_status(), TaskStatus::Cancelled);
+    ///
+    /// // A panicking task doesn't stop its worker
+    /// let panicking = scheduler.spawn(|_| -> u32 { panic!("task failed") });
+    /// assert!(panicking.wait().is_err());
+    /// assert_eq!(panicking.get_status(), TaskStatus::Panicked);
+    /// assert_eq!(scheduler.spawn(|_| 42).wait().unwrap(), 42);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `task` - Function to run, receiving a [`TaskContext`]
+    pub fn spawn<T, F>(&mut self, task: F) -> TaskHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&TaskContext) -> T + Send + 'static,
+    {
+        let handle = TaskHandle {
+            state: Arc::new(TaskState {
+                status: AtomicU8::new(STATUS_PENDING),
+                result: Mutex::new(None),
+                finished: Condvar::new(),
+                waker: Mutex::new(None),
+            }),
+            progress: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+
+        let context = TaskContext {
+            progress: handle.progress.clone(),
+            cancelled: handle.cancelled.clone(),
+        };
+        let state = handle.state.clone();
+        let shutdown = self.shutdown.clone();
+
+        let job: Job = Box::new(move || {
+            if context.is_cancelled() || shutdown.load(Ordering::Relaxed) {
+                state.finish(STATUS_CANCELLED, None);
+                return;
+            }
+
+            state.status.store(STATUS_RUNNING, Ordering::Release);
+            // The worker outlives a panicking task, and the handle still gets notified
+            match panic::catch_unwind(AssertUnwindSafe(|| task(&context))) {
+                Ok(result) => state.finish(STATUS_COMPLETED, Some(result)),
+                Err(_) => state.finish(STATUS_PANICKED, None),
+            }
+        });
+
+        self.sender().send(job).unwrap();
+
+        handle
+    }
+
+    /// Registers a callback receiving the result of a task once it is completed
+    ///
+    /// The callback is run by [`TaskScheduler::process_completions`] on the thread owning the
+    /// scheduler, it is dropped without being called if the task is cancelled before running or
+    /// if its result was taken through the handle.
+    ///
+    /// # Arguments
+    /// * `handle` - Handle of the task
+    /// * `callback` - Function called with the result of the task
+    pub fn on_complete<T, F>(&mut self, handle: &TaskHandle<T>, callback: F)
+    where
+        T: 'static,
+        F: FnOnce(T) + 'static,
+    {
+        self.callbacks.push(Box::new(PendingCallback {
+            handle: handle.clone(),
+            callback: Some(callback),
+        }));
+    }
+
+    /// Runs the callbacks of finished tasks, returns how many callbacks were processed
+    pub fn process_completions(&mut self) -> usize {
+        let count = self.callbacks.len();
+        self.callbacks.retain_mut(|c| !c.try_complete());
+
+        count - self.callbacks.len()
+    }
+
+    /// Returns how many completion callbacks are waiting for their task
+    pub fn get_pending_callback_count(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    fn sender(&mut self) -> &Sender<Job> {
+        if self.sender.is_none() {
+            let (sender, receiver) = channel::<Job>();
+            let receiver = Arc::new(Mutex::new(receiver));
+
+            for i in 0..self.thread_count {
+                let receiver = receiver.clone();
+                let worker = thread::Builder::new()
+                    .name(format!("nazara task worker #{}", i))
+                    .spawn(move || TaskScheduler::worker_loop(&receiver))
+                    .unwrap();
+
+                self.workers.push(worker);
+            }
+
+            self.sender = Some(sender);
+        }
+
+        self.sender.as_ref().unwrap()
+    }
+
+    fn worker_loop(receiver: &Mutex<Receiver<Job>>) {
+        loop {
+            let job = receiver.lock().unwrap().recv();
+            match job {
+                Ok(job) => {
+                    nz_profile_scope!("task", "task");
+                    job()
+                }
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl Default for TaskScheduler {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Drop for TaskScheduler {
+    fn drop(&mut self) {
+        // Queued tasks are cancelled, running ones are waited for
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.sender = None;
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::io::{self, BufRead, Read, Seek};
+
+use crate::errors::{NazaraError, NazaraResult, ResourceError};
+
+mod archive;
+mod directory;
+mod memory;
+
+pub use self::{
+    archive::{PakBackend, ZipBackend},
+    directory::DirectoryBackend,
+    memory::MemoryBackend,
+};
+
+/// Stream returned when opening a file from the [`Vfs`]
+///
+/// Any [`std::io::BufRead`] + [`std::io::Seek`] type is a stream, so they can be given to
+/// [`ImageLoader::load_from_reader`](crate::image::ImageLoader::load_from_reader).
+pub trait VfsStream: BufRead + Seek + Send {}
+
+impl<T: BufRead + Seek + Send> VfsStream for T {}
+
+/// Storage mounted in a [`Vfs`]
+///
+/// Paths given to backends are relative to their mount point, normalized and `/`-separated
+/// (the root of the backend being the empty string).
+pub trait VfsBackend: Send + Sync {
+    /// Opens a file for reading, returns an error of kind [`std::io::ErrorKind::NotFound`] if
+    /// the file does not exist in this backend
+    fn open(&self, path: &str) -> io::Result<Box<dyn VfsStream>>;
+
+    /// Returns true if the file or directory exists in this backend
+    fn exists(&self, path: &str) -> bool;
+
+    /// Returns the names of the files and directories directly inside a directory
+    fn list(&self, path: &str) -> io::Result<Vec<String>>;
+}
+
+struct Mount {
+    name: String,
+    backend: Box<dyn VfsBackend>,
+}
+
+/// Virtual filesystem giving access to files through mount points (`assets:/textures/a.png`)
+///
+/// Many backends can be mounted on the same mount point, in which case they are layered: the
+/// last mounted backend is searched first, allowing mods or patches to override base files.
+///
+/// ```
+/// use nazara_core::vfs::{MemoryBackend, Vfs};
+///
+/// let base = MemoryBackend::new();
+/// base.insert("config/game.ini", b"base".to_vec());
+/// base.insert("config/keys.ini", b"keys".to_vec());
+///
+/// let patch = MemoryBackend::new();
+/// patch.insert("config/game.ini", b"patched".to_vec());
+///
+/// let mut vfs = Vfs::new();
+/// vfs.mount("assets", base).unwrap();
+/// vfs.mount("assets", patch).unwrap();
+///
+/// assert_eq!(vfs.read("assets:/config/game.ini").unwrap(), b"patched");
+/// assert_eq!(vfs.read("assets:/config/keys.ini").unwrap(), b"keys");
+/// assert_eq!(vfs.list("assets:/config").unwrap(), vec!["game.ini", "keys.ini"]);
+/// assert!(vfs.read("assets:/../secret").is_err());
+/// assert!(vfs.read("music:/theme.ogg").is_err());
+/// ```
+pub struct Vfs {
+    mounts: Vec<Mount>,
+}
+
+impl Vfs {
+    /// Creates a new virtual filesystem without any mount point
+    pub fn new() -> Vfs {
+        Vfs { mounts: Vec::new() }
+    }
+
+    /// Mounts a backend, layering it above the backends already mounted at this mount point
+    ///
+    /// # Arguments
+    /// * `mount_point` - Name of the mount point (`assets` for `assets:/` paths)
+    /// * `backend` - Backend to mount
+    pub fn mount<B: VfsBackend + 'static>(
+        &mut self,
+        mount_point: &str,
+        backend: B,
+    ) -> NazaraResult<()> {
+        let name = mount_point.trim_end_matches(":/").trim_end_matches(':');
+        if name.is_empty() || name.contains(['/', '\\', ':']) {
+            return Err(NazaraError::from(ResourceError::InvalidPath(
+                mount_point.to_string(),
+            )));
+        }
+
+        self.mounts.push(Mount {
+            name: name.to_string(),
+            backend: Box::new(backend),
+        });
+
+        Ok(())
+    }
+
+    /// Unmounts all backends of a mount point, returns how many backends were removed
+    ///
+    /// # Arguments
+    /// * `mount_point` - Name of the mount point
+    pub fn unmount(&mut self, mount_point: &str) -> usize {
+        let name = mount_point.trim_end_matches(":/").trim_end_matches(':');
+        let count = self.mounts.len();
+        self.mounts.retain(|m| m.name != name);
+
+        count - self.mounts.len()
+    }
+
+    /// Opens a file from the topmost backend containing it
+    ///
+    /// # Arguments
+    /// * `path` - Virtual path of the file (`mount:/dir/file`)
+    pub fn open(&self, path: &str) -> NazaraResult<Box<dyn VfsStream>> {
+        let (name, relative) = Vfs::split_path(path)?;
+        for mount in self.layers(name) {
+            match mount.backend.open(&relative) {
+                Ok(stream) => return Ok(stream),
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(NazaraError::from(ResourceError::from(e))),
+            }
+        }
+
+        Err(NazaraError::from(ResourceError::NotFound(path.to_string())))
+    }
+
+    /// Reads the whole content of a file
+    ///
+    /// # Arguments
+    /// * `path` - Virtual path of the file (`mount:/dir/file`)
+    pub fn read(&self, path: &str) -> NazaraResult<Vec<u8>> {
+        let mut stream = self.open(path)?;
+        let mut content = Vec::new();
+        stream
+            .read_to_end(&mut content)
+            .map_err(|e| NazaraError::from(ResourceError::from(e)))?;
+
+        Ok(content)
+    }
+
+    /// Returns true if a backend of the mount point contains this file or directory
+    ///
+    /// # Arguments
+    /// * `path` - Virtual path (`mount:/dir/file`)
+    pub fn exists(&self, path: &str) -> bool {
+        match Vfs::split_path(path) {
+            Ok((name, relative)) => self.layers(name).any(|m| m.backend.exists(&relative)),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the sorted names of the entries of a directory, merged from all layers
+    ///
+    /// # Arguments
+    /// * `path` - Virtual path of the directory (`mount:/dir`)
+    pub fn list(&self, path: &str) -> NazaraResult<Vec<String>> {
+        let (name, relative) = Vfs::split_path(path)?;
+        let mut found = false;
+        let mut entries = Vec::new();
+        for mount in self.layers(name) {
+            match mount.backend.list(&relative) {
+                Ok(names) => {
+                    found = true;
+                    entries.extend(names);
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(NazaraError::from(ResourceError::from(e))),
+            }
+        }
+
+        if !found {
+            return Err(NazaraError::from(ResourceError::NotFound(path.to_string())));
+        }
+
+        entries.sort();
+        entries.dedup();
+        Ok(entries)
+    }
+
+    fn layers<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Mount> {
+        self.mounts.iter().rev().filter(move |m| m.name == name)
+    }
+
+    fn split_path(path: &str) -> NazaraResult<(&str, String)> {
+        let invalid = || NazaraError::from(ResourceError::InvalidPath(path.to_string()));
+
+        let separator = path.find(':').ok_or_else(invalid)?;
+        let (name, relative) = (&path[..separator], &path[separator + 1..]);
+        if name.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok((name, normalize_path(relative).ok_or_else(invalid)?))
+    }
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normalizes a relative path, returns `None` if it goes above its root
+///
+/// ```
+/// use nazara_core::vfs::normalize_path;
+///
+/// assert_eq!(normalize_path("/textures//./a.png").unwrap(), "textures/a.png");
+/// assert_eq!(normalize_path("textures\\old\\..\\a.png").unwrap(), "textures/a.png");
+/// assert_eq!(normalize_path("/").unwrap(), "");
+/// assert_eq!(normalize_path("../a.png"), None);
+/// ```
+///
+/// # Arguments
+/// * `path` - Path to normalize, using `/` or `\` as separator
+pub fn normalize_path(path: &str) -> Option<String> {
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => (),
+            ".." => {
+                components.pop()?;
+            }
+            c => components.push(c),
+        }
+    }
+
+    Some(components.join("/"))
+}
+
+/// Returns the names of the direct children of a directory from a list of file paths
+pub(crate) fn list_children<'a, I>(paths: I, directory: &str) -> io::Result<Vec<String>>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let prefix = if directory.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", directory)
+    };
+
+    let mut found = directory.is_empty();
+    let mut children = Vec::new();
+    for path in paths {
+        if let Some(rest) = path.strip_prefix(prefix.as_str()) {
+            found = true;
+            if let Some(child) = rest.split('/').next().filter(|c| !c.is_empty()) {
+                if !children.iter().any(|c| c == child) {
+                    children.push(child.to_string());
+                }
+            }
+        }
+    }
+
+    if found {
+        Ok(children)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            directory.to_string(),
+        ))
+    }
+}
+
+/// Returns true if a path is a file or a directory in a list of file paths
+pub(crate) fn contains_path<'a, I>(mut paths: I, path: &str) -> bool
+where
+    I: Iterator<Item = &'a str>,
+{
+    paths.any(|p| {
+        p == path
+            || path.is_empty()
+            || (p.starts_with(path) && p.as_bytes().get(path.len()) == Some(&b'/'))
+    })
+}
+
+pub(crate) fn read_to_stream<R: Read>(mut reader: R) -> io::Result<Box<dyn VfsStream>> {
+    let mut content = Vec::new();
+    reader.read_to_end(&mut content)?;
+
+    Ok(Box::new(io::Cursor::new(content)))
+}
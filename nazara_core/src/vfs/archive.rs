@@ -0,0 +1,251 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::Path,
+    sync::Mutex,
+};
+
+use zip::ZipArchive;
+
+use super::{contains_path, list_children, normalize_path, read_to_stream, VfsBackend, VfsStream};
+use crate::errors::{NazaraError, NazaraResult, ResourceError};
+
+/// Readable archive source
+pub trait ArchiveReader: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ArchiveReader for T {}
+
+/// Read-only backend giving access to the files of a zip archive
+///
+/// Files are decompressed in memory when opened.
+///
+/// ```
+/// use std::io::{Cursor, Write};
+/// use nazara_core::vfs::{Vfs, ZipBackend};
+/// use zip::{write::FileOptions, ZipWriter};
+///
+/// let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+/// writer.start_file("textures/grass.raw", FileOptions::default()).unwrap();
+/// writer.write_all(b"grass").unwrap();
+/// let archive = writer.finish().unwrap();
+///
+/// let mut vfs = Vfs::new();
+/// vfs.mount("data", ZipBackend::from_reader(archive).unwrap()).unwrap();
+///
+/// assert_eq!(vfs.read("data:/textures/grass.raw").unwrap(), b"grass");
+/// assert_eq!(vfs.list("data:/").unwrap(), vec!["textures"]);
+/// ```
+pub struct ZipBackend {
+    archive: Mutex<ZipArchive<Box<dyn ArchiveReader>>>,
+    // Normalized path to name inside the archive
+    files: BTreeMap<String, String>,
+}
+
+impl ZipBackend {
+    /// Opens a zip archive from a file
+    ///
+    /// # Arguments
+    /// * `file` - [`std::path::Path`] of the archive
+    pub fn open(file: &Path) -> NazaraResult<ZipBackend> {
+        let file = File::open(file).map_err(|e| NazaraError::from(ResourceError::from(e)))?;
+        ZipBackend::from_reader(BufReader::new(file))
+    }
+
+    /// Opens a zip archive from a stream
+    ///
+    /// # Arguments
+    /// * `reader` - Stream containing the archive
+    pub fn from_reader<R: ArchiveReader + 'static>(reader: R) -> NazaraResult<ZipBackend> {
+        let reader: Box<dyn ArchiveReader> = Box::new(reader);
+        let archive =
+            ZipArchive::new(reader).map_err(|e| NazaraError::from(ResourceError::from(e)))?;
+
+        let files = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .filter_map(|name| normalize_path(name).map(|path| (path, name.to_string())))
+            .collect();
+
+        Ok(ZipBackend {
+            archive: Mutex::new(archive),
+            files,
+        })
+    }
+}
+
+impl VfsBackend for ZipBackend {
+    fn open(&self, path: &str) -> io::Result<Box<dyn VfsStream>> {
+        let name = self
+            .files
+            .get(path)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.to_string()))?;
+
+        let mut archive = self.archive.lock().unwrap();
+        let file = archive.by_name(name)?;
+        read_to_stream(file)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        contains_path(self.files.keys().map(|p| p.as_str()), path)
+    }
+
+    fn list(&self, path: &str) -> io::Result<Vec<String>> {
+        list_children(self.files.keys().map(|p| p.as_str()), path)
+    }
+}
+
+const PAK_MAGIC: &[u8; 4] = b"PACK";
+const PAK_ENTRY_SIZE: u64 = 64;
+const PAK_NAME_SIZE: usize = 56;
+
+/// Read-only backend giving access to the files of a pak archive
+///
+/// Pak archives are uncompressed archives using the Quake format: a `PACK` magic followed by the
+/// offset and size of a directory of 64 bytes entries (56 bytes name, offset and size), all
+/// integers being little-endian.
+///
+/// ```
+/// use std::io::Cursor;
+/// use nazara_core::vfs::{PakBackend, Vfs};
+///
+/// let mut pak = Vec::new();
+/// pak.extend_from_slice(b"PACK");
+/// pak.extend_from_slice(&(12u32 + 5).to_le_bytes());
+/// pak.extend_from_slice(&64u32.to_le_bytes());
+/// pak.extend_from_slice(b"hello");
+/// let mut name = [0u8; 56];
+/// name[..9].copy_from_slice(b"maps/e1m1");
+/// pak.extend_from_slice(&name);
+/// pak.extend_from_slice(&12u32.to_le_bytes());
+/// pak.extend_from_slice(&5u32.to_le_bytes());
+///
+/// let mut vfs = Vfs::new();
+/// vfs.mount("pak0", PakBackend::from_reader(Cursor::new(pak.clone())).unwrap()).unwrap();
+///
+/// assert_eq!(vfs.read("pak0:/maps/e1m1").unwrap(), b"hello");
+/// assert!(vfs.exists("pak0:/maps"));
+///
+/// // Files are checked to be inside of the archive
+/// let size_offset = pak.len() - 4;
+/// pak[size_offset..].copy_from_slice(&u32::MAX.to_le_bytes());
+/// assert!(PakBackend::from_reader(Cursor::new(pak)).is_err());
+/// ```
+pub struct PakBackend {
+    reader: Mutex<Box<dyn ArchiveReader>>,
+    // Normalized path to offset and size of the file
+    files: BTreeMap<String, (u64, u64)>,
+}
+
+impl PakBackend {
+    /// Opens a pak archive from a file
+    ///
+    /// # Arguments
+    /// * `file` - [`std::path::Path`] of the archive
+    pub fn open(file: &Path) -> NazaraResult<PakBackend> {
+        let file = File::open(file).map_err(|e| NazaraError::from(ResourceError::from(e)))?;
+        PakBackend::from_reader(BufReader::new(file))
+    }
+
+    /// Opens a pak archive from a stream
+    ///
+    /// # Arguments
+    /// * `reader` - Stream containing the archive
+    pub fn from_reader<R: ArchiveReader + 'static>(mut reader: R) -> NazaraResult<PakBackend> {
+        let files = PakBackend::read_directory(&mut reader)?;
+
+        Ok(PakBackend {
+            reader: Mutex::new(Box::new(reader)),
+            files,
+        })
+    }
+
+    fn read_directory<R: Read + Seek>(
+        reader: &mut R,
+    ) -> NazaraResult<BTreeMap<String, (u64, u64)>> {
+        let invalid = |e: &str| NazaraError::from(ResourceError::ArchiveError(e.to_string()));
+        let io_error = |e: io::Error| NazaraError::from(ResourceError::from(e));
+
+        let mut header = [0u8; 12];
+        reader.read_exact(&mut header).map_err(io_error)?;
+        if &header[0..4] != PAK_MAGIC {
+            return Err(invalid("not a pak archive"));
+        }
+
+        let directory_offset = read_u32(&header[4..8]) as u64;
+        let directory_size = read_u32(&header[8..12]) as u64;
+        if !directory_size.is_multiple_of(PAK_ENTRY_SIZE) {
+            return Err(invalid("invalid pak directory size"));
+        }
+
+        // Offsets and sizes are untrusted, everything must be inside of the archive
+        let archive_size = reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+        if directory_offset + directory_size > archive_size {
+            return Err(invalid("pak directory is outside of the archive"));
+        }
+
+        reader
+            .seek(SeekFrom::Start(directory_offset))
+            .map_err(io_error)?;
+
+        let mut files = BTreeMap::new();
+        let mut entry = [0u8; PAK_ENTRY_SIZE as usize];
+        for _ in 0..directory_size / PAK_ENTRY_SIZE {
+            reader.read_exact(&mut entry).map_err(io_error)?;
+
+            let name = &entry[..PAK_NAME_SIZE];
+            let name_len = name.iter().position(|&c| c == 0).unwrap_or(PAK_NAME_SIZE);
+            let name = std::str::from_utf8(&name[..name_len])
+                .map_err(|_| invalid("invalid pak file name"))?;
+            let offset = read_u32(&entry[PAK_NAME_SIZE..PAK_NAME_SIZE + 4]) as u64;
+            let size = read_u32(&entry[PAK_NAME_SIZE + 4..]) as u64;
+            if offset + size > archive_size {
+                return Err(invalid("pak file is outside of the archive"));
+            }
+
+            if let Some(path) = normalize_path(name) {
+                files.insert(path, (offset, size));
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+impl VfsBackend for PakBackend {
+    fn open(&self, path: &str) -> io::Result<Box<dyn VfsStream>> {
+        let &(offset, size) = self
+            .files
+            .get(path)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.to_string()))?;
+
+        let mut reader = self.reader.lock().unwrap();
+        reader.seek(SeekFrom::Start(offset))?;
+
+        // The size was checked when opening the archive, which could have been truncated since
+        let mut content = Vec::new();
+        (&mut *reader).take(size).read_to_end(&mut content)?;
+        if content.len() as u64 != size {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("{} is truncated", path),
+            ));
+        }
+
+        Ok(Box::new(io::Cursor::new(content)))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        contains_path(self.files.keys().map(|p| p.as_str()), path)
+    }
+
+    fn list(&self, path: &str) -> io::Result<Vec<String>> {
+        list_children(self.files.keys().map(|p| p.as_str()), path)
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut value = [0u8; 4];
+    value.copy_from_slice(bytes);
+    u32::from_le_bytes(value)
+}
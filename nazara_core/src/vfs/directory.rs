@@ -0,0 +1,63 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+use super::{VfsBackend, VfsStream};
+
+/// Backend giving access to a directory of the real filesystem
+///
+/// ```
+/// use nazara_core::vfs::{DirectoryBackend, Vfs};
+/// use nazara_core::image::ImageLoader;
+/// use cgmath::Vector3;
+///
+/// let mut vfs = Vfs::new();
+/// vfs.mount("assets", DirectoryBackend::new("./test_ressources")).unwrap();
+///
+/// assert!(vfs.exists("assets:/image.png"));
+/// assert!(vfs.list("assets:/").unwrap().contains(&"image.gif".to_string()));
+///
+/// let image = ImageLoader::load_from_reader(vfs.open("assets:/image.png").unwrap()).unwrap();
+/// assert_eq!(image.get_dims(), Vector3 { x: 800, y: 629, z: 1 });
+/// ```
+pub struct DirectoryBackend {
+    root: PathBuf,
+}
+
+impl DirectoryBackend {
+    /// Creates a new backend for a directory
+    ///
+    /// # Arguments
+    /// * `root` - Directory which will be the root of the mount point
+    pub fn new<P: AsRef<Path>>(root: P) -> DirectoryBackend {
+        DirectoryBackend {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        path.split('/')
+            .filter(|c| !c.is_empty())
+            .fold(self.root.clone(), |p, c| p.join(c))
+    }
+}
+
+impl VfsBackend for DirectoryBackend {
+    fn open(&self, path: &str) -> io::Result<Box<dyn VfsStream>> {
+        let file = File::open(self.resolve(path))?;
+
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.resolve(path).exists()
+    }
+
+    fn list(&self, path: &str) -> io::Result<Vec<String>> {
+        fs::read_dir(self.resolve(path))?
+            .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+            .collect()
+    }
+}
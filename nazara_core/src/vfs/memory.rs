@@ -0,0 +1,90 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Cursor},
+    sync::{Arc, RwLock},
+};
+
+use super::{contains_path, list_children, normalize_path, VfsBackend, VfsStream};
+
+/// Shared immutable file content, so opening a file does not copy it
+struct SharedBytes(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Backend storing files in memory
+///
+/// Clones of a memory backend share the same files, so files can still be added after the
+/// backend has been mounted.
+///
+/// ```
+/// use nazara_core::vfs::{MemoryBackend, Vfs};
+///
+/// let memory = MemoryBackend::new();
+///
+/// let mut vfs = Vfs::new();
+/// vfs.mount("tmp", memory.clone()).unwrap();
+/// assert!(!vfs.exists("tmp:/save/slot1.dat"));
+///
+/// memory.insert("save/slot1.dat", vec![1, 2, 3]);
+/// assert!(vfs.exists("tmp:/save"));
+/// assert_eq!(vfs.read("tmp:/save/slot1.dat").unwrap(), vec![1, 2, 3]);
+///
+/// memory.remove("save/slot1.dat");
+/// assert!(!vfs.exists("tmp:/save/slot1.dat"));
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryBackend {
+    files: Arc<RwLock<BTreeMap<String, Arc<Vec<u8>>>>>,
+}
+
+impl MemoryBackend {
+    /// Creates a new empty memory backend
+    pub fn new() -> MemoryBackend {
+        MemoryBackend::default()
+    }
+
+    /// Adds or replaces a file
+    ///
+    /// # Arguments
+    /// * `path` - Path of the file, relative to the backend root
+    /// * `content` - Content of the file
+    pub fn insert(&self, path: &str, content: Vec<u8>) {
+        if let Some(path) = normalize_path(path) {
+            self.files.write().unwrap().insert(path, Arc::new(content));
+        }
+    }
+
+    /// Removes a file, returns true if it existed
+    ///
+    /// # Arguments
+    /// * `path` - Path of the file, relative to the backend root
+    pub fn remove(&self, path: &str) -> bool {
+        match normalize_path(path) {
+            Some(path) => self.files.write().unwrap().remove(&path).is_some(),
+            None => false,
+        }
+    }
+}
+
+impl VfsBackend for MemoryBackend {
+    fn open(&self, path: &str) -> io::Result<Box<dyn VfsStream>> {
+        match self.files.read().unwrap().get(path) {
+            Some(content) => Ok(Box::new(Cursor::new(SharedBytes(content.clone())))),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, path.to_string())),
+        }
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let files = self.files.read().unwrap();
+        contains_path(files.keys().map(|p| p.as_str()), path)
+    }
+
+    fn list(&self, path: &str) -> io::Result<Vec<String>> {
+        let files = self.files.read().unwrap();
+        list_children(files.keys().map(|p| p.as_str()), path)
+    }
+}
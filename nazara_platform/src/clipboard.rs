@@ -0,0 +1,242 @@
+//! Clipboard shared with the other applications of the desktop
+//!
+//! On Linux, the X11 clipboard is used, which Wayland compositors share with their X11
+//! applications through XWayland. Wayland sessions without XWayland fall back to the external
+//! `wl-copy` and `wl-paste` commands of [wl-clipboard](https://github.com/bugaevc/wl-clipboard)
+//! if they are installed. When none is available, and with the headless backend, the clipboard
+//! only lives in memory.
+
+#[cfg(target_os = "linux")]
+mod wl_clipboard;
+#[cfg(target_os = "linux")]
+mod x11;
+
+use crate::window;
+use nazara_core::{enums::PixelFormatType, errors::NazaraResult, image::Image};
+
+/// Data put in the clipboard by the application
+pub(crate) enum Content {
+    Text(String),
+    /// RGBA8 pixels
+    Image {
+        pixels: Vec<u8>,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl Content {
+    fn from_image(image: &Image) -> NazaraResult<Content> {
+        let (pixels, width, height) = window::image_to_rgba(image)?;
+
+        Ok(Content::Image {
+            pixels,
+            width,
+            height,
+        })
+    }
+
+    fn get_text(&self) -> Option<String> {
+        match self {
+            Content::Text(text) => Some(text.clone()),
+            Content::Image { .. } => None,
+        }
+    }
+
+    fn get_image(&self) -> Option<Image> {
+        match self {
+            Content::Text(_) => None,
+            Content::Image {
+                pixels,
+                width,
+                height,
+            } => {
+                let mut image =
+                    Image::new_2d(PixelFormatType::RGBA8, *width as usize, *height as usize);
+                image.update_mipmap_content(0, pixels.clone());
+                Some(image)
+            }
+        }
+    }
+}
+
+enum ClipboardHandle {
+    Memory(Option<Content>),
+    // Xlib holds a table of its functions
+    #[cfg(target_os = "linux")]
+    X11(Box<x11::X11Clipboard>),
+    #[cfg(target_os = "linux")]
+    WlClipboard(wl_clipboard::WlClipboardCommands),
+}
+
+/// Copies and pastes text and images
+///
+/// The clipboard of a [`WindowApplication`](crate::window_application::WindowApplication) is
+/// returned by its `get_clipboard` method.
+///
+/// ```
+/// use nazara_core::enums::PixelFormatType;
+/// use nazara_core::image::Image;
+/// use nazara_platform::clipboard::Clipboard;
+///
+/// let mut clipboard = Clipboard::new_in_memory();
+/// assert!(!clipboard.is_shared());
+/// assert_eq!(clipboard.get_text().unwrap(), None);
+///
+/// clipboard.set_text("Hello Nazara").unwrap();
+/// assert_eq!(clipboard.get_text().unwrap(), Some(String::from("Hello Nazara")));
+///
+/// // The clipboard holds a single item
+/// let mut image = Image::new_2d(PixelFormatType::RGB8, 1, 1);
+/// image.update_mipmap_content(0, vec![255, 128, 0]);
+/// clipboard.set_image(&image).unwrap();
+/// assert_eq!(clipboard.get_text().unwrap(), None);
+///
+/// let pasted = clipboard.get_image().unwrap().unwrap();
+/// assert_eq!(pasted.get_pixel_format(), PixelFormatType::RGBA8);
+/// assert_eq!(pasted.get_mipmap_content(0), &[255, 128, 0, 255]);
+/// ```
+pub struct Clipboard {
+    handle: ClipboardHandle,
+}
+
+impl Clipboard {
+    /// Open the clipboard of the desktop, or create one in memory if there is none
+    pub fn new() -> Clipboard {
+        #[cfg(target_os = "linux")]
+        {
+            if std::env::var_os("DISPLAY").is_some() {
+                match x11::X11Clipboard::open() {
+                    Ok(clipboard) => {
+                        return Clipboard {
+                            handle: ClipboardHandle::X11(Box::new(clipboard)),
+                        }
+                    }
+                    Err(reason) => nazara_core::nz_warning!(
+                        "platform",
+                        "failed to open the X11 clipboard: {}",
+                        reason
+                    ),
+                }
+            }
+
+            if std::env::var_os("WAYLAND_DISPLAY").is_some() && wl_clipboard::is_installed() {
+                return Clipboard {
+                    handle: ClipboardHandle::WlClipboard(wl_clipboard::WlClipboardCommands),
+                };
+            }
+        }
+
+        nazara_core::nz_warning!(
+            "platform",
+            "no clipboard available, the clipboard isn't shared with other applications"
+        );
+        Clipboard::new_in_memory()
+    }
+
+    /// Create a clipboard which isn't shared with other applications
+    pub fn new_in_memory() -> Clipboard {
+        Clipboard {
+            handle: ClipboardHandle::Memory(None),
+        }
+    }
+
+    /// Return true if the clipboard is shared with the other applications
+    pub fn is_shared(&self) -> bool {
+        match self.handle {
+            ClipboardHandle::Memory(_) => false,
+            #[cfg(target_os = "linux")]
+            _ => true,
+        }
+    }
+
+    /// Return the text in the clipboard, `None` if it is empty or holds something else
+    pub fn get_text(&mut self) -> NazaraResult<Option<String>> {
+        match &mut self.handle {
+            ClipboardHandle::Memory(content) => Ok(content.as_ref().and_then(Content::get_text)),
+            #[cfg(target_os = "linux")]
+            ClipboardHandle::X11(clipboard) => clipboard.get_text(),
+            #[cfg(target_os = "linux")]
+            ClipboardHandle::WlClipboard(clipboard) => clipboard.get_text(),
+        }
+    }
+
+    /// Put text in the clipboard, replacing its content
+    ///
+    /// On X11, other applications can paste the text as long as the application runs, unless
+    /// a clipboard manager saves it.
+    ///
+    /// # Arguments
+    /// * `text` - Text to copy
+    pub fn set_text<T: Into<String>>(&mut self, text: T) -> NazaraResult<()> {
+        let content = Content::Text(text.into());
+        self.set_content(content)
+    }
+
+    /// Return the image in the clipboard, `None` if it is empty or holds something else
+    ///
+    /// Images copied by other applications are exchanged as PNG.
+    pub fn get_image(&mut self) -> NazaraResult<Option<Image>> {
+        match &mut self.handle {
+            ClipboardHandle::Memory(content) => Ok(content.as_ref().and_then(Content::get_image)),
+            #[cfg(target_os = "linux")]
+            ClipboardHandle::X11(clipboard) => clipboard.get_image(),
+            #[cfg(target_os = "linux")]
+            ClipboardHandle::WlClipboard(clipboard) => clipboard.get_image(),
+        }
+    }
+
+    /// Put an image in the clipboard, replacing its content
+    ///
+    /// # Arguments
+    /// * `image` - 2D image in `RGBA8`, `RGB8` or `L8` format
+    pub fn set_image(&mut self, image: &Image) -> NazaraResult<()> {
+        let content = Content::from_image(image)?;
+        self.set_content(content)
+    }
+
+    /// Answer the requests of other applications pasting the content of the clipboard
+    ///
+    /// Called by the [`WindowApplication`](crate::window_application::WindowApplication) at
+    /// each frame.
+    pub fn update(&mut self) {
+        #[cfg(target_os = "linux")]
+        {
+            if let ClipboardHandle::X11(clipboard) = &mut self.handle {
+                clipboard.update();
+            }
+        }
+    }
+
+    fn set_content(&mut self, content: Content) -> NazaraResult<()> {
+        match &mut self.handle {
+            ClipboardHandle::Memory(current) => {
+                *current = Some(content);
+                Ok(())
+            }
+            #[cfg(target_os = "linux")]
+            ClipboardHandle::X11(clipboard) => clipboard.set(content),
+            #[cfg(target_os = "linux")]
+            ClipboardHandle::WlClipboard(clipboard) => clipboard.set(content),
+        }
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encode the content of the clipboard in the format exchanged with other applications
+#[cfg(target_os = "linux")]
+fn encode_png(pixels: &[u8], width: u32, height: u32) -> NazaraResult<Vec<u8>> {
+    use nazara_core::errors::PlatformError;
+
+    let mut png = Vec::new();
+    image::png::PNGEncoder::new(&mut png)
+        .encode(pixels, width, height, image::ColorType::RGBA(8))
+        .map_err(|err| PlatformError::Clipboard(format!("failed to encode image: {}", err)))?;
+
+    Ok(png)
+}
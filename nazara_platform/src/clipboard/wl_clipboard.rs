@@ -0,0 +1,97 @@
+use super::Content;
+use nazara_core::{
+    errors::{NazaraError, NazaraResult, PlatformError},
+    image::{Image, ImageLoader},
+};
+use std::{
+    env,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+const PNG_TYPE: &str = "image/png";
+
+/// Return true if the wl-clipboard commands are installed, without running them
+pub(crate) fn is_installed() -> bool {
+    let paths = match env::var_os("PATH") {
+        Some(paths) => paths,
+        None => return false,
+    };
+
+    ["wl-copy", "wl-paste"]
+        .iter()
+        .all(|command| env::split_paths(&paths).any(|path| path.join(command).is_file()))
+}
+
+/// Clipboard of the Wayland compositor, reached by running the external `wl-copy` and
+/// `wl-paste` commands
+///
+/// This is only a fallback for Wayland sessions without XWayland, the Wayland data device
+/// protocol isn't spoken directly.
+pub(crate) struct WlClipboardCommands;
+
+impl WlClipboardCommands {
+    pub(crate) fn get_text(&mut self) -> NazaraResult<Option<String>> {
+        Ok(paste(&["--no-newline", "--type", "text"])?
+            .map(|text| String::from_utf8_lossy(&text).into_owned()))
+    }
+
+    pub(crate) fn get_image(&mut self) -> NazaraResult<Option<Image>> {
+        paste(&["--type", PNG_TYPE])?
+            .map(|png| ImageLoader::load_from_mem(&png))
+            .transpose()
+    }
+
+    pub(crate) fn set(&mut self, content: Content) -> NazaraResult<()> {
+        match content {
+            Content::Text(text) => copy(&[], text.as_bytes()),
+            Content::Image {
+                pixels,
+                width,
+                height,
+            } => copy(
+                &["--type", PNG_TYPE],
+                &super::encode_png(&pixels, width, height)?,
+            ),
+        }
+    }
+}
+
+/// Run wl-paste, return `None` if the clipboard doesn't hold the requested type
+fn paste(args: &[&str]) -> NazaraResult<Option<Vec<u8>>> {
+    let output = Command::new("wl-paste")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| PlatformError::Clipboard(format!("failed to run wl-paste: {}", err)))?;
+
+    Ok(if output.status.success() {
+        Some(output.stdout)
+    } else {
+        None
+    })
+}
+
+/// Run wl-copy, which keeps serving the data in the background once it read it
+fn copy(args: &[&str], data: &[u8]) -> NazaraResult<()> {
+    let error = |err: std::io::Error| -> NazaraError {
+        PlatformError::Clipboard(format!("failed to run wl-copy: {}", err)).into()
+    };
+
+    let mut child = Command::new("wl-copy")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(data).map_err(error)?;
+    }
+
+    let status = child.wait().map_err(error)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PlatformError::Clipboard(format!("wl-copy failed ({})", status)).into())
+    }
+}
@@ -0,0 +1,585 @@
+use super::Content;
+use nazara_core::{
+    errors::{NazaraResult, PlatformError},
+    image::{Image, ImageLoader},
+};
+use std::{
+    ffi::CString,
+    mem,
+    os::raw::{c_int, c_long, c_uchar},
+    ptr, slice,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+use x11_dl::xlib::{self, Atom, Display, XErrorEvent, Xlib};
+
+/// Delay after which the owner of the clipboard is considered not answering
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Delay after which an application pasting large data in chunks is considered gone
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest chunk of data sent at once, larger data being sent in several chunks
+const MAX_CHUNK_SIZE: usize = 256 * 1024;
+
+type ErrorHandler = unsafe extern "C" fn(*mut Display, *mut XErrorEvent) -> c_int;
+
+/// State of the error handler installed while sending data to other applications
+struct ErrorTrap {
+    display: usize,
+    error_code: c_uchar,
+    previous: Option<ErrorHandler>,
+}
+
+static ERROR_TRAP: Mutex<Option<ErrorTrap>> = Mutex::new(None);
+
+/// Record the errors of the trapped display, instead of letting Xlib exit the process
+unsafe extern "C" fn trap_error(display: *mut Display, event: *mut XErrorEvent) -> c_int {
+    let previous = match ERROR_TRAP.lock().unwrap().as_mut() {
+        Some(trap) if trap.display == display as usize => {
+            // Safety: Xlib gives a valid event
+            trap.error_code = unsafe { (*event).error_code };
+            return 0;
+        }
+        Some(trap) => trap.previous,
+        None => None,
+    };
+
+    // Errors of other connections, of winit for example, are left to their handler
+    match previous {
+        // Safety: the previous handler was installed for these errors
+        Some(handler) => unsafe { handler(display, event) },
+        None => 0,
+    }
+}
+
+struct Atoms {
+    clipboard: Atom,
+    targets: Atom,
+    utf8_string: Atom,
+    text: Atom,
+    png: Atom,
+    incr: Atom,
+    // Property of our window receiving the pasted data
+    property: Atom,
+}
+
+/// Data offered to the applications pasting while we own the clipboard
+enum Offer {
+    Text(String),
+    Png(Vec<u8>),
+}
+
+/// Data too large for a single request, sent in chunks to an application pasting it
+struct Transfer {
+    requestor: xlib::Window,
+    property: Atom,
+    target: Atom,
+    data: Vec<u8>,
+    offset: usize,
+    last_activity: Instant,
+}
+
+/// Clipboard of the X server, owned through a hidden window
+///
+/// X11 applications keep the data they copied until another one takes the clipboard: the
+/// requests of the applications pasting are answered by [`update`](X11Clipboard::update).
+pub(crate) struct X11Clipboard {
+    xlib: Xlib,
+    display: *mut Display,
+    window: xlib::Window,
+    atoms: Atoms,
+    offer: Option<Offer>,
+    transfers: Vec<Transfer>,
+    chunk_size: usize,
+}
+
+impl X11Clipboard {
+    pub(crate) fn open() -> Result<X11Clipboard, String> {
+        let xlib = Xlib::open().map_err(|err| err.to_string())?;
+
+        // Safety: the display is checked before it's used, and the window is created on it
+        unsafe {
+            let display = (xlib.XOpenDisplay)(ptr::null());
+            if display.is_null() {
+                return Err(String::from("can't connect to the X server"));
+            }
+
+            let root = (xlib.XDefaultRootWindow)(display);
+            let window = (xlib.XCreateSimpleWindow)(display, root, 0, 0, 1, 1, 0, 0, 0);
+            // Large data is received in chunks, announced by property changes
+            (xlib.XSelectInput)(display, window, xlib::PropertyChangeMask);
+
+            let intern = |name: &str| {
+                let name = CString::new(name).expect("atom names have no null byte");
+                (xlib.XInternAtom)(display, name.as_ptr(), xlib::False)
+            };
+            let atoms = Atoms {
+                clipboard: intern("CLIPBOARD"),
+                targets: intern("TARGETS"),
+                utf8_string: intern("UTF8_STRING"),
+                text: intern("TEXT"),
+                png: intern("image/png"),
+                incr: intern("INCR"),
+                property: intern("NAZARA_CLIPBOARD"),
+            };
+
+            // Requests are limited in size, given in 4 bytes units, part of it being taken by
+            // the header of the request
+            let max_request_size = match (xlib.XExtendedMaxRequestSize)(display) {
+                0 => (xlib.XMaxRequestSize)(display),
+                size => size,
+            };
+            let chunk_size = (max_request_size as usize * 4)
+                .saturating_sub(1024)
+                .clamp(1024, MAX_CHUNK_SIZE);
+
+            Ok(X11Clipboard {
+                xlib,
+                display,
+                window,
+                atoms,
+                offer: None,
+                transfers: Vec::new(),
+                chunk_size,
+            })
+        }
+    }
+
+    pub(crate) fn get_text(&mut self) -> NazaraResult<Option<String>> {
+        if let Some(offer) = self.get_own_offer() {
+            return Ok(match offer {
+                Offer::Text(text) => Some(text.clone()),
+                Offer::Png(_) => None,
+            });
+        }
+
+        // Latin-1 strings are only used by old applications
+        let text = match self.convert(self.atoms.utf8_string)? {
+            Some(text) => Some(String::from_utf8_lossy(&text).into_owned()),
+            None => self
+                .convert(xlib::XA_STRING)?
+                .map(|text| text.iter().map(|&byte| char::from(byte)).collect()),
+        };
+
+        Ok(text)
+    }
+
+    pub(crate) fn get_image(&mut self) -> NazaraResult<Option<Image>> {
+        let png = match self.get_own_offer() {
+            Some(Offer::Png(png)) => Some(png.clone()),
+            Some(Offer::Text(_)) => None,
+            None => self.convert(self.atoms.png)?,
+        };
+
+        png.map(|png| ImageLoader::load_from_mem(&png)).transpose()
+    }
+
+    pub(crate) fn set(&mut self, content: Content) -> NazaraResult<()> {
+        self.offer = Some(match content {
+            Content::Text(text) => Offer::Text(text),
+            Content::Image {
+                pixels,
+                width,
+                height,
+            } => Offer::Png(super::encode_png(&pixels, width, height)?),
+        });
+
+        // Safety: the display and the window are valid as long as self lives
+        let owner = unsafe {
+            (self.xlib.XSetSelectionOwner)(
+                self.display,
+                self.atoms.clipboard,
+                self.window,
+                xlib::CurrentTime,
+            );
+            (self.xlib.XGetSelectionOwner)(self.display, self.atoms.clipboard)
+        };
+        if owner != self.window {
+            self.offer = None;
+            return Err(PlatformError::Clipboard(String::from(
+                "the X server refused the ownership of the clipboard",
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Answer the pending requests of the applications pasting
+    pub(crate) fn update(&mut self) {
+        while let Some(event) = self.next_event() {
+            self.handle_event(&event);
+        }
+
+        let (stale, transfers) = mem::take(&mut self.transfers)
+            .into_iter()
+            .partition(|transfer| transfer.last_activity.elapsed() >= TRANSFER_TIMEOUT);
+        self.transfers = transfers;
+        for transfer in stale {
+            self.end_transfer(&transfer);
+        }
+    }
+
+    fn get_own_offer(&self) -> Option<&Offer> {
+        // Safety: the display is valid as long as self lives
+        let owner = unsafe { (self.xlib.XGetSelectionOwner)(self.display, self.atoms.clipboard) };
+        self.offer.as_ref().filter(|_| owner == self.window)
+    }
+
+    fn next_event(&self) -> Option<xlib::XEvent> {
+        // Safety: the event is filled by XNextEvent before being read
+        unsafe {
+            if (self.xlib.XPending)(self.display) == 0 {
+                return None;
+            }
+
+            let mut event: xlib::XEvent = mem::zeroed();
+            (self.xlib.XNextEvent)(self.display, &mut event);
+            Some(event)
+        }
+    }
+
+    /// Wait for an event matching a predicate, handling the other ones
+    fn wait_event<F>(&mut self, predicate: F) -> NazaraResult<xlib::XEvent>
+    where
+        F: Fn(&xlib::XEvent) -> bool,
+    {
+        let start = Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match self.next_event() {
+                Some(event) if predicate(&event) => return Ok(event),
+                Some(event) => self.handle_event(&event),
+                None => thread::sleep(Duration::from_millis(1)),
+            }
+        }
+
+        Err(PlatformError::Clipboard(String::from(
+            "the application owning the clipboard didn't answer",
+        ))
+        .into())
+    }
+
+    /// Ask the owner of the clipboard for its content, return `None` if it can't provide it in
+    /// the requested format
+    fn convert(&mut self, target: Atom) -> NazaraResult<Option<Vec<u8>>> {
+        let window = self.window;
+
+        // Safety: the display and the window are valid as long as self lives
+        unsafe {
+            if (self.xlib.XGetSelectionOwner)(self.display, self.atoms.clipboard) == 0 {
+                return Ok(None);
+            }
+            (self.xlib.XConvertSelection)(
+                self.display,
+                self.atoms.clipboard,
+                target,
+                self.atoms.property,
+                window,
+                xlib::CurrentTime,
+            );
+        }
+
+        let event = self.wait_event(|event| {
+            // Safety: the union is read as the variant given by its type
+            event.get_type() == xlib::SelectionNotify
+                && unsafe { event.selection.requestor } == window
+        })?;
+        // Safety: the event is a SelectionNotify
+        if unsafe { event.selection.property } == 0 {
+            return Ok(None);
+        }
+
+        let (property_type, data) = self.read_property();
+        if property_type != self.atoms.incr {
+            return Ok(Some(data));
+        }
+
+        // Large data is sent in chunks, each one replacing the previous one once it's deleted,
+        // until an empty one
+        let property = self.atoms.property;
+        let mut data = Vec::new();
+        loop {
+            self.wait_event(|event| {
+                // Safety: the union is read as the variant given by its type
+                event.get_type() == xlib::PropertyNotify
+                    && unsafe { event.property.window == window && event.property.atom == property }
+                    && unsafe { event.property.state } == xlib::PropertyNewValue
+            })?;
+
+            let (_, chunk) = self.read_property();
+            if chunk.is_empty() {
+                return Ok(Some(data));
+            }
+            data.extend_from_slice(&chunk);
+        }
+    }
+
+    /// Read and delete the property receiving the pasted data, return its type and content
+    fn read_property(&self) -> (Atom, Vec<u8>) {
+        let mut property_type = 0;
+        let mut format = 0;
+        let mut item_count = 0;
+        let mut remaining = 0;
+        let mut data: *mut c_uchar = ptr::null_mut();
+
+        // Safety: Xlib allocates the data, which is copied then freed
+        unsafe {
+            (self.xlib.XGetWindowProperty)(
+                self.display,
+                self.window,
+                self.atoms.property,
+                0,
+                c_long::MAX / 4,
+                xlib::True,
+                xlib::AnyPropertyType as Atom,
+                &mut property_type,
+                &mut format,
+                &mut item_count,
+                &mut remaining,
+                &mut data,
+            );
+            if data.is_null() {
+                return (property_type, Vec::new());
+            }
+
+            // Items of 32 bits are returned as longs
+            let item_size = match format {
+                16 => mem::size_of::<i16>(),
+                32 => mem::size_of::<c_long>(),
+                _ => 1,
+            };
+            let content = slice::from_raw_parts(data, item_count as usize * item_size).to_vec();
+            (self.xlib.XFree)(data as *mut _);
+
+            (property_type, content)
+        }
+    }
+
+    fn handle_event(&mut self, event: &xlib::XEvent) {
+        match event.get_type() {
+            // Another application took the clipboard
+            xlib::SelectionClear => self.offer = None,
+            xlib::SelectionRequest => {
+                // Safety: the event is a SelectionRequest
+                let request = unsafe { event.selection_request };
+                self.answer(&request);
+            }
+            xlib::PropertyNotify => {
+                // Safety: the event is a PropertyNotify
+                let event = unsafe { event.property };
+                if event.state == xlib::PropertyDelete {
+                    self.continue_transfer(&event);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Run Xlib calls on windows of other applications, return false if they failed
+    ///
+    /// These windows can be destroyed at any time, the errors are trapped instead of making
+    /// Xlib exit the process.
+    fn trap_errors<F: FnOnce()>(&self, calls: F) -> bool {
+        // Safety: the handler only reads the error events given by Xlib
+        unsafe {
+            let previous = (self.xlib.XSetErrorHandler)(Some(trap_error));
+            *ERROR_TRAP.lock().unwrap() = Some(ErrorTrap {
+                display: self.display as usize,
+                error_code: 0,
+                previous,
+            });
+
+            calls();
+            // Errors are received once the server processed the requests
+            (self.xlib.XSync)(self.display, xlib::False);
+
+            let trap = ERROR_TRAP.lock().unwrap().take();
+            (self.xlib.XSetErrorHandler)(trap.as_ref().and_then(|trap| trap.previous));
+            trap.is_none_or(|trap| trap.error_code == 0)
+        }
+    }
+
+    /// Send the next chunk of a transfer once the previous one was read by the requestor
+    fn continue_transfer(&mut self, event: &xlib::XPropertyEvent) {
+        let index = match self
+            .transfers
+            .iter()
+            .position(|t| t.requestor == event.window && t.property == event.atom)
+        {
+            Some(index) => index,
+            None => return,
+        };
+
+        let transfer = &self.transfers[index];
+        let end = (transfer.offset + self.chunk_size).min(transfer.data.len());
+        let chunk = &transfer.data[transfer.offset..end];
+        // Safety: the data outlives the request, which Xlib copies
+        let sent = self.trap_errors(|| unsafe {
+            (self.xlib.XChangeProperty)(
+                self.display,
+                transfer.requestor,
+                transfer.property,
+                transfer.target,
+                8,
+                xlib::PropModeReplace,
+                chunk.as_ptr(),
+                chunk.len() as c_int,
+            );
+        });
+
+        // The transfer ends with an empty chunk
+        if !sent || chunk.is_empty() {
+            let transfer = self.transfers.remove(index);
+            self.end_transfer(&transfer);
+        } else {
+            let transfer = &mut self.transfers[index];
+            transfer.offset = end;
+            transfer.last_activity = Instant::now();
+        }
+    }
+
+    fn end_transfer(&self, transfer: &Transfer) {
+        // Safety: errors are trapped if the requestor window was destroyed
+        self.trap_errors(|| unsafe {
+            (self.xlib.XSelectInput)(self.display, transfer.requestor, xlib::NoEventMask);
+        });
+    }
+
+    /// Send the content of the clipboard to an application pasting it
+    fn answer(&mut self, request: &xlib::XSelectionRequestEvent) {
+        // Obsolete clients give no property, expecting the target to be used
+        let property = if request.property == 0 {
+            request.target
+        } else {
+            request.property
+        };
+
+        let mut targets = vec![self.atoms.targets];
+        let data = match &self.offer {
+            Some(Offer::Text(text)) => {
+                targets.extend_from_slice(&[
+                    self.atoms.utf8_string,
+                    self.atoms.text,
+                    xlib::XA_STRING,
+                ]);
+                if request.target == xlib::XA_STRING {
+                    Some(
+                        text.chars()
+                            .map(|c| if (c as u32) < 256 { c as u8 } else { b'?' })
+                            .collect(),
+                    )
+                } else if request.target == self.atoms.utf8_string
+                    || request.target == self.atoms.text
+                {
+                    Some(text.as_bytes().to_vec())
+                } else {
+                    None
+                }
+            }
+            Some(Offer::Png(png)) => {
+                targets.push(self.atoms.png);
+                if request.target == self.atoms.png {
+                    Some(png.clone())
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        // Large data is announced with its size, then sent in chunks as the requestor reads them
+        let incremental = data
+            .as_ref()
+            .is_some_and(|data| data.len() > self.chunk_size);
+        let accepted =
+            (self.offer.is_some() && request.target == self.atoms.targets) || data.is_some();
+
+        let mut notify = xlib::XEvent {
+            selection: xlib::XSelectionEvent {
+                type_: xlib::SelectionNotify,
+                serial: 0,
+                send_event: xlib::True,
+                display: self.display,
+                requestor: request.requestor,
+                selection: request.selection,
+                target: request.target,
+                // A missing property tells the request was refused
+                property: if accepted { property } else { 0 },
+                time: request.time,
+            },
+        };
+
+        // Safety: the data outlives the requests, which Xlib copies, and errors are trapped if
+        // the requestor window was destroyed
+        let sent = self.trap_errors(|| unsafe {
+            if request.target == self.atoms.targets && self.offer.is_some() {
+                (self.xlib.XChangeProperty)(
+                    self.display,
+                    request.requestor,
+                    property,
+                    xlib::XA_ATOM,
+                    32,
+                    xlib::PropModeReplace,
+                    targets.as_ptr() as *const c_uchar,
+                    targets.len() as c_int,
+                );
+            } else if let Some(data) = &data {
+                if incremental {
+                    // Chunks are sent when the requestor deletes the property
+                    (self.xlib.XSelectInput)(
+                        self.display,
+                        request.requestor,
+                        xlib::PropertyChangeMask,
+                    );
+                    let size = data.len() as c_long;
+                    (self.xlib.XChangeProperty)(
+                        self.display,
+                        request.requestor,
+                        property,
+                        self.atoms.incr,
+                        32,
+                        xlib::PropModeReplace,
+                        &size as *const c_long as *const c_uchar,
+                        1,
+                    );
+                } else {
+                    (self.xlib.XChangeProperty)(
+                        self.display,
+                        request.requestor,
+                        property,
+                        request.target,
+                        8,
+                        xlib::PropModeReplace,
+                        data.as_ptr(),
+                        data.len() as c_int,
+                    );
+                }
+            }
+
+            (self.xlib.XSendEvent)(self.display, request.requestor, xlib::False, 0, &mut notify);
+        });
+
+        if let (true, true, Some(data)) = (sent, incremental, data) {
+            self.transfers.push(Transfer {
+                requestor: request.requestor,
+                property,
+                target: request.target,
+                data,
+                offset: 0,
+                last_activity: Instant::now(),
+            });
+        }
+    }
+}
+
+impl Drop for X11Clipboard {
+    fn drop(&mut self) {
+        // Safety: the window and the display are destroyed once, and never used afterwards
+        unsafe {
+            (self.xlib.XDestroyWindow)(self.display, self.window);
+            (self.xlib.XCloseDisplay)(self.display);
+        }
+    }
+}
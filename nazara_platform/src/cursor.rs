@@ -0,0 +1,112 @@
+//! Images of the mouse cursor, set with [`Window::set_cursor`](crate::window::Window::set_cursor)
+//!
+//! Custom cursors are shown by X11 windows and stored by headless ones, the other windowing
+//! systems don't support them.
+
+#[cfg(target_os = "linux")]
+mod x11;
+
+use crate::{enums::SystemCursor, window};
+use nazara_core::{
+    errors::{ImageError, NazaraError, NazaraResult},
+    image::Image,
+};
+use winit::window::Window as W_Window;
+
+#[derive(Debug, PartialEq, Clone)]
+/// Represents the image of the cursor over a window
+pub enum Cursor {
+    System(SystemCursor),
+    Custom(CustomCursor),
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor::System(SystemCursor::Default)
+    }
+}
+
+impl From<SystemCursor> for Cursor {
+    fn from(cursor: SystemCursor) -> Self {
+        Cursor::System(cursor)
+    }
+}
+
+impl From<CustomCursor> for Cursor {
+    fn from(cursor: CustomCursor) -> Self {
+        Cursor::Custom(cursor)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+/// Cursor image made by the application
+pub struct CustomCursor {
+    pixels: Vec<u8>,
+    size: (u32, u32),
+    hotspot: (u32, u32),
+}
+
+impl CustomCursor {
+    /// Create a cursor from an image
+    ///
+    /// ```
+    /// use nazara_core::enums::PixelFormatType;
+    /// use nazara_core::image::Image;
+    /// use nazara_platform::cursor::CustomCursor;
+    ///
+    /// let image = Image::new_2d(PixelFormatType::RGB8, 16, 16);
+    /// let cursor = CustomCursor::from_image(&image, 8, 8).unwrap();
+    /// assert_eq!(cursor.get_size(), (16, 16));
+    /// assert_eq!(cursor.get_pixels().len(), 16 * 16 * 4);
+    ///
+    /// // The hotspot must be inside of the image
+    /// assert!(CustomCursor::from_image(&image, 16, 0).is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `image` - 2D image in `RGBA8`, `RGB8` or `L8` format
+    /// * `hotspot_x` - Horizontal position of the pixel pointing at the cursor position
+    /// * `hotspot_y` - Vertical position of the pixel pointing at the cursor position
+    pub fn from_image(image: &Image, hotspot_x: u32, hotspot_y: u32) -> NazaraResult<CustomCursor> {
+        let (pixels, width, height) = window::image_to_rgba(image)?;
+        if hotspot_x >= width || hotspot_y >= height {
+            return Err(NazaraError::from(ImageError::DimensionError));
+        }
+
+        Ok(CustomCursor {
+            pixels,
+            size: (width, height),
+            hotspot: (hotspot_x, hotspot_y),
+        })
+    }
+
+    /// Return the RGBA pixels of the cursor, row by row
+    pub fn get_pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn get_hotspot(&self) -> (u32, u32) {
+        self.hotspot
+    }
+}
+
+/// Show a custom cursor over a platform window
+#[cfg(target_os = "linux")]
+pub(crate) fn define_native_cursor(window: &W_Window, cursor: &CustomCursor) -> Result<(), String> {
+    x11::define_cursor(window, cursor)
+}
+
+/// Show a custom cursor over a platform window
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn define_native_cursor(
+    _window: &W_Window,
+    _cursor: &CustomCursor,
+) -> Result<(), String> {
+    Err(String::from(
+        "custom cursors aren't supported by the windowing system",
+    ))
+}
@@ -0,0 +1,46 @@
+use super::CustomCursor;
+use winit::{platform::unix::WindowExtUnix, window::Window as W_Window};
+
+/// Show a custom cursor over an X11 window, made with Xcursor
+pub(crate) fn define_cursor(window: &W_Window, cursor: &CustomCursor) -> Result<(), String> {
+    let (connection, xlib_window) = match (window.xlib_xconnection(), window.xlib_window()) {
+        (Some(connection), Some(xlib_window)) => (connection, xlib_window),
+        _ => return Err(String::from("only X11 windows support custom cursors")),
+    };
+    let (width, height) = cursor.get_size();
+    let (hotspot_x, hotspot_y) = cursor.get_hotspot();
+
+    // Safety: the image is filled within its size then destroyed once loaded, the server keeps
+    // the cursor as long as the window uses it
+    unsafe {
+        let image = (connection.xcursor.XcursorImageCreate)(width as i32, height as i32);
+        if image.is_null() {
+            return Err(String::from("failed to allocate the cursor image"));
+        }
+
+        (*image).xhot = hotspot_x;
+        (*image).yhot = hotspot_y;
+        let pixels = std::slice::from_raw_parts_mut((*image).pixels, (width * height) as usize);
+        // Xcursor pixels are ARGB with premultiplied alpha
+        for (pixel, rgba) in pixels.iter_mut().zip(cursor.get_pixels().chunks_exact(4)) {
+            let alpha = u32::from(rgba[3]);
+            let premultiply = |channel: u8| u32::from(channel) * alpha / 255;
+            *pixel = alpha << 24
+                | premultiply(rgba[0]) << 16
+                | premultiply(rgba[1]) << 8
+                | premultiply(rgba[2]);
+        }
+
+        let x_cursor = (connection.xcursor.XcursorImageLoadCursor)(connection.display, image);
+        (connection.xcursor.XcursorImageDestroy)(image);
+        if x_cursor == 0 {
+            return Err(String::from("the X server refused the cursor"));
+        }
+
+        (connection.xlib.XDefineCursor)(connection.display, xlib_window, x_cursor);
+        (connection.xlib.XFreeCursor)(connection.display, x_cursor);
+        (connection.xlib.XFlush)(connection.display);
+    }
+
+    Ok(())
+}
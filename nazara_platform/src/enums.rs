@@ -1 +1,629 @@
+use crate::{gamepad::GamepadId, monitor::Monitor};
+use winit::{
+    event::{ModifiersState, MouseButton as W_MouseButton, MouseScrollDelta, VirtualKeyCode},
+    monitor::VideoMode as W_VideoMode,
+    window::CursorIcon,
+};
 
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents the implementation of the windows of an application
+pub enum Backend {
+    /// Windows of the windowing system, receiving events from the user
+    Native,
+    /// Simulated windows, receiving scripted events, for automated tests
+    Headless,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents a key by its meaning in the current keyboard layout
+pub enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Num0,
+    Num1,
+    Num2,
+    Num3,
+    Num4,
+    Num5,
+    Num6,
+    Num7,
+    Num8,
+    Num9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    Down,
+    Left,
+    Right,
+    Up,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadDecimal,
+    NumpadDivide,
+    NumpadEnter,
+    NumpadEquals,
+    NumpadMultiply,
+    NumpadSubtract,
+    Apostrophe,
+    Backslash,
+    Comma,
+    Equal,
+    Grave,
+    LBracket,
+    Minus,
+    Period,
+    RBracket,
+    Semicolon,
+    Slash,
+    LAlt,
+    LControl,
+    LShift,
+    LSystem,
+    RAlt,
+    RControl,
+    RShift,
+    RSystem,
+    Backspace,
+    CapsLock,
+    Delete,
+    End,
+    Escape,
+    Home,
+    Insert,
+    Menu,
+    NumLock,
+    PageDown,
+    PageUp,
+    Pause,
+    PrintScreen,
+    Return,
+    ScrollLock,
+    Space,
+    Tab,
+    MediaNext,
+    MediaPlayPause,
+    MediaPrevious,
+    MediaStop,
+    VolumeDown,
+    VolumeMute,
+    VolumeUp,
+    /// The key has no Nazara equivalent, use the scancode to identify it
+    Undefined,
+}
+
+impl From<VirtualKeyCode> for Key {
+    fn from(key: VirtualKeyCode) -> Self {
+        use VirtualKeyCode as V;
+
+        match key {
+            V::A => Key::A,
+            V::B => Key::B,
+            V::C => Key::C,
+            V::D => Key::D,
+            V::E => Key::E,
+            V::F => Key::F,
+            V::G => Key::G,
+            V::H => Key::H,
+            V::I => Key::I,
+            V::J => Key::J,
+            V::K => Key::K,
+            V::L => Key::L,
+            V::M => Key::M,
+            V::N => Key::N,
+            V::O => Key::O,
+            V::P => Key::P,
+            V::Q => Key::Q,
+            V::R => Key::R,
+            V::S => Key::S,
+            V::T => Key::T,
+            V::U => Key::U,
+            V::V => Key::V,
+            V::W => Key::W,
+            V::X => Key::X,
+            V::Y => Key::Y,
+            V::Z => Key::Z,
+            V::Key0 => Key::Num0,
+            V::Key1 => Key::Num1,
+            V::Key2 => Key::Num2,
+            V::Key3 => Key::Num3,
+            V::Key4 => Key::Num4,
+            V::Key5 => Key::Num5,
+            V::Key6 => Key::Num6,
+            V::Key7 => Key::Num7,
+            V::Key8 => Key::Num8,
+            V::Key9 => Key::Num9,
+            V::F1 => Key::F1,
+            V::F2 => Key::F2,
+            V::F3 => Key::F3,
+            V::F4 => Key::F4,
+            V::F5 => Key::F5,
+            V::F6 => Key::F6,
+            V::F7 => Key::F7,
+            V::F8 => Key::F8,
+            V::F9 => Key::F9,
+            V::F10 => Key::F10,
+            V::F11 => Key::F11,
+            V::F12 => Key::F12,
+            V::F13 => Key::F13,
+            V::F14 => Key::F14,
+            V::F15 => Key::F15,
+            V::Down => Key::Down,
+            V::Left => Key::Left,
+            V::Right => Key::Right,
+            V::Up => Key::Up,
+            V::Numpad0 => Key::Numpad0,
+            V::Numpad1 => Key::Numpad1,
+            V::Numpad2 => Key::Numpad2,
+            V::Numpad3 => Key::Numpad3,
+            V::Numpad4 => Key::Numpad4,
+            V::Numpad5 => Key::Numpad5,
+            V::Numpad6 => Key::Numpad6,
+            V::Numpad7 => Key::Numpad7,
+            V::Numpad8 => Key::Numpad8,
+            V::Numpad9 => Key::Numpad9,
+            V::Add => Key::NumpadAdd,
+            V::Decimal => Key::NumpadDecimal,
+            V::Divide => Key::NumpadDivide,
+            V::NumpadEnter => Key::NumpadEnter,
+            V::NumpadEquals => Key::NumpadEquals,
+            V::Multiply => Key::NumpadMultiply,
+            V::Subtract => Key::NumpadSubtract,
+            V::Apostrophe => Key::Apostrophe,
+            V::Backslash => Key::Backslash,
+            V::Comma => Key::Comma,
+            V::Equals => Key::Equal,
+            V::Grave => Key::Grave,
+            V::LBracket => Key::LBracket,
+            V::Minus => Key::Minus,
+            V::Period => Key::Period,
+            V::RBracket => Key::RBracket,
+            V::Semicolon => Key::Semicolon,
+            V::Slash => Key::Slash,
+            V::LAlt => Key::LAlt,
+            V::LControl => Key::LControl,
+            V::LShift => Key::LShift,
+            V::LWin => Key::LSystem,
+            V::RAlt => Key::RAlt,
+            V::RControl => Key::RControl,
+            V::RShift => Key::RShift,
+            V::RWin => Key::RSystem,
+            V::Back => Key::Backspace,
+            V::Capital => Key::CapsLock,
+            V::Delete => Key::Delete,
+            V::End => Key::End,
+            V::Escape => Key::Escape,
+            V::Home => Key::Home,
+            V::Insert => Key::Insert,
+            V::Apps => Key::Menu,
+            V::Numlock => Key::NumLock,
+            V::PageDown => Key::PageDown,
+            V::PageUp => Key::PageUp,
+            V::Pause => Key::Pause,
+            V::Snapshot => Key::PrintScreen,
+            V::Return => Key::Return,
+            V::Scroll => Key::ScrollLock,
+            V::Space => Key::Space,
+            V::Tab => Key::Tab,
+            V::NextTrack => Key::MediaNext,
+            V::PlayPause => Key::MediaPlayPause,
+            V::PrevTrack => Key::MediaPrevious,
+            V::MediaStop => Key::MediaStop,
+            V::VolumeDown => Key::VolumeDown,
+            V::Mute => Key::VolumeMute,
+            V::VolumeUp => Key::VolumeUp,
+            _ => Key::Undefined,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents a mouse button
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    /// Additional buttons, identified by the platform
+    Other(u8),
+}
+
+impl From<W_MouseButton> for MouseButton {
+    fn from(button: W_MouseButton) -> Self {
+        match button {
+            W_MouseButton::Left => MouseButton::Left,
+            W_MouseButton::Middle => MouseButton::Middle,
+            W_MouseButton::Right => MouseButton::Right,
+            W_MouseButton::Other(id) => MouseButton::Other(id),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+/// Represents the modifier keys held when an event occured
+pub struct KeyModifiers {
+    pub alt: bool,
+    pub control: bool,
+    pub shift: bool,
+    /// Windows or Command key
+    pub system: bool,
+}
+
+impl From<ModifiersState> for KeyModifiers {
+    fn from(state: ModifiersState) -> Self {
+        KeyModifiers {
+            alt: state.alt(),
+            control: state.ctrl(),
+            shift: state.shift(),
+            system: state.logo(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents how far the mouse wheel moved
+pub enum MouseWheelDelta {
+    /// Lines or rows to scroll, as reported by a notched wheel
+    Lines { x: f32, y: f32 },
+    /// Pixels to scroll, as reported by a touchpad
+    Pixels { x: f64, y: f64 },
+}
+
+impl From<MouseScrollDelta> for MouseWheelDelta {
+    fn from(delta: MouseScrollDelta) -> Self {
+        match delta {
+            MouseScrollDelta::LineDelta(x, y) => MouseWheelDelta::Lines { x, y },
+            MouseScrollDelta::PixelDelta(position) => MouseWheelDelta::Pixels {
+                x: position.x,
+                y: position.y,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents a display mode of a monitor
+pub struct VideoMode {
+    /// Resolution in physical pixels
+    pub width: u32,
+    pub height: u32,
+    /// Bits per pixel
+    pub bit_depth: u16,
+    /// Refresh rate in Hz
+    pub refresh_rate: u16,
+}
+
+impl From<&W_VideoMode> for VideoMode {
+    fn from(mode: &W_VideoMode) -> Self {
+        let size = mode.size();
+
+        VideoMode {
+            width: size.width,
+            height: size.height,
+            bit_depth: mode.bit_depth(),
+            refresh_rate: mode.refresh_rate(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+/// Represents how a window covers a monitor
+pub enum Fullscreen {
+    /// The window covers a monitor without changing its display mode. If none is given, it is
+    /// the primary monitor when the window is created, and the monitor the window is on when
+    /// switching an existing window to fullscreen.
+    Borderless(Option<Monitor>),
+    /// The window takes exclusive control of a monitor, whose display mode is changed to one
+    /// of its [video modes](Monitor::get_video_modes)
+    Exclusive(Monitor, VideoMode),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents a cursor image provided by the platform
+pub enum SystemCursor {
+    Default,
+    Crosshair,
+    Hand,
+    Arrow,
+    Move,
+    Text,
+    Wait,
+    Help,
+    /// The application is busy but can still be used
+    Progress,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    ZoomIn,
+    ZoomOut,
+    ResizeHorizontal,
+    ResizeVertical,
+    /// Resizing from the top-right or bottom-left corner
+    ResizeDiagonalUp,
+    /// Resizing from the top-left or bottom-right corner
+    ResizeDiagonalDown,
+}
+
+impl From<SystemCursor> for CursorIcon {
+    fn from(cursor: SystemCursor) -> Self {
+        match cursor {
+            SystemCursor::Default => CursorIcon::Default,
+            SystemCursor::Crosshair => CursorIcon::Crosshair,
+            SystemCursor::Hand => CursorIcon::Hand,
+            SystemCursor::Arrow => CursorIcon::Arrow,
+            SystemCursor::Move => CursorIcon::Move,
+            SystemCursor::Text => CursorIcon::Text,
+            SystemCursor::Wait => CursorIcon::Wait,
+            SystemCursor::Help => CursorIcon::Help,
+            SystemCursor::Progress => CursorIcon::Progress,
+            SystemCursor::NotAllowed => CursorIcon::NotAllowed,
+            SystemCursor::Grab => CursorIcon::Grab,
+            SystemCursor::Grabbing => CursorIcon::Grabbing,
+            SystemCursor::ZoomIn => CursorIcon::ZoomIn,
+            SystemCursor::ZoomOut => CursorIcon::ZoomOut,
+            SystemCursor::ResizeHorizontal => CursorIcon::EwResize,
+            SystemCursor::ResizeVertical => CursorIcon::NsResize,
+            SystemCursor::ResizeDiagonalUp => CursorIcon::NeswResize,
+            SystemCursor::ResizeDiagonalDown => CursorIcon::NwseResize,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents how the cursor is held by a window
+pub enum CursorGrab {
+    /// The cursor moves freely
+    None,
+    /// The cursor can't leave the window
+    Confined,
+    /// The cursor is hidden and stays in place, only raw motion is reported, as needed by
+    /// first-person cameras
+    Locked,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Represents a key being pressed or released
+pub struct KeyEvent {
+    /// Physical location of the key, independent of the keyboard layout
+    pub scancode: u32,
+    /// Meaning of the key in the keyboard layout
+    pub key: Key,
+    pub modifiers: KeyModifiers,
+    /// True if the key was already pressed, the event being caused by auto-repeat
+    pub repeated: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Represents a character typed, after the keyboard layout and input method were applied
+pub struct TextEvent {
+    pub character: char,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents a mouse button being pressed or released
+pub struct MouseButtonEvent {
+    pub button: MouseButton,
+    /// Position of the cursor relative to the top-left corner of the window, in physical pixels
+    pub x: f64,
+    pub y: f64,
+    pub modifiers: KeyModifiers,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents the cursor moving inside a window
+pub struct MouseMoveEvent {
+    /// New position of the cursor relative to the top-left corner of the window, in physical
+    /// pixels
+    pub x: f64,
+    pub y: f64,
+    /// Movement since the previous position, zero when the cursor enters the window
+    pub delta_x: f64,
+    pub delta_y: f64,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents a movement of the mouse itself, unaffected by the cursor acceleration and the
+/// edges of the screen
+pub struct RawMotionEvent {
+    /// Movement in units of the device, usually close to pixels
+    pub delta_x: f64,
+    pub delta_y: f64,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents the mouse wheel moving
+pub struct MouseWheelEvent {
+    pub delta: MouseWheelDelta,
+    pub modifiers: KeyModifiers,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Represents the new size of a window
+pub struct ResizeEvent {
+    /// Size of the inside of the window, in physical pixels
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Represents the new position of a window
+pub struct MoveEvent {
+    /// Position of the top-left corner of the window on the desktop, in physical pixels
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents a change of the ratio between physical and logical pixels of a window, when it
+/// moves to another monitor or the user changes the display settings
+pub struct ScaleFactorEvent {
+    pub scale_factor: f64,
+    /// Size of the inside of the window suggested by the platform for the new scale factor, in
+    /// physical pixels
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents an event received by a window
+pub enum WindowEvent {
+    KeyPressed(KeyEvent),
+    KeyReleased(KeyEvent),
+    /// Text typed, input methods report their committed text with this event too
+    TextEntered(TextEvent),
+    MouseButtonPressed(MouseButtonEvent),
+    MouseButtonReleased(MouseButtonEvent),
+    MouseMoved(MouseMoveEvent),
+    /// The mouse moved while the window had the focus, reported even when the cursor is locked
+    RawMouseMotion(RawMotionEvent),
+    MouseWheelMoved(MouseWheelEvent),
+    /// The cursor entered the window
+    MouseEntered,
+    /// The cursor left the window
+    MouseLeft,
+    /// The user asked to close the window, with its close button or a shortcut
+    CloseRequested,
+    Resized(ResizeEvent),
+    Moved(MoveEvent),
+    FocusGained,
+    /// The window lost the focus, the keys and buttons held are considered released
+    FocusLost,
+    Minimized,
+    /// The window is no longer minimized
+    Restored,
+    ScaleFactorChanged(ScaleFactorEvent),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents a button of a gamepad, by its position on a Xbox-style layout
+pub enum GamepadButton {
+    /// Bottom face button
+    A,
+    /// Right face button
+    B,
+    /// Left face button
+    X,
+    /// Top face button
+    Y,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    /// Share, capture or microphone button
+    Misc1,
+    Paddle1,
+    Paddle2,
+    Paddle3,
+    Paddle4,
+    Touchpad,
+}
+
+impl GamepadButton {
+    pub const COUNT: usize = 21;
+
+    pub(crate) fn get_index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Represents an axis of a gamepad
+///
+/// Sticks range from -1 to 1, left and up being negative, triggers range from 0 to 1.
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    pub const COUNT: usize = 6;
+
+    pub(crate) fn get_index(self) -> usize {
+        self as usize
+    }
+
+    /// Return true for the triggers, whose value is never negative
+    pub fn is_trigger(self) -> bool {
+        self == GamepadAxis::LeftTrigger || self == GamepadAxis::RightTrigger
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Represents a gamepad button being pressed or released
+pub struct GamepadButtonEvent {
+    pub gamepad: GamepadId,
+    pub button: GamepadButton,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents a gamepad axis moving
+pub struct GamepadAxisEvent {
+    pub gamepad: GamepadId,
+    pub axis: GamepadAxis,
+    /// New value, after the deadzone was applied
+    pub value: f32,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Represents an event received from a gamepad
+pub enum GamepadEvent {
+    Connected(GamepadId),
+    Disconnected(GamepadId),
+    ButtonPressed(GamepadButtonEvent),
+    ButtonReleased(GamepadButtonEvent),
+    AxisMoved(GamepadAxisEvent),
+}
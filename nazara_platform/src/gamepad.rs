@@ -0,0 +1,566 @@
+//! Gamepads and joysticks
+//!
+//! Devices are read by backends ([`EvdevBackend`](evdev::EvdevBackend) on Linux,
+//! [`MockBackend`](mock::MockBackend) for tests) which report raw buttons, axes and hats. A
+//! [`GamepadMapping`](mapping::GamepadMapping) of the device model translates them into the
+//! standard layout of [`GamepadButton`] and [`GamepadAxis`].
+//!
+//! ```
+//! use nazara_platform::enums::{GamepadAxis, GamepadButton, GamepadEvent};
+//! use nazara_platform::gamepad::{mock::MockBackend, DeviceInfo, GamepadManager};
+//! use std::{cell::RefCell, rc::Rc, time::Duration};
+//!
+//! let backend = MockBackend::new();
+//! let mut manager = GamepadManager::new();
+//! manager.add_backend(Box::new(backend.clone()));
+//!
+//! let events = Rc::new(RefCell::new(Vec::new()));
+//! let received = events.clone();
+//! manager.on_event().connect(move |event| received.borrow_mut().push(*event));
+//!
+//! // A Xbox 360 controller, known by the default mappings, its triggers at rest
+//! let device = backend.connect(
+//!     DeviceInfo {
+//!         name: String::from("Xbox 360 Controller"),
+//!         guid: String::from("030000005e0400008e02000014010000"),
+//!         button_count: 11,
+//!         axis_count: 6,
+//!         hat_count: 1,
+//!     },
+//!     &[0.0, 0.0, -1.0, 0.0, 0.0, -1.0],
+//! );
+//! manager.update();
+//! let gamepad = manager.get_gamepads()[0];
+//! assert!(manager.is_mapped(gamepad));
+//!
+//! // Triggers rest at the minimum of their raw axis
+//! assert_eq!(manager.get_axis(gamepad, GamepadAxis::LeftTrigger), 0.0);
+//! assert_eq!(manager.get_axis(gamepad, GamepadAxis::RightTrigger), 0.0);
+//! assert_eq!(*events.borrow(), [GamepadEvent::Connected(gamepad)]);
+//!
+//! device.set_button(0, true);
+//! device.set_axis(0, 0.05);
+//! device.set_axis(1, -1.0);
+//! device.set_hat(0, 1);
+//! manager.update();
+//!
+//! assert!(manager.is_button_pressed(gamepad, GamepadButton::A));
+//! assert!(manager.is_button_pressed(gamepad, GamepadButton::DPadUp));
+//! // Small moves are ignored by the deadzone
+//! assert_eq!(manager.get_axis(gamepad, GamepadAxis::LeftX), 0.0);
+//! assert_eq!(manager.get_axis(gamepad, GamepadAxis::LeftY), -1.0);
+//! assert_eq!(manager.get_axis(gamepad, GamepadAxis::LeftTrigger), 0.0);
+//!
+//! manager.rumble(gamepad, 1.0, 0.5, Duration::from_millis(200)).unwrap();
+//! assert_eq!(device.get_rumbles().len(), 1);
+//!
+//! device.disconnect();
+//! manager.update();
+//! assert!(manager.get_gamepads().is_empty());
+//! assert_eq!(events.borrow().first(), Some(&GamepadEvent::Connected(gamepad)));
+//! assert_eq!(events.borrow().last(), Some(&GamepadEvent::Disconnected(gamepad)));
+//! ```
+
+#[cfg(target_os = "linux")]
+pub mod evdev;
+pub mod mapping;
+pub mod mock;
+
+use crate::enums::{
+    GamepadAxis, GamepadAxisEvent, GamepadButton, GamepadButtonEvent, GamepadEvent,
+};
+use mapping::{AxisRange, GamepadMapping, MappingDatabase, MappingSource, MappingTarget};
+use nazara_core::{
+    errors::{NazaraResult, PlatformError},
+    signal::Signal,
+};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Deadzone applied by default to the sticks
+pub const DEFAULT_STICK_DEADZONE: f32 = 0.1;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Identifies a gamepad as long as it stays connected
+pub struct GamepadId(u64);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// Describes a device, as reported by its backend
+pub struct DeviceInfo {
+    pub name: String,
+    /// SDL GUID of the device, identifying its model
+    pub guid: String,
+    pub button_count: usize,
+    pub axis_count: usize,
+    pub hat_count: usize,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Change of the raw state of a device
+pub enum RawEvent {
+    Button {
+        index: usize,
+        pressed: bool,
+    },
+    /// Axis moved, its value ranging from -1 to 1
+    Axis {
+        index: usize,
+        value: f32,
+    },
+    /// Hat moved, its value being a combination of 1 (up), 2 (right), 4 (down) and 8 (left)
+    Hat {
+        index: usize,
+        value: u8,
+    },
+    /// The device was unplugged, it won't report any other event
+    Disconnected,
+}
+
+/// A device opened by a [`GamepadBackend`]
+pub trait GamepadDevice {
+    fn get_info(&self) -> &DeviceInfo;
+
+    /// Append the events which happened since the last call
+    ///
+    /// # Arguments
+    /// * `events` - Events read, in the order they happened
+    fn poll(&mut self, events: &mut Vec<RawEvent>);
+
+    /// Make the device vibrate, if it has motors
+    ///
+    /// # Arguments
+    /// * `strong` - Strength of the low frequency motor, from 0 to 1
+    /// * `weak` - Strength of the high frequency motor, from 0 to 1
+    /// * `duration` - Duration of the vibration
+    fn rumble(&mut self, _strong: f32, _weak: f32, _duration: Duration) -> NazaraResult<()> {
+        Err(PlatformError::Unsupported(String::from("the device has no rumble motor")).into())
+    }
+}
+
+/// Source of devices
+pub trait GamepadBackend {
+    /// Return the devices connected since the last call
+    fn scan(&mut self) -> Vec<Box<dyn GamepadDevice>>;
+}
+
+/// Raw state of a device
+#[derive(Default)]
+pub(crate) struct RawState {
+    pub(crate) buttons: Vec<bool>,
+    pub(crate) axes: Vec<f32>,
+    pub(crate) hats: Vec<u8>,
+}
+
+impl RawState {
+    fn new(info: &DeviceInfo) -> RawState {
+        RawState {
+            buttons: vec![false; info.button_count],
+            axes: vec![0.0; info.axis_count],
+            hats: vec![0; info.hat_count],
+        }
+    }
+
+    fn apply(&mut self, event: &RawEvent) {
+        fn set<T>(values: &mut Vec<T>, index: usize, value: T)
+        where
+            T: Default + Clone,
+        {
+            if index >= values.len() {
+                values.resize(index + 1, T::default());
+            }
+            values[index] = value;
+        }
+
+        match *event {
+            RawEvent::Button { index, pressed } => set(&mut self.buttons, index, pressed),
+            RawEvent::Axis { index, value } => set(&mut self.axes, index, value),
+            RawEvent::Hat { index, value } => set(&mut self.hats, index, value),
+            RawEvent::Disconnected => (),
+        }
+    }
+}
+
+struct Gamepad {
+    device: Box<dyn GamepadDevice>,
+    mapping: Option<GamepadMapping>,
+    raw: RawState,
+    buttons: [bool; GamepadButton::COUNT],
+    axes: [f32; GamepadAxis::COUNT],
+}
+
+/// Tracks the connected gamepads and their state
+///
+/// The [`WindowApplication`](crate::window_application::WindowApplication) updates its manager
+/// at the beginning of each frame.
+pub struct GamepadManager {
+    backends: Vec<Box<dyn GamepadBackend>>,
+    gamepads: BTreeMap<GamepadId, Gamepad>,
+    mappings: MappingDatabase,
+    deadzones: [f32; GamepadAxis::COUNT],
+    next_id: u64,
+    on_event: Signal<GamepadEvent>,
+    raw_events: Vec<RawEvent>,
+}
+
+impl GamepadManager {
+    /// Create a manager without backend, using the default mappings
+    pub fn new() -> GamepadManager {
+        let mut deadzones = [DEFAULT_STICK_DEADZONE; GamepadAxis::COUNT];
+        deadzones[GamepadAxis::LeftTrigger.get_index()] = 0.0;
+        deadzones[GamepadAxis::RightTrigger.get_index()] = 0.0;
+
+        GamepadManager {
+            backends: Vec::new(),
+            gamepads: BTreeMap::new(),
+            mappings: MappingDatabase::new(),
+            deadzones,
+            next_id: 0,
+            on_event: Signal::new(),
+            raw_events: Vec::new(),
+        }
+    }
+
+    /// Add a source of devices, which is scanned at each update
+    pub fn add_backend(&mut self, backend: Box<dyn GamepadBackend>) {
+        self.backends.push(backend);
+    }
+
+    /// Return the mappings used for the devices connected afterwards
+    pub fn get_mappings(&mut self) -> &mut MappingDatabase {
+        &mut self.mappings
+    }
+
+    /// Signal emitted for each connection, disconnection and change of a mapped control
+    pub fn on_event(&self) -> &Signal<GamepadEvent> {
+        &self.on_event
+    }
+
+    /// Connect the new devices, read the events of the connected ones and emit the changes
+    pub fn update(&mut self) {
+        let devices: Vec<_> = self
+            .backends
+            .iter_mut()
+            .flat_map(|backend| backend.scan())
+            .collect();
+        for device in devices {
+            self.next_id += 1;
+            let id = GamepadId(self.next_id);
+            let info = device.get_info();
+            let mapping = self.mappings.get(&info.guid).cloned();
+            if mapping.is_none() {
+                nazara_core::nz_info!(
+                    "platform",
+                    "no mapping for {} ({}), only its raw state is available",
+                    info.name,
+                    info.guid
+                );
+            }
+
+            let raw = RawState::new(info);
+            self.gamepads.insert(
+                id,
+                Gamepad {
+                    device,
+                    mapping,
+                    raw,
+                    buttons: [false; GamepadButton::COUNT],
+                    axes: [0.0; GamepadAxis::COUNT],
+                },
+            );
+            self.on_event.emit(&GamepadEvent::Connected(id));
+        }
+
+        let mut disconnected = Vec::new();
+        for (&id, gamepad) in self.gamepads.iter_mut() {
+            self.raw_events.clear();
+            gamepad.device.poll(&mut self.raw_events);
+            for event in &self.raw_events {
+                gamepad.raw.apply(event);
+            }
+            if self.raw_events.contains(&RawEvent::Disconnected) {
+                disconnected.push(id);
+                continue;
+            }
+
+            if let Some(mapping) = &gamepad.mapping {
+                let (buttons, mut axes) = evaluate(mapping, &gamepad.raw);
+                for (axis, value) in axes.iter_mut().enumerate() {
+                    *value = apply_deadzone(*value, self.deadzones[axis]);
+                }
+
+                emit_changes(&self.on_event, id, gamepad, &buttons, &axes);
+                gamepad.buttons = buttons;
+                gamepad.axes = axes;
+            }
+        }
+
+        for id in disconnected {
+            self.gamepads.remove(&id);
+            self.on_event.emit(&GamepadEvent::Disconnected(id));
+        }
+    }
+
+    /// Return the connected gamepads, in the order they were connected
+    pub fn get_gamepads(&self) -> Vec<GamepadId> {
+        self.gamepads.keys().copied().collect()
+    }
+
+    pub fn is_connected(&self, gamepad: GamepadId) -> bool {
+        self.gamepads.contains_key(&gamepad)
+    }
+
+    /// Return the information reported by the device
+    pub fn get_info(&self, gamepad: GamepadId) -> Option<&DeviceInfo> {
+        self.gamepads
+            .get(&gamepad)
+            .map(|gamepad| gamepad.device.get_info())
+    }
+
+    /// Return true if a mapping was found for the device, its standard controls are never
+    /// pressed otherwise
+    pub fn is_mapped(&self, gamepad: GamepadId) -> bool {
+        self.gamepads
+            .get(&gamepad)
+            .is_some_and(|gamepad| gamepad.mapping.is_some())
+    }
+
+    pub fn is_button_pressed(&self, gamepad: GamepadId, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&gamepad)
+            .is_some_and(|gamepad| gamepad.buttons[button.get_index()])
+    }
+
+    /// Return the value of an axis, after the deadzone was applied
+    pub fn get_axis(&self, gamepad: GamepadId, axis: GamepadAxis) -> f32 {
+        self.gamepads
+            .get(&gamepad)
+            .map_or(0.0, |gamepad| gamepad.axes[axis.get_index()])
+    }
+
+    /// Return the state of a button as numbered by the device
+    pub fn is_raw_button_pressed(&self, gamepad: GamepadId, index: usize) -> bool {
+        self.gamepads
+            .get(&gamepad)
+            .and_then(|gamepad| gamepad.raw.buttons.get(index).copied())
+            .unwrap_or(false)
+    }
+
+    /// Return the value of an axis as numbered by the device, from -1 to 1
+    pub fn get_raw_axis(&self, gamepad: GamepadId, index: usize) -> f32 {
+        self.gamepads
+            .get(&gamepad)
+            .and_then(|gamepad| gamepad.raw.axes.get(index).copied())
+            .unwrap_or(0.0)
+    }
+
+    /// Return the directions of a hat as numbered by the device, see [`RawEvent::Hat`]
+    pub fn get_raw_hat(&self, gamepad: GamepadId, index: usize) -> u8 {
+        self.gamepads
+            .get(&gamepad)
+            .and_then(|gamepad| gamepad.raw.hats.get(index).copied())
+            .unwrap_or(0)
+    }
+
+    pub fn get_deadzone(&self, axis: GamepadAxis) -> f32 {
+        self.deadzones[axis.get_index()]
+    }
+
+    /// Set the range around the rest position in which an axis is considered at rest
+    ///
+    /// Values outside of it are rescaled, so the axis still covers its whole range.
+    ///
+    /// # Arguments
+    /// * `axis` - Axis of every gamepad to configure
+    /// * `deadzone` - Size of the range, from 0 to 1
+    pub fn set_deadzone(&mut self, axis: GamepadAxis, deadzone: f32) {
+        self.deadzones[axis.get_index()] = deadzone.clamp(0.0, 0.99);
+    }
+
+    /// Make a gamepad vibrate
+    ///
+    /// # Arguments
+    /// * `gamepad` - Gamepad to make vibrate
+    /// * `strong` - Strength of the low frequency motor, from 0 to 1
+    /// * `weak` - Strength of the high frequency motor, from 0 to 1
+    /// * `duration` - Duration of the vibration
+    pub fn rumble(
+        &mut self,
+        gamepad: GamepadId,
+        strong: f32,
+        weak: f32,
+        duration: Duration,
+    ) -> NazaraResult<()> {
+        match self.gamepads.get_mut(&gamepad) {
+            Some(gamepad) => {
+                gamepad
+                    .device
+                    .rumble(strong.clamp(0.0, 1.0), weak.clamp(0.0, 1.0), duration)
+            }
+            None => {
+                Err(PlatformError::Unsupported(String::from("the gamepad is disconnected")).into())
+            }
+        }
+    }
+}
+
+impl Default for GamepadManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Return the bounds of a range, the first being the rest position
+fn get_bounds(range: AxisRange, full: (f32, f32)) -> (f32, f32) {
+    match range {
+        AxisRange::Full => full,
+        AxisRange::Positive => (0.0, 1.0),
+        AxisRange::Negative => (0.0, -1.0),
+    }
+}
+
+/// Return the position of the source in its range, from 0 (rest) to 1, `None` outside of it
+fn get_activation(source: &MappingSource, raw: &RawState) -> Option<f32> {
+    match *source {
+        MappingSource::Button(index) => Some(if raw.buttons.get(index) == Some(&true) {
+            1.0
+        } else {
+            0.0
+        }),
+        MappingSource::Hat { index, mask } => {
+            let value = raw.hats.get(index).copied().unwrap_or(0);
+            Some(if value & mask != 0 { 1.0 } else { 0.0 })
+        }
+        MappingSource::Axis {
+            index,
+            range,
+            inverted,
+        } => {
+            let mut value = raw.axes.get(index).copied().unwrap_or(0.0);
+            if inverted {
+                value = -value;
+            }
+
+            let (min, max) = get_bounds(range, (-1.0, 1.0));
+            let activation = (value - min) / (max - min);
+            if (0.0..=1.0).contains(&activation) {
+                Some(activation)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Compute the standard controls from the raw state, as SDL does
+fn evaluate(
+    mapping: &GamepadMapping,
+    raw: &RawState,
+) -> ([bool; GamepadButton::COUNT], [f32; GamepadAxis::COUNT]) {
+    let mut buttons = [false; GamepadButton::COUNT];
+    let mut axes = [0.0f32; GamepadAxis::COUNT];
+
+    for (target, source) in mapping.get_bindings() {
+        let activation = match get_activation(source, raw) {
+            Some(activation) => activation,
+            None => continue,
+        };
+
+        match *target {
+            MappingTarget::Button(button) => {
+                buttons[button.get_index()] |= activation > 0.5;
+            }
+            MappingTarget::Axis(axis, range) => {
+                let full = if axis.is_trigger() {
+                    (0.0, 1.0)
+                } else {
+                    (-1.0, 1.0)
+                };
+                let (min, max) = get_bounds(range, full);
+                let value = min + activation * (max - min);
+
+                // Several inputs may drive the same axis, such as the two halves of a d-pad
+                let current = &mut axes[axis.get_index()];
+                if value.abs() > current.abs() {
+                    *current = value;
+                }
+            }
+        }
+    }
+
+    (buttons, axes)
+}
+
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+}
+
+fn emit_changes(
+    signal: &Signal<GamepadEvent>,
+    id: GamepadId,
+    gamepad: &Gamepad,
+    buttons: &[bool; GamepadButton::COUNT],
+    axes: &[f32; GamepadAxis::COUNT],
+) {
+    use GamepadButton as B;
+    const BUTTONS: [GamepadButton; GamepadButton::COUNT] = [
+        B::A,
+        B::B,
+        B::X,
+        B::Y,
+        B::Back,
+        B::Guide,
+        B::Start,
+        B::LeftStick,
+        B::RightStick,
+        B::LeftShoulder,
+        B::RightShoulder,
+        B::DPadUp,
+        B::DPadDown,
+        B::DPadLeft,
+        B::DPadRight,
+        B::Misc1,
+        B::Paddle1,
+        B::Paddle2,
+        B::Paddle3,
+        B::Paddle4,
+        B::Touchpad,
+    ];
+    use GamepadAxis as A;
+    const AXES: [GamepadAxis; GamepadAxis::COUNT] = [
+        A::LeftX,
+        A::LeftY,
+        A::RightX,
+        A::RightY,
+        A::LeftTrigger,
+        A::RightTrigger,
+    ];
+
+    for &button in BUTTONS.iter() {
+        let index = button.get_index();
+        if buttons[index] != gamepad.buttons[index] {
+            let event = GamepadButtonEvent {
+                gamepad: id,
+                button,
+            };
+            signal.emit(&if buttons[index] {
+                GamepadEvent::ButtonPressed(event)
+            } else {
+                GamepadEvent::ButtonReleased(event)
+            });
+        }
+    }
+
+    for &axis in AXES.iter() {
+        let value = axes[axis.get_index()];
+        if (value - gamepad.axes[axis.get_index()]).abs() > f32::EPSILON {
+            signal.emit(&GamepadEvent::AxisMoved(GamepadAxisEvent {
+                gamepad: id,
+                axis,
+                value,
+            }));
+        }
+    }
+}
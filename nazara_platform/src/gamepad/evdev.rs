@@ -0,0 +1,559 @@
+//! Linux devices, read from the evdev interface of `/dev/input`
+//!
+//! Buttons, axes and hats are numbered as SDL does, so SDL mappings apply to them.
+
+use super::{DeviceInfo, GamepadBackend, GamepadDevice, RawEvent};
+use nazara_core::errors::{NazaraResult, PlatformError};
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    ffi::{CStr, CString},
+    fs, io, mem,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    ptr,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+const EV_SYN: u16 = 0x00;
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+const EV_FF: u16 = 0x15;
+const EV_MAX: usize = 0x1f;
+
+const SYN_REPORT: u16 = 0;
+const SYN_DROPPED: u16 = 3;
+
+const BTN_JOYSTICK: usize = 0x120;
+const BTN_GAMEPAD: usize = 0x130;
+const KEY_MAX: usize = 0x2ff;
+
+const ABS_HAT0X: usize = 0x10;
+const ABS_HAT3Y: usize = 0x17;
+const ABS_MAX: usize = 0x3f;
+
+const FF_RUMBLE: usize = 0x50;
+const FF_MAX: usize = 0x7f;
+
+/// Delay between two scans of `/dev/input`
+const SCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+// Requests of the evdev ioctls, see linux/input.h
+const IOC_WRITE: u32 = 1;
+const IOC_READ: u32 = 2;
+
+fn ioc(direction: u32, number: usize, size: usize) -> libc::Ioctl {
+    ((direction << 30) | ((size as u32) << 16) | (u32::from(b'E') << 8) | number as u32)
+        as libc::Ioctl
+}
+
+fn eviocgid() -> libc::Ioctl {
+    ioc(IOC_READ, 0x02, mem::size_of::<libc::input_id>())
+}
+
+fn eviocgname(len: usize) -> libc::Ioctl {
+    ioc(IOC_READ, 0x06, len)
+}
+
+fn eviocgkey(len: usize) -> libc::Ioctl {
+    ioc(IOC_READ, 0x18, len)
+}
+
+fn eviocgbit(event_type: usize, len: usize) -> libc::Ioctl {
+    ioc(IOC_READ, 0x20 + event_type, len)
+}
+
+fn eviocgabs(axis: usize) -> libc::Ioctl {
+    ioc(IOC_READ, 0x40 + axis, mem::size_of::<libc::input_absinfo>())
+}
+
+fn eviocsff() -> libc::Ioctl {
+    ioc(IOC_WRITE, 0x80, mem::size_of::<libc::ff_effect>())
+}
+
+fn has_bit(bits: &[u8], bit: usize) -> bool {
+    bits.get(bit / 8)
+        .is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
+}
+
+/// Owned file descriptor, closed on drop
+struct Fd(libc::c_int);
+
+impl Fd {
+    fn open(path: &Path) -> Option<(Fd, bool)> {
+        let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let flags = libc::O_NONBLOCK | libc::O_CLOEXEC;
+
+        // Writing is only needed for rumble, which is given up if the device is read-only
+        // Safety: the path is a valid null-terminated string
+        let fd = unsafe { libc::open(path.as_ptr(), libc::O_RDWR | flags) };
+        if fd >= 0 {
+            return Some((Fd(fd), true));
+        }
+        // Safety: the path is a valid null-terminated string
+        let fd = unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | flags) };
+        if fd >= 0 {
+            Some((Fd(fd), false))
+        } else {
+            None
+        }
+    }
+
+    /// Run an ioctl filling a value
+    ///
+    /// # Safety
+    /// `request` must be a request writing at most `size_of::<T>()` bytes
+    unsafe fn read_value<T>(&self, request: libc::Ioctl, value: &mut T) -> bool {
+        libc::ioctl(self.0, request, value as *mut T) >= 0
+    }
+
+    /// Run an ioctl filling a buffer, whose length is given by the request
+    fn read_bits(&self, request: impl Fn(usize) -> libc::Ioctl, bit_count: usize) -> Vec<u8> {
+        let mut bits = vec![0u8; bit_count / 8 + 1];
+        // Safety: the request writes at most the length of the buffer
+        let result = unsafe { libc::ioctl(self.0, request(bits.len()), bits.as_mut_ptr()) };
+        if result < 0 {
+            bits.iter_mut().for_each(|byte| *byte = 0);
+        }
+
+        bits
+    }
+}
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        // Safety: the descriptor is owned and closed once
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+/// Reads the gamepads and joysticks connected to the computer
+///
+/// Devices are detected when they are plugged, as long as the user can read them, which
+/// usually requires being in the `input` group.
+pub struct EvdevBackend {
+    last_scan: Option<Instant>,
+    // Devices currently opened, their path is removed when they are dropped
+    opened: Rc<RefCell<HashSet<PathBuf>>>,
+    // Devices which aren't gamepads or couldn't be opened
+    ignored: HashSet<PathBuf>,
+}
+
+impl EvdevBackend {
+    pub fn new() -> EvdevBackend {
+        EvdevBackend {
+            last_scan: None,
+            opened: Rc::new(RefCell::new(HashSet::new())),
+            ignored: HashSet::new(),
+        }
+    }
+}
+
+impl Default for EvdevBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GamepadBackend for EvdevBackend {
+    fn scan(&mut self) -> Vec<Box<dyn GamepadDevice>> {
+        let now = Instant::now();
+        if let Some(last_scan) = self.last_scan {
+            if now.duration_since(last_scan) < SCAN_INTERVAL {
+                return Vec::new();
+            }
+        }
+        self.last_scan = Some(now);
+
+        let entries = match fs::read_dir("/dev/input") {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        let paths: HashSet<PathBuf> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().as_bytes().starts_with(b"event"))
+            .map(|entry| entry.path())
+            .collect();
+
+        // A path freed by an unplugged device may be reused by a new one
+        self.ignored.retain(|path| paths.contains(path));
+
+        let mut devices = Vec::new();
+        for path in paths {
+            if self.ignored.contains(&path) || self.opened.borrow().contains(&path) {
+                continue;
+            }
+
+            match EvdevDevice::open(&path, self.opened.clone()) {
+                Some(device) => {
+                    nazara_core::nz_info!(
+                        "platform",
+                        "gamepad {} connected ({})",
+                        device.info.name,
+                        path.display()
+                    );
+                    self.opened.borrow_mut().insert(path);
+                    devices.push(Box::new(device) as Box<dyn GamepadDevice>);
+                }
+                None => {
+                    self.ignored.insert(path);
+                }
+            }
+        }
+
+        devices
+    }
+}
+
+struct EvdevDevice {
+    fd: Fd,
+    path: PathBuf,
+    opened: Rc<RefCell<HashSet<PathBuf>>>,
+    info: DeviceInfo,
+    // Index of each key and absolute axis code, as SDL numbers them
+    button_indices: Vec<Option<usize>>,
+    axis_indices: Vec<Option<usize>>,
+    hat_indices: [Option<usize>; 4],
+    // Range of each axis
+    axis_ranges: Vec<(i32, i32)>,
+    // Horizontal and vertical direction of each hat, from -1 to 1
+    hat_directions: Vec<(i32, i32)>,
+    can_rumble: bool,
+    rumble_effect: i16,
+    // Events are dropped until the next report after the kernel buffer overflowed
+    dropping: bool,
+    pending: Vec<RawEvent>,
+}
+
+impl EvdevDevice {
+    fn open(path: &Path, opened: Rc<RefCell<HashSet<PathBuf>>>) -> Option<EvdevDevice> {
+        let (fd, writable) = Fd::open(path)?;
+
+        let event_bits = fd.read_bits(|len| eviocgbit(0, len), EV_MAX);
+        let key_bits = fd.read_bits(|len| eviocgbit(EV_KEY as usize, len), KEY_MAX);
+        let abs_bits = fd.read_bits(|len| eviocgbit(EV_ABS as usize, len), ABS_MAX);
+        let ff_bits = fd.read_bits(|len| eviocgbit(EV_FF as usize, len), FF_MAX);
+
+        let is_gamepad = has_bit(&event_bits, EV_KEY as usize)
+            && has_bit(&event_bits, EV_ABS as usize)
+            && (has_bit(&key_bits, BTN_GAMEPAD) || has_bit(&key_bits, BTN_JOYSTICK));
+        if !is_gamepad {
+            return None;
+        }
+
+        let mut name = [0u8; 256];
+        // Safety: the request writes at most the length of the buffer
+        unsafe {
+            libc::ioctl(fd.0, eviocgname(name.len() - 1), name.as_mut_ptr());
+        }
+        let name = CStr::from_bytes_until_nul(&name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut id = libc::input_id {
+            bustype: 0,
+            vendor: 0,
+            product: 0,
+            version: 0,
+        };
+        // Safety: the request fills an input_id
+        unsafe {
+            fd.read_value(eviocgid(), &mut id);
+        }
+
+        // SDL numbers the joystick buttons first, then the other ones
+        let mut button_indices = vec![None; KEY_MAX + 1];
+        let mut button_count = 0;
+        for code in (BTN_JOYSTICK..=KEY_MAX).chain(0..BTN_JOYSTICK) {
+            if has_bit(&key_bits, code) {
+                button_indices[code] = Some(button_count);
+                button_count += 1;
+            }
+        }
+
+        let mut axis_indices = vec![None; ABS_MAX + 1];
+        let mut axis_ranges = Vec::new();
+        for code in (0..=ABS_MAX).filter(|code| !(ABS_HAT0X..=ABS_HAT3Y).contains(code)) {
+            if has_bit(&abs_bits, code) {
+                axis_indices[code] = Some(axis_ranges.len());
+                axis_ranges.push((0, 0));
+            }
+        }
+
+        let mut hat_indices = [None; 4];
+        let mut hat_count = 0;
+        for (hat, index) in hat_indices.iter_mut().enumerate() {
+            let code = ABS_HAT0X + hat * 2;
+            if has_bit(&abs_bits, code) || has_bit(&abs_bits, code + 1) {
+                *index = Some(hat_count);
+                hat_count += 1;
+            }
+        }
+
+        let info = DeviceInfo {
+            name: name.clone(),
+            guid: make_guid(&id, &name),
+            button_count,
+            axis_count: axis_ranges.len(),
+            hat_count,
+        };
+        let mut device = EvdevDevice {
+            fd,
+            path: path.to_path_buf(),
+            opened,
+            info,
+            button_indices,
+            axis_indices,
+            hat_indices,
+            axis_ranges,
+            hat_directions: vec![(0, 0); hat_count],
+            can_rumble: writable
+                && has_bit(&event_bits, EV_FF as usize)
+                && has_bit(&ff_bits, FF_RUMBLE),
+            rumble_effect: -1,
+            dropping: false,
+            pending: Vec::new(),
+        };
+        // The state at connection is reported by the first poll
+        device.synchronize();
+
+        Some(device)
+    }
+
+    /// Read the whole state of the device, reporting it as events
+    fn synchronize(&mut self) {
+        let key_states = self.fd.read_bits(eviocgkey, KEY_MAX);
+        for code in 0..=KEY_MAX {
+            if let Some(index) = self.button_indices[code] {
+                self.pending.push(RawEvent::Button {
+                    index,
+                    pressed: has_bit(&key_states, code),
+                });
+            }
+        }
+
+        for code in 0..=ABS_MAX {
+            let is_hat = (ABS_HAT0X..=ABS_HAT3Y).contains(&code);
+            if !is_hat && self.axis_indices[code].is_none() {
+                continue;
+            }
+
+            let mut absinfo = libc::input_absinfo {
+                value: 0,
+                minimum: 0,
+                maximum: 0,
+                fuzz: 0,
+                flat: 0,
+                resolution: 0,
+            };
+            // Safety: the request fills an input_absinfo
+            if !unsafe { self.fd.read_value(eviocgabs(code), &mut absinfo) } {
+                continue;
+            }
+            if let Some(index) = self.axis_indices[code] {
+                self.axis_ranges[index] = (absinfo.minimum, absinfo.maximum);
+            }
+            self.handle_axis(code, absinfo.value);
+        }
+    }
+
+    fn handle_axis(&mut self, code: usize, value: i32) {
+        if (ABS_HAT0X..=ABS_HAT3Y).contains(&code) {
+            let hat = (code - ABS_HAT0X) / 2;
+            if let Some(index) = self.hat_indices[hat] {
+                let directions = &mut self.hat_directions[index];
+                if (code - ABS_HAT0X).is_multiple_of(2) {
+                    directions.0 = value.signum();
+                } else {
+                    directions.1 = value.signum();
+                }
+
+                let value = match directions.1 {
+                    -1 => 1,
+                    1 => 4,
+                    _ => 0,
+                } | match directions.0 {
+                    1 => 2,
+                    -1 => 8,
+                    _ => 0,
+                };
+                self.pending.push(RawEvent::Hat { index, value });
+            }
+        } else if let Some(index) = self.axis_indices.get(code).copied().flatten() {
+            let (min, max) = self.axis_ranges[index];
+            let value = if max > min {
+                (2.0 * (value - min) as f32 / (max - min) as f32 - 1.0).clamp(-1.0, 1.0)
+            } else {
+                0.0
+            };
+            self.pending.push(RawEvent::Axis { index, value });
+        }
+    }
+
+    fn handle_event(&mut self, event: &libc::input_event) {
+        if self.dropping {
+            if event.type_ == EV_SYN && event.code == SYN_REPORT {
+                self.dropping = false;
+                self.synchronize();
+            }
+            return;
+        }
+
+        match event.type_ {
+            EV_SYN if event.code == SYN_DROPPED => self.dropping = true,
+            EV_KEY => {
+                if let Some(index) = self
+                    .button_indices
+                    .get(event.code as usize)
+                    .copied()
+                    .flatten()
+                {
+                    // Key repeats, whose value is 2, are reported as presses
+                    self.pending.push(RawEvent::Button {
+                        index,
+                        pressed: event.value != 0,
+                    });
+                }
+            }
+            EV_ABS => self.handle_axis(event.code as usize, event.value),
+            _ => (),
+        }
+    }
+}
+
+impl Drop for EvdevDevice {
+    fn drop(&mut self) {
+        self.opened.borrow_mut().remove(&self.path);
+    }
+}
+
+impl GamepadDevice for EvdevDevice {
+    fn get_info(&self) -> &DeviceInfo {
+        &self.info
+    }
+
+    fn poll(&mut self, events: &mut Vec<RawEvent>) {
+        const BUFFER_LEN: usize = 32;
+        let event_size = mem::size_of::<libc::input_event>();
+
+        loop {
+            // Safety: input_event is plain data, for which zeroed memory is valid
+            let mut buffer: [libc::input_event; BUFFER_LEN] = unsafe { mem::zeroed() };
+            // Safety: the buffer can hold BUFFER_LEN events
+            let read = unsafe {
+                libc::read(
+                    self.fd.0,
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    event_size * BUFFER_LEN,
+                )
+            };
+
+            if read < 0 {
+                let error = io::Error::last_os_error();
+                if error.raw_os_error() != Some(libc::EAGAIN) {
+                    nazara_core::nz_info!(
+                        "platform",
+                        "gamepad {} disconnected ({})",
+                        self.info.name,
+                        error
+                    );
+                    self.pending.push(RawEvent::Disconnected);
+                }
+                break;
+            }
+
+            let count = read as usize / event_size;
+            for event in &buffer[..count] {
+                self.handle_event(event);
+            }
+            if count < BUFFER_LEN {
+                break;
+            }
+        }
+
+        events.append(&mut self.pending);
+    }
+
+    fn rumble(&mut self, strong: f32, weak: f32, duration: Duration) -> NazaraResult<()> {
+        if !self.can_rumble {
+            return Err(PlatformError::Unsupported(format!(
+                "{} has no rumble motor or isn't writable",
+                self.info.name
+            ))
+            .into());
+        }
+
+        // Safety: ff_effect is plain data, for which zeroed memory is valid
+        let mut effect: libc::ff_effect = unsafe { mem::zeroed() };
+        effect.type_ = FF_RUMBLE as u16;
+        // Uploading an effect with the id of the previous one replaces it
+        effect.id = self.rumble_effect;
+        effect.replay.length = duration.as_millis().min(u128::from(u16::MAX)) as u16;
+        let rumble = libc::ff_rumble_effect {
+            strong_magnitude: (strong * f32::from(u16::MAX)) as u16,
+            weak_magnitude: (weak * f32::from(u16::MAX)) as u16,
+        };
+        // Safety: the union of the effect starts with the parameters of a rumble effect, which
+        // is smaller and less aligned than it
+        unsafe {
+            ptr::write(effect.u.as_mut_ptr() as *mut libc::ff_rumble_effect, rumble);
+        }
+
+        // Safety: the request reads and updates an ff_effect
+        if !unsafe { self.fd.read_value(eviocsff(), &mut effect) } {
+            return Err(PlatformError::Unsupported(format!(
+                "failed to upload rumble effect to {}: {}",
+                self.info.name,
+                io::Error::last_os_error()
+            ))
+            .into());
+        }
+        self.rumble_effect = effect.id;
+
+        // Safety: input_event is plain data, for which zeroed memory is valid
+        let mut play: libc::input_event = unsafe { mem::zeroed() };
+        play.type_ = EV_FF;
+        play.code = effect.id as u16;
+        play.value = 1;
+        // Safety: the event is a valid input_event
+        let written = unsafe {
+            libc::write(
+                self.fd.0,
+                &play as *const libc::input_event as *const libc::c_void,
+                mem::size_of::<libc::input_event>(),
+            )
+        };
+        if written < 0 {
+            return Err(PlatformError::Unsupported(format!(
+                "failed to play rumble effect on {}: {}",
+                self.info.name,
+                io::Error::last_os_error()
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the SDL GUID of a device, as 32 hexadecimal digits
+fn make_guid(id: &libc::input_id, name: &str) -> String {
+    let mut bytes = Vec::with_capacity(16);
+    bytes.extend_from_slice(&id.bustype.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+
+    if id.vendor != 0 && id.product != 0 {
+        for value in [id.vendor, id.product, id.version].iter() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes.extend_from_slice(&[0, 0]);
+        }
+    } else {
+        // Devices without identifiers are told apart by their name
+        bytes.extend(name.bytes().chain(std::iter::repeat(0)).take(12));
+    }
+
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
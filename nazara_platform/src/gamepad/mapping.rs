@@ -0,0 +1,379 @@
+use crate::enums::{GamepadAxis, GamepadButton};
+use nazara_core::errors::{NazaraResult, PlatformError};
+use std::collections::BTreeMap;
+
+/// Mappings of common controllers, completed by the `SDL_GAMECONTROLLERCONFIG` environment
+/// variable
+///
+/// They have no platform field, so the mock devices use them on every platform.
+const DEFAULT_MAPPINGS: &str = "\
+030000005e0400008e02000014010000,Xbox 360 Controller,a:b0,b:b1,back:b6,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b8,leftshoulder:b4,leftstick:b9,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b10,righttrigger:a5,rightx:a3,righty:a4,start:b7,x:b2,y:b3,
+030000005e040000ea02000001030000,Xbox One Controller,a:b0,b:b1,back:b6,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b8,leftshoulder:b4,leftstick:b9,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b10,righttrigger:a5,rightx:a3,righty:a4,start:b7,x:b2,y:b3,
+030000004c050000c405000011810000,PS4 Controller,a:b0,b:b1,back:b8,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b10,leftshoulder:b4,leftstick:b11,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b12,righttrigger:a5,rightx:a3,righty:a4,start:b9,x:b3,y:b2,
+";
+
+/// Name of the current platform in the `platform` field of the mappings, as SDL names it
+#[cfg(target_os = "linux")]
+const PLATFORM: &str = "Linux";
+#[cfg(target_os = "windows")]
+const PLATFORM: &str = "Windows";
+#[cfg(target_os = "macos")]
+const PLATFORM: &str = "Mac OS X";
+#[cfg(target_os = "ios")]
+const PLATFORM: &str = "iOS";
+#[cfg(target_os = "android")]
+const PLATFORM: &str = "Android";
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "android"
+)))]
+const PLATFORM: &str = "Unknown";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Part of an input axis used by a binding
+pub enum AxisRange {
+    Full,
+    Positive,
+    Negative,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Raw input of a device, identified by its index as SDL numbers them
+pub enum MappingSource {
+    Button(usize),
+    Axis {
+        index: usize,
+        range: AxisRange,
+        inverted: bool,
+    },
+    /// Direction of a hat, `mask` being 1 for up, 2 for right, 4 for down and 8 for left
+    Hat {
+        index: usize,
+        mask: u8,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Standard control driven by a raw input
+pub enum MappingTarget {
+    Button(GamepadButton),
+    Axis(GamepadAxis, AxisRange),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+/// Layout of a controller model, parsed from a SDL mapping string
+pub struct GamepadMapping {
+    guid: String,
+    name: String,
+    platform: Option<String>,
+    bindings: Vec<(MappingTarget, MappingSource)>,
+}
+
+impl GamepadMapping {
+    /// Parse a SDL mapping string, such as the lines of `gamecontrollerdb.txt`
+    ///
+    /// ```
+    /// use nazara_platform::enums::{GamepadAxis, GamepadButton};
+    /// use nazara_platform::gamepad::mapping::{
+    ///     AxisRange, GamepadMapping, MappingSource, MappingTarget,
+    /// };
+    ///
+    /// let mapping = GamepadMapping::parse(
+    ///     "03000000de2800000112000001000000,Steam Controller,a:b0,lefttrigger:+a2,lefty:a1~,dpup:h0.1,platform:Linux,",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(mapping.get_name(), "Steam Controller");
+    /// assert_eq!(mapping.get_platform(), Some("Linux"));
+    /// assert_eq!(
+    ///     mapping.get_bindings()[0],
+    ///     (MappingTarget::Button(GamepadButton::A), MappingSource::Button(0))
+    /// );
+    /// assert_eq!(
+    ///     mapping.get_bindings()[2],
+    ///     (
+    ///         MappingTarget::Axis(GamepadAxis::LeftY, AxisRange::Full),
+    ///         MappingSource::Axis { index: 1, range: AxisRange::Full, inverted: true }
+    ///     )
+    /// );
+    ///
+    /// assert!(GamepadMapping::parse("not a mapping").is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `mapping` - GUID, name and comma-separated `control:input` bindings
+    pub fn parse(mapping: &str) -> NazaraResult<GamepadMapping> {
+        let error = |reason: &str| {
+            PlatformError::InvalidMapping(format!("{} in \"{}\"", reason, mapping)).into()
+        };
+
+        let mut fields = mapping.trim().split(',');
+        let guid = fields.next().unwrap_or_default().to_ascii_lowercase();
+        if guid.len() != 32 || !guid.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error("invalid GUID"));
+        }
+        let name = fields.next().ok_or_else(|| error("missing name"))?;
+
+        let mut bindings = Vec::new();
+        let mut platform = None;
+        for field in fields.filter(|field| !field.is_empty()) {
+            let mut parts = field.splitn(2, ':');
+            let target = parts.next().unwrap_or_default();
+            let source = parts.next().ok_or_else(|| error("missing input"))?;
+
+            if target == "platform" {
+                platform = Some(source.to_string());
+                continue;
+            }
+            // Metadata and controls Nazara doesn't know are ignored
+            if target == "crc" || target == "hint" {
+                continue;
+            }
+            let target = match parse_target(target) {
+                Some(target) => target,
+                None => continue,
+            };
+            let source = parse_source(source).ok_or_else(|| error("invalid input"))?;
+            bindings.push((target, source));
+        }
+
+        Ok(GamepadMapping {
+            guid,
+            name: name.to_string(),
+            platform,
+            bindings,
+        })
+    }
+
+    pub fn get_guid(&self) -> &str {
+        &self.guid
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the platform the mapping is for, `None` if it is for every platform
+    pub fn get_platform(&self) -> Option<&str> {
+        self.platform.as_deref()
+    }
+
+    /// Return true if the mapping is for the current platform or for every platform
+    pub fn is_for_current_platform(&self) -> bool {
+        self.platform
+            .as_deref()
+            .is_none_or(|platform| platform.eq_ignore_ascii_case(PLATFORM))
+    }
+
+    pub fn get_bindings(&self) -> &[(MappingTarget, MappingSource)] {
+        &self.bindings
+    }
+}
+
+/// Collection of mappings, looked up by the GUID of the devices
+///
+/// ```
+/// use nazara_platform::gamepad::mapping::MappingDatabase;
+///
+/// let mut database = MappingDatabase::new();
+/// // Xbox 360 controllers are known by default
+/// assert!(database.get("030000005e0400008e02000014010000").is_some());
+///
+/// // Invalid lines and lines for other platforms are skipped
+/// let other_platform = if cfg!(target_os = "windows") { "Linux" } else { "Windows" };
+/// let added = database.add_mappings(&format!(
+///     "# Lines starting with a hash are comments
+///      03000000de2800000112000001000000,Steam Controller,a:b0,b:b1,
+///      03000000de2800000112000001000000,Other Steam Controller,a:b1,platform:{},
+///      not a mapping",
+///     other_platform
+/// ));
+/// assert_eq!(added, 1);
+///
+/// // The CRC of the name and the version of the device are ignored if needed
+/// let mapping = database.get("0300abcdde2800000112000002000000").unwrap();
+/// assert_eq!(mapping.get_name(), "Steam Controller");
+/// assert!(database.get(&"é".repeat(16)).is_none());
+/// ```
+#[derive(Clone)]
+pub struct MappingDatabase {
+    // Sorted, so the lowest GUID is used when several mappings are close to a device
+    mappings: BTreeMap<String, GamepadMapping>,
+}
+
+impl MappingDatabase {
+    /// Create a database holding the default mappings and those of the
+    /// `SDL_GAMECONTROLLERCONFIG` environment variable
+    pub fn new() -> MappingDatabase {
+        let mut database = MappingDatabase::empty();
+        database.add_mappings(DEFAULT_MAPPINGS);
+
+        if let Ok(mappings) = std::env::var("SDL_GAMECONTROLLERCONFIG") {
+            database.add_mappings(&mappings);
+        }
+
+        database
+    }
+
+    pub fn empty() -> MappingDatabase {
+        MappingDatabase {
+            mappings: BTreeMap::new(),
+        }
+    }
+
+    /// Add a mapping, replacing the one of the same GUID
+    pub fn add_mapping(&mut self, mapping: GamepadMapping) {
+        self.mappings.insert(mapping.guid.clone(), mapping);
+    }
+
+    /// Parse and add mappings, one per line, return how many were added
+    ///
+    /// As SDL does, the mappings of other platforms are skipped. Invalid lines are skipped with
+    /// a warning.
+    ///
+    /// # Arguments
+    /// * `mappings` - SDL mapping strings, empty lines and lines starting with `#` are ignored
+    pub fn add_mappings(&mut self, mappings: &str) -> usize {
+        let mut count = 0;
+        for line in mappings.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match GamepadMapping::parse(line) {
+                Ok(mapping) if mapping.is_for_current_platform() => {
+                    self.add_mapping(mapping);
+                    count += 1;
+                }
+                Ok(_) => {}
+                Err(err) => nazara_core::nz_warning!("platform", "{}", err),
+            }
+        }
+
+        count
+    }
+
+    /// Return the mapping of a device
+    ///
+    /// As SDL does, mappings whose GUID only differs by the CRC of the name or by the version
+    /// of the device are used when no mapping matches exactly, the lowest GUID first.
+    ///
+    /// # Arguments
+    /// * `guid` - SDL GUID of the device, as 32 hexadecimal digits
+    pub fn get(&self, guid: &str) -> Option<&GamepadMapping> {
+        let guid = guid.to_ascii_lowercase();
+        if let Some(mapping) = self.mappings.get(&guid) {
+            return Some(mapping);
+        }
+        if guid.len() != 32 || !guid.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let without_crc = |guid: &str| format!("{}0000{}", &guid[..4], &guid[8..]);
+        let without_version = |guid: &str| format!("{}0000{}", &guid[..24], &guid[28..]);
+        let find = |matches: &dyn Fn(&str) -> bool| {
+            self.mappings
+                .values()
+                .find(|mapping| matches(&mapping.guid))
+        };
+
+        find(&|other| without_crc(other) == without_crc(&guid)).or_else(|| {
+            find(&|other| {
+                without_version(&without_crc(other)) == without_version(&without_crc(&guid))
+            })
+        })
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.mappings.len()
+    }
+}
+
+impl Default for MappingDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_target(target: &str) -> Option<MappingTarget> {
+    let (range, name) = match target.as_bytes().first() {
+        Some(b'+') => (AxisRange::Positive, &target[1..]),
+        Some(b'-') => (AxisRange::Negative, &target[1..]),
+        _ => (AxisRange::Full, target),
+    };
+
+    let axis = match name {
+        "leftx" => Some(GamepadAxis::LeftX),
+        "lefty" => Some(GamepadAxis::LeftY),
+        "rightx" => Some(GamepadAxis::RightX),
+        "righty" => Some(GamepadAxis::RightY),
+        "lefttrigger" => Some(GamepadAxis::LeftTrigger),
+        "righttrigger" => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    };
+    if let Some(axis) = axis {
+        return Some(MappingTarget::Axis(axis, range));
+    }
+
+    let button = match name {
+        "a" => GamepadButton::A,
+        "b" => GamepadButton::B,
+        "x" => GamepadButton::X,
+        "y" => GamepadButton::Y,
+        "back" => GamepadButton::Back,
+        "guide" => GamepadButton::Guide,
+        "start" => GamepadButton::Start,
+        "leftstick" => GamepadButton::LeftStick,
+        "rightstick" => GamepadButton::RightStick,
+        "leftshoulder" => GamepadButton::LeftShoulder,
+        "rightshoulder" => GamepadButton::RightShoulder,
+        "dpup" => GamepadButton::DPadUp,
+        "dpdown" => GamepadButton::DPadDown,
+        "dpleft" => GamepadButton::DPadLeft,
+        "dpright" => GamepadButton::DPadRight,
+        "misc1" => GamepadButton::Misc1,
+        "paddle1" => GamepadButton::Paddle1,
+        "paddle2" => GamepadButton::Paddle2,
+        "paddle3" => GamepadButton::Paddle3,
+        "paddle4" => GamepadButton::Paddle4,
+        "touchpad" => GamepadButton::Touchpad,
+        _ => return None,
+    };
+
+    Some(MappingTarget::Button(button))
+}
+
+fn parse_source(source: &str) -> Option<MappingSource> {
+    let (range, source) = match source.as_bytes().first() {
+        Some(b'+') => (AxisRange::Positive, &source[1..]),
+        Some(b'-') => (AxisRange::Negative, &source[1..]),
+        _ => (AxisRange::Full, source),
+    };
+
+    if let Some(index) = source.strip_prefix('b') {
+        return Some(MappingSource::Button(index.parse().ok()?));
+    }
+    if let Some(index) = source.strip_prefix('a') {
+        let (index, inverted) = match index.strip_suffix('~') {
+            Some(index) => (index, true),
+            None => (index, false),
+        };
+
+        return Some(MappingSource::Axis {
+            index: index.parse().ok()?,
+            range,
+            inverted,
+        });
+    }
+    if let Some(hat) = source.strip_prefix('h') {
+        let mut parts = hat.splitn(2, '.');
+        let index = parts.next()?.parse().ok()?;
+        let mask = parts.next()?.parse().ok()?;
+
+        return Some(MappingSource::Hat { index, mask });
+    }
+
+    None
+}
@@ -0,0 +1,132 @@
+//! Simulated devices, driven by the application to test its gamepad handling
+
+use super::{DeviceInfo, GamepadBackend, GamepadDevice, RawEvent};
+use nazara_core::errors::NazaraResult;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// Vibration requested to a [`MockGamepad`]
+pub struct Rumble {
+    pub strong: f32,
+    pub weak: f32,
+    pub duration: Duration,
+}
+
+#[derive(Default)]
+struct DeviceState {
+    events: Vec<RawEvent>,
+    rumbles: Vec<Rumble>,
+}
+
+/// Backend whose devices are connected by the application
+///
+/// Clones share the same devices, so a clone can be given to a
+/// [`GamepadManager`](super::GamepadManager) while the original one is kept to connect devices.
+#[derive(Clone, Default)]
+pub struct MockBackend {
+    connected: Rc<RefCell<Vec<MockDevice>>>,
+}
+
+impl MockBackend {
+    pub fn new() -> MockBackend {
+        MockBackend::default()
+    }
+
+    /// Plug a device, detected at the next scan
+    ///
+    /// Its buttons are released and its hats centered. As real devices do, the initial
+    /// position of its axes is reported by the first update.
+    ///
+    /// # Arguments
+    /// * `info` - Description reported by the device
+    /// * `axes` - Initial position of each axis, from -1 to 1, the missing ones being at 0
+    pub fn connect(&self, info: DeviceInfo, axes: &[f32]) -> MockGamepad {
+        let state = Rc::new(RefCell::new(DeviceState::default()));
+        let gamepad = MockGamepad { state };
+        for (index, &value) in axes.iter().enumerate() {
+            gamepad.set_axis(index, value);
+        }
+
+        let state = gamepad.state.clone();
+        self.connected.borrow_mut().push(MockDevice { info, state });
+
+        gamepad
+    }
+}
+
+impl GamepadBackend for MockBackend {
+    fn scan(&mut self) -> Vec<Box<dyn GamepadDevice>> {
+        self.connected
+            .borrow_mut()
+            .drain(..)
+            .map(|device| Box::new(device) as Box<dyn GamepadDevice>)
+            .collect()
+    }
+}
+
+/// Handle to a simulated device, whose changes are read at the next update
+pub struct MockGamepad {
+    state: Rc<RefCell<DeviceState>>,
+}
+
+impl MockGamepad {
+    fn push(&self, event: RawEvent) {
+        self.state.borrow_mut().events.push(event);
+    }
+
+    pub fn set_button(&self, index: usize, pressed: bool) {
+        self.push(RawEvent::Button { index, pressed });
+    }
+
+    /// # Arguments
+    /// * `index` - Index of the axis
+    /// * `value` - Position of the axis, from -1 to 1
+    pub fn set_axis(&self, index: usize, value: f32) {
+        self.push(RawEvent::Axis {
+            index,
+            value: value.clamp(-1.0, 1.0),
+        });
+    }
+
+    /// # Arguments
+    /// * `index` - Index of the hat
+    /// * `value` - Directions of the hat, see [`RawEvent::Hat`]
+    pub fn set_hat(&self, index: usize, value: u8) {
+        self.push(RawEvent::Hat { index, value });
+    }
+
+    /// Unplug the device
+    pub fn disconnect(&self) {
+        self.push(RawEvent::Disconnected);
+    }
+
+    /// Return the vibrations requested to the device so far
+    pub fn get_rumbles(&self) -> Vec<Rumble> {
+        self.state.borrow().rumbles.clone()
+    }
+}
+
+struct MockDevice {
+    info: DeviceInfo,
+    state: Rc<RefCell<DeviceState>>,
+}
+
+impl GamepadDevice for MockDevice {
+    fn get_info(&self) -> &DeviceInfo {
+        &self.info
+    }
+
+    fn poll(&mut self, events: &mut Vec<RawEvent>) {
+        events.append(&mut self.state.borrow_mut().events);
+    }
+
+    fn rumble(&mut self, strong: f32, weak: f32, duration: Duration) -> NazaraResult<()> {
+        self.state.borrow_mut().rumbles.push(Rumble {
+            strong,
+            weak,
+            duration,
+        });
+
+        Ok(())
+    }
+}
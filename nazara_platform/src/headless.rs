@@ -0,0 +1,231 @@
+//! Backend simulating windows without a windowing system, to run the application in automated
+//! tests
+//!
+//! Headless windows never receive events from the platform: events are scheduled with an
+//! [`EventInjector`], and the time advances by a fixed step at each frame.
+
+use crate::{
+    enums::{MouseMoveEvent, MoveEvent, ResizeEvent, WindowEvent},
+    window::WindowId,
+};
+use std::{cell::Cell, cell::RefCell, rc::Rc, time::Duration};
+
+#[derive(Debug, Clone, Copy)]
+enum Trigger {
+    NextFrame,
+    Frame(u64),
+    Time(Duration),
+}
+
+struct ScheduledEvent {
+    trigger: Trigger,
+    window: WindowId,
+    event: WindowEvent,
+}
+
+/// Events waiting to be delivered to the windows, in the order they were scheduled
+#[derive(Default)]
+pub(crate) struct ScheduledEvents {
+    events: RefCell<Vec<ScheduledEvent>>,
+}
+
+impl ScheduledEvents {
+    fn push(&self, trigger: Trigger, window: WindowId, event: WindowEvent) {
+        self.events.borrow_mut().push(ScheduledEvent {
+            trigger,
+            window,
+            event,
+        });
+    }
+
+    /// Remove and return the events due at a frame
+    pub(crate) fn take_due(&self, frame: u64, time: Duration) -> Vec<(WindowId, WindowEvent)> {
+        let mut events = self.events.borrow_mut();
+        let (due, pending) = events
+            .drain(..)
+            .partition(|scheduled| match scheduled.trigger {
+                Trigger::NextFrame => true,
+                Trigger::Frame(at) => at <= frame,
+                Trigger::Time(at) => at <= time,
+            });
+        *events = pending;
+
+        due.into_iter()
+            .map(|scheduled: ScheduledEvent| (scheduled.window, scheduled.event))
+            .collect()
+    }
+}
+
+/// Schedules events for the windows of a
+/// [`WindowApplication`](crate::window_application::WindowApplication)
+///
+/// The events are delivered at the beginning of a frame, before the application is executed,
+/// as if the windows received them from the platform. Events for closed windows are dropped.
+///
+/// ```
+/// use nazara_core::config::Config;
+/// use nazara_platform::enums::{Backend, Key, KeyEvent, KeyModifiers, WindowEvent};
+/// use nazara_platform::keyboard::Keyboard;
+/// use nazara_platform::window::WindowBuilder;
+/// use nazara_platform::window_application::WindowApplication;
+/// use std::time::Duration;
+///
+/// let mut app = WindowApplication::with_backend(Config::new(), Backend::Headless);
+/// let window = WindowBuilder::new().build(&mut app).unwrap();
+///
+/// let key_event = KeyEvent {
+///     scancode: 1,
+///     key: Key::Escape,
+///     modifiers: KeyModifiers::default(),
+///     repeated: false,
+/// };
+/// let injector = app.get_event_injector();
+/// injector.push_at_frame(2, window.get_id(), WindowEvent::KeyPressed(key_event));
+/// injector.push_at_time(Duration::from_secs(1), window.get_id(), WindowEvent::CloseRequested);
+///
+/// assert!(app.step().unwrap());
+/// assert!(app.step().unwrap());
+/// assert!(!Keyboard::is_key_pressed(Key::Escape));
+/// assert!(app.step().unwrap());
+/// assert!(Keyboard::is_key_pressed(Key::Escape));
+///
+/// // The window is closed at the frame beginning after one second of simulated time, at 60
+/// // frames per second, which quits the application
+/// while app.step().unwrap() {}
+/// assert!(!window.is_open());
+/// assert_eq!(app.get_frame_count(), 61);
+/// ```
+#[derive(Clone)]
+pub struct EventInjector {
+    events: Rc<ScheduledEvents>,
+}
+
+impl EventInjector {
+    pub(crate) fn new(events: Rc<ScheduledEvents>) -> EventInjector {
+        EventInjector { events }
+    }
+
+    /// Deliver an event at the next frame
+    ///
+    /// # Arguments
+    /// * `window` - Window receiving the event
+    /// * `event` - Event to deliver
+    pub fn push(&self, window: WindowId, event: WindowEvent) {
+        self.events.push(Trigger::NextFrame, window, event);
+    }
+
+    /// Deliver an event at a given frame, or at the next one if it already began
+    ///
+    /// # Arguments
+    /// * `frame` - Index of the frame, the first frame being 0
+    /// * `window` - Window receiving the event
+    /// * `event` - Event to deliver
+    pub fn push_at_frame(&self, frame: u64, window: WindowId, event: WindowEvent) {
+        self.events.push(Trigger::Frame(frame), window, event);
+    }
+
+    /// Deliver an event at the first frame beginning once the application ran for a duration
+    ///
+    /// # Arguments
+    /// * `time` - Time elapsed since the application was created
+    /// * `window` - Window receiving the event
+    /// * `event` - Event to deliver
+    pub fn push_at_time(&self, time: Duration, window: WindowId, event: WindowEvent) {
+        self.events.push(Trigger::Time(time), window, event);
+    }
+
+    /// Return the number of events not delivered yet
+    pub fn get_pending_count(&self) -> usize {
+        self.events.events.borrow().len()
+    }
+}
+
+/// Simulated window, whose geometry changes are reported as events at the next frame
+pub(crate) struct HeadlessWindow {
+    id: WindowId,
+    // Physical pixels
+    size: Cell<(u32, u32)>,
+    position: Cell<(i32, i32)>,
+    scale_factor: Cell<f64>,
+    cursor_position: Cell<Option<(f64, f64)>>,
+    events: Rc<ScheduledEvents>,
+}
+
+impl HeadlessWindow {
+    pub(crate) fn new(
+        id: WindowId,
+        size: (u32, u32),
+        position: (i32, i32),
+        events: Rc<ScheduledEvents>,
+    ) -> HeadlessWindow {
+        HeadlessWindow {
+            id,
+            size: Cell::new(size),
+            position: Cell::new(position),
+            scale_factor: Cell::new(1.0),
+            cursor_position: Cell::new(None),
+            events,
+        }
+    }
+
+    pub(crate) fn get_size(&self) -> (u32, u32) {
+        self.size.get()
+    }
+
+    pub(crate) fn set_size(&self, width: u32, height: u32) {
+        self.size.set((width, height));
+        self.events.push(
+            Trigger::NextFrame,
+            self.id,
+            WindowEvent::Resized(ResizeEvent { width, height }),
+        );
+    }
+
+    pub(crate) fn get_position(&self) -> (i32, i32) {
+        self.position.get()
+    }
+
+    pub(crate) fn set_position(&self, x: i32, y: i32) {
+        self.position.set((x, y));
+        self.events.push(
+            Trigger::NextFrame,
+            self.id,
+            WindowEvent::Moved(MoveEvent { x, y }),
+        );
+    }
+
+    pub(crate) fn get_scale_factor(&self) -> f64 {
+        self.scale_factor.get()
+    }
+
+    /// Move the cursor inside of the window, in physical pixels
+    pub(crate) fn set_cursor_position(&self, x: f64, y: f64) {
+        let (previous_x, previous_y) = self.cursor_position.get().unwrap_or((x, y));
+        self.cursor_position.set(Some((x, y)));
+        self.events.push(
+            Trigger::NextFrame,
+            self.id,
+            WindowEvent::MouseMoved(MouseMoveEvent {
+                x,
+                y,
+                delta_x: x - previous_x,
+                delta_y: y - previous_y,
+            }),
+        );
+    }
+
+    /// Update the simulated state with an event delivered to the window
+    pub(crate) fn apply(&self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(e) => self.size.set((e.width, e.height)),
+            WindowEvent::Moved(e) => self.position.set((e.x, e.y)),
+            WindowEvent::MouseMoved(e) => self.cursor_position.set(Some((e.x, e.y))),
+            WindowEvent::MouseLeft => self.cursor_position.set(None),
+            WindowEvent::ScaleFactorChanged(e) => {
+                self.scale_factor.set(e.scale_factor);
+                self.size.set((e.width, e.height));
+            }
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! State of the keyboard and the mouse, queried through [`Keyboard`](crate::keyboard::Keyboard)
+//! and [`Mouse`](crate::mouse::Mouse)
+//!
+//! The state is fed with the events received by the windows of the
+//! [`WindowApplication`](crate::window_application::WindowApplication), and is updated after each
+//! frame. Events can also be injected, to simulate input in tests.
+//!
+//! The state is local to each thread, the event loop feeding the one of the main thread.
+
+use crate::enums::{Key, KeyModifiers, MouseButton, MouseWheelDelta, WindowEvent};
+use std::{cell::RefCell, collections::HashSet};
+
+#[derive(Default)]
+pub(crate) struct InputState {
+    pub(crate) pressed_keys: HashSet<Key>,
+    pub(crate) pressed_scancodes: HashSet<u32>,
+    pub(crate) just_pressed_keys: HashSet<Key>,
+    pub(crate) just_released_keys: HashSet<Key>,
+    pub(crate) modifiers: KeyModifiers,
+    pub(crate) cursor_position: Option<(f64, f64)>,
+    pub(crate) motion: (f64, f64),
+    pub(crate) raw_motion: (f64, f64),
+    pub(crate) wheel: (f32, f32),
+    pub(crate) pressed_buttons: HashSet<MouseButton>,
+    pub(crate) just_pressed_buttons: HashSet<MouseButton>,
+    pub(crate) just_released_buttons: HashSet<MouseButton>,
+}
+
+// Pixels scrolled by a touchpad equivalent to a notch of a mouse wheel
+const PIXELS_PER_LINE: f64 = 20.0;
+
+impl InputState {
+    fn release_all(&mut self) {
+        let cursor_position = self.cursor_position;
+        *self = InputState::default();
+        self.cursor_position = cursor_position;
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<InputState> = RefCell::new(InputState::default());
+}
+
+pub(crate) fn with_state<R, F: FnOnce(&InputState) -> R>(func: F) -> R {
+    STATE.with(|state| func(&state.borrow()))
+}
+
+/// Updates the input state of the current thread with an event, as if a window received it
+///
+/// ```
+/// use nazara_platform::enums::{Key, KeyEvent, KeyModifiers, WindowEvent};
+/// use nazara_platform::input;
+/// use nazara_platform::keyboard::Keyboard;
+///
+/// let event = KeyEvent {
+///     scancode: 57,
+///     key: Key::Space,
+///     modifiers: KeyModifiers::default(),
+///     repeated: false,
+/// };
+///
+/// input::inject_event(&WindowEvent::KeyPressed(event));
+/// assert!(Keyboard::is_key_pressed(Key::Space));
+/// assert!(Keyboard::is_key_just_pressed(Key::Space));
+///
+/// input::update();
+/// assert!(Keyboard::is_key_pressed(Key::Space));
+/// assert!(!Keyboard::is_key_just_pressed(Key::Space));
+///
+/// input::inject_event(&WindowEvent::KeyReleased(event));
+/// assert!(!Keyboard::is_key_pressed(Key::Space));
+/// assert!(Keyboard::is_key_just_released(Key::Space));
+/// ```
+///
+/// # Arguments
+/// * `event` - Event to apply, events unrelated to input are ignored
+pub fn inject_event(event: &WindowEvent) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        match event {
+            WindowEvent::KeyPressed(e) => {
+                state.modifiers = e.modifiers;
+                state.pressed_scancodes.insert(e.scancode);
+                if state.pressed_keys.insert(e.key) && !e.repeated {
+                    state.just_pressed_keys.insert(e.key);
+                }
+            }
+            WindowEvent::KeyReleased(e) => {
+                state.modifiers = e.modifiers;
+                state.pressed_scancodes.remove(&e.scancode);
+                if state.pressed_keys.remove(&e.key) {
+                    state.just_released_keys.insert(e.key);
+                }
+            }
+            WindowEvent::MouseButtonPressed(e) => {
+                state.cursor_position = Some((e.x, e.y));
+                if state.pressed_buttons.insert(e.button) {
+                    state.just_pressed_buttons.insert(e.button);
+                }
+            }
+            WindowEvent::MouseButtonReleased(e) => {
+                state.cursor_position = Some((e.x, e.y));
+                if state.pressed_buttons.remove(&e.button) {
+                    state.just_released_buttons.insert(e.button);
+                }
+            }
+            WindowEvent::MouseMoved(e) => {
+                state.cursor_position = Some((e.x, e.y));
+                state.motion.0 += e.delta_x;
+                state.motion.1 += e.delta_y;
+            }
+            WindowEvent::RawMouseMotion(e) => {
+                state.raw_motion.0 += e.delta_x;
+                state.raw_motion.1 += e.delta_y;
+            }
+            WindowEvent::MouseWheelMoved(e) => {
+                let (x, y) = match e.delta {
+                    MouseWheelDelta::Lines { x, y } => (x, y),
+                    MouseWheelDelta::Pixels { x, y } => {
+                        ((x / PIXELS_PER_LINE) as f32, (y / PIXELS_PER_LINE) as f32)
+                    }
+                };
+                state.wheel.0 += x;
+                state.wheel.1 += y;
+            }
+            WindowEvent::MouseLeft => state.cursor_position = None,
+            // Keys released while no window has the focus are never reported
+            WindowEvent::FocusLost => state.release_all(),
+            _ => (),
+        }
+    });
+}
+
+/// Begins a new frame: keys and buttons are no longer "just" pressed or released, and the
+/// mouse motion and wheel deltas are reset
+///
+/// Called by the [`WindowApplication`](crate::window_application::WindowApplication) after each
+/// frame.
+pub fn update() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.just_pressed_keys.clear();
+        state.just_released_keys.clear();
+        state.just_pressed_buttons.clear();
+        state.just_released_buttons.clear();
+        state.motion = (0.0, 0.0);
+        state.raw_motion = (0.0, 0.0);
+        state.wheel = (0.0, 0.0);
+    });
+}
+
+/// Releases every key and button, without reporting them as just released
+///
+/// Injecting [`WindowEvent::FocusLost`] has the same effect.
+pub fn reset() {
+    STATE.with(|state| state.borrow_mut().release_all());
+}
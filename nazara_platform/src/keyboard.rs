@@ -0,0 +1,74 @@
+use crate::{
+    enums::{Key, KeyModifiers},
+    input,
+};
+
+/// Queries the state of the keyboard
+///
+/// The state is updated by the [`input`](crate::input) module, a key being pressed as long as
+/// one of the windows reported it as pressed and not released.
+///
+/// ```
+/// use nazara_platform::enums::{Key, KeyEvent, KeyModifiers, WindowEvent};
+/// use nazara_platform::input;
+/// use nazara_platform::keyboard::Keyboard;
+///
+/// let modifiers = KeyModifiers { shift: true, ..KeyModifiers::default() };
+/// input::inject_event(&WindowEvent::KeyPressed(KeyEvent {
+///     scancode: 30,
+///     key: Key::A,
+///     modifiers,
+///     repeated: false,
+/// }));
+///
+/// assert!(Keyboard::is_key_pressed(Key::A));
+/// assert!(Keyboard::is_scancode_pressed(30));
+/// assert!(!Keyboard::is_key_pressed(Key::B));
+/// assert!(Keyboard::get_modifiers().shift);
+/// assert_eq!(Keyboard::get_pressed_keys(), vec![Key::A]);
+/// ```
+pub struct Keyboard;
+
+impl Keyboard {
+    /// Returns true if the key is held
+    ///
+    /// # Arguments
+    /// * `key` - Key to check
+    pub fn is_key_pressed(key: Key) -> bool {
+        input::with_state(|state| state.pressed_keys.contains(&key))
+    }
+
+    /// Returns true if the key at the given physical location is held
+    ///
+    /// # Arguments
+    /// * `scancode` - Scancode of the key, as given by [`KeyEvent::scancode`](crate::enums::KeyEvent::scancode)
+    pub fn is_scancode_pressed(scancode: u32) -> bool {
+        input::with_state(|state| state.pressed_scancodes.contains(&scancode))
+    }
+
+    /// Returns true if the key was pressed during the current frame, auto-repeat excluded
+    ///
+    /// # Arguments
+    /// * `key` - Key to check
+    pub fn is_key_just_pressed(key: Key) -> bool {
+        input::with_state(|state| state.just_pressed_keys.contains(&key))
+    }
+
+    /// Returns true if the key was released during the current frame
+    ///
+    /// # Arguments
+    /// * `key` - Key to check
+    pub fn is_key_just_released(key: Key) -> bool {
+        input::with_state(|state| state.just_released_keys.contains(&key))
+    }
+
+    /// Returns the keys held, in no particular order
+    pub fn get_pressed_keys() -> Vec<Key> {
+        input::with_state(|state| state.pressed_keys.iter().copied().collect())
+    }
+
+    /// Returns the modifiers held during the last key event
+    pub fn get_modifiers() -> KeyModifiers {
+        input::with_state(|state| state.modifiers)
+    }
+}
@@ -1,4 +1,12 @@
+pub mod clipboard;
+pub mod cursor;
 pub mod enums;
+pub mod gamepad;
+pub mod headless;
+pub mod input;
+pub mod keyboard;
+pub mod monitor;
+pub mod mouse;
 pub mod window;
 pub mod window_application;
 
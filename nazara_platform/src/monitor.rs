@@ -0,0 +1,153 @@
+//! Monitors connected to the computer, listed by
+//! [`WindowApplication::get_monitors`](crate::window_application::WindowApplication::get_monitors)
+
+use crate::enums::VideoMode;
+use winit::monitor::MonitorHandle;
+
+/// Monitor simulated by the headless backend
+const HEADLESS_NAME: &str = "Headless";
+const HEADLESS_MODES: [VideoMode; 2] = [
+    VideoMode {
+        width: 1920,
+        height: 1080,
+        bit_depth: 32,
+        refresh_rate: 60,
+    },
+    VideoMode {
+        width: 1280,
+        height: 720,
+        bit_depth: 32,
+        refresh_rate: 60,
+    },
+];
+
+#[derive(Debug, PartialEq, Clone)]
+/// Describes a monitor, as it was when it was listed
+///
+/// ```
+/// use nazara_core::config::Config;
+/// use nazara_platform::enums::{Backend, Fullscreen};
+/// use nazara_platform::window::WindowBuilder;
+/// use nazara_platform::window_application::WindowApplication;
+///
+/// // The headless backend simulates a single monitor
+/// let mut app = WindowApplication::with_backend(Config::new(), Backend::Headless);
+/// let monitor = app.get_primary_monitor().unwrap();
+/// assert!(monitor.is_primary());
+/// assert_eq!(app.get_monitors(), vec![monitor.clone()]);
+/// assert_eq!(monitor.get_refresh_rate(), Some(60));
+///
+/// // Modes are sorted from the largest
+/// let mode = monitor.get_video_modes()[0];
+/// assert_eq!((mode.width, mode.height), monitor.get_size());
+///
+/// let window = WindowBuilder::new()
+///     .with_fullscreen(Fullscreen::Exclusive(monitor.clone(), mode))
+///     .build(&mut app)
+///     .unwrap();
+/// assert_eq!(window.get_fullscreen(), Some(Fullscreen::Exclusive(monitor, mode)));
+/// ```
+pub struct Monitor {
+    name: String,
+    position: (i32, i32),
+    size: (u32, u32),
+    scale_factor: f64,
+    video_modes: Vec<VideoMode>,
+    primary: bool,
+    // Simulated monitors have no handle
+    handle: Option<MonitorHandle>,
+}
+
+impl Monitor {
+    /// Describe a monitor of the platform
+    ///
+    /// # Arguments
+    /// * `handle` - Monitor to describe
+    /// * `primary` - Primary monitor of the platform
+    pub(crate) fn from_native(handle: MonitorHandle, primary: &MonitorHandle) -> Monitor {
+        let mut video_modes: Vec<VideoMode> = handle
+            .video_modes()
+            .map(|mode| VideoMode::from(&mode))
+            .collect();
+        video_modes.sort_by(|a, b| {
+            (b.width, b.height, b.bit_depth, b.refresh_rate).cmp(&(
+                a.width,
+                a.height,
+                a.bit_depth,
+                a.refresh_rate,
+            ))
+        });
+        video_modes.dedup();
+
+        Monitor {
+            name: handle.name().unwrap_or_default(),
+            position: handle.position().into(),
+            size: handle.size().into(),
+            scale_factor: handle.scale_factor(),
+            video_modes,
+            primary: handle == *primary,
+            handle: Some(handle),
+        }
+    }
+
+    /// Describe the monitor simulated by the headless backend
+    pub(crate) fn headless() -> Monitor {
+        let mode = HEADLESS_MODES[0];
+
+        Monitor {
+            name: String::from(HEADLESS_NAME),
+            position: (0, 0),
+            size: (mode.width, mode.height),
+            scale_factor: 1.0,
+            video_modes: HEADLESS_MODES.to_vec(),
+            primary: true,
+            handle: None,
+        }
+    }
+
+    /// Return the name of the monitor, which may be empty
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the position of the top-left corner of the monitor on the desktop, in physical
+    /// pixels
+    pub fn get_position(&self) -> (i32, i32) {
+        self.position
+    }
+
+    /// Return the resolution of the monitor, in physical pixels
+    pub fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the ratio between physical and logical pixels of the windows on the monitor
+    pub fn get_scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Return the refresh rate of the monitor in Hz, `None` if it is unknown
+    ///
+    /// The platform only reports the refresh rates of the video modes: the highest one of
+    /// the modes matching the resolution of the monitor is returned.
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.video_modes
+            .iter()
+            .filter(|mode| (mode.width, mode.height) == self.size)
+            .map(|mode| mode.refresh_rate)
+            .max()
+    }
+
+    /// Return the modes supported in exclusive fullscreen, from the largest
+    pub fn get_video_modes(&self) -> &[VideoMode] {
+        &self.video_modes
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    pub(crate) fn get_handle(&self) -> Option<&MonitorHandle> {
+        self.handle.as_ref()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{enums::MouseButton, input};
+
+/// Queries the state of the mouse
+///
+/// The position is the last one reported by a window, relative to its top-left corner.
+///
+/// ```
+/// use nazara_platform::enums::{KeyModifiers, MouseButton, MouseButtonEvent, MouseMoveEvent, WindowEvent};
+/// use nazara_platform::input;
+/// use nazara_platform::mouse::Mouse;
+///
+/// input::inject_event(&WindowEvent::MouseMoved(MouseMoveEvent {
+///     x: 120.0,
+///     y: 80.0,
+///     delta_x: 20.0,
+///     delta_y: -5.0,
+/// }));
+/// input::inject_event(&WindowEvent::MouseButtonPressed(MouseButtonEvent {
+///     button: MouseButton::Left,
+///     x: 120.0,
+///     y: 80.0,
+///     modifiers: KeyModifiers::default(),
+/// }));
+///
+/// assert_eq!(Mouse::get_position(), Some((120.0, 80.0)));
+/// assert_eq!(Mouse::get_motion(), (20.0, -5.0));
+/// assert!(Mouse::is_button_pressed(MouseButton::Left));
+/// assert!(Mouse::is_button_just_pressed(MouseButton::Left));
+///
+/// input::update();
+/// assert_eq!(Mouse::get_motion(), (0.0, 0.0));
+/// assert!(!Mouse::is_button_just_pressed(MouseButton::Left));
+/// ```
+pub struct Mouse;
+
+impl Mouse {
+    /// Returns the position of the cursor, `None` if it is outside of the windows
+    pub fn get_position() -> Option<(f64, f64)> {
+        input::with_state(|state| state.cursor_position)
+    }
+
+    /// Returns the distance the cursor moved during the current frame
+    pub fn get_motion() -> (f64, f64) {
+        input::with_state(|state| state.motion)
+    }
+
+    /// Returns the distance the mouse moved during the current frame, even if the cursor is
+    /// locked or stopped by the edges of the screen
+    pub fn get_raw_motion() -> (f64, f64) {
+        input::with_state(|state| state.raw_motion)
+    }
+
+    /// Returns the lines scrolled during the current frame, horizontally and vertically
+    pub fn get_wheel_delta() -> (f32, f32) {
+        input::with_state(|state| state.wheel)
+    }
+
+    /// Returns true if the button is held
+    ///
+    /// # Arguments
+    /// * `button` - Button to check
+    pub fn is_button_pressed(button: MouseButton) -> bool {
+        input::with_state(|state| state.pressed_buttons.contains(&button))
+    }
+
+    /// Returns true if the button was pressed during the current frame
+    ///
+    /// # Arguments
+    /// * `button` - Button to check
+    pub fn is_button_just_pressed(button: MouseButton) -> bool {
+        input::with_state(|state| state.just_pressed_buttons.contains(&button))
+    }
+
+    /// Returns true if the button was released during the current frame
+    ///
+    /// # Arguments
+    /// * `button` - Button to check
+    pub fn is_button_just_released(button: MouseButton) -> bool {
+        input::with_state(|state| state.just_released_buttons.contains(&button))
+    }
+}
@@ -1,53 +1,956 @@
-use super::window_application::{WindowApplication, WindowCallbacks};
-use std::{cell::RefCell, rc::Rc};
-use winit::window::{Window as W_Window, WindowBuilder as W_WindowBuilder};
+use super::{
+    cursor::{self, Cursor},
+    enums::{
+        CursorGrab, Fullscreen, KeyEvent, MouseButtonEvent, MouseMoveEvent, MouseWheelEvent,
+        MoveEvent, RawMotionEvent, ResizeEvent, ScaleFactorEvent, TextEvent, VideoMode,
+        WindowEvent,
+    },
+    headless::HeadlessWindow,
+    monitor::Monitor,
+    window_application::{WindowApplication, WindowCallbacks},
+};
+use nazara_core::{
+    enums::PixelFormatType,
+    errors::{ImageError, NazaraError, NazaraResult, PlatformError},
+    image::Image,
+    nz_warning,
+    signal::Signal,
+};
+use std::rc::Rc;
+use winit::{
+    dpi::{LogicalSize, PhysicalPosition},
+    monitor::MonitorHandle,
+    window::{
+        Fullscreen as W_Fullscreen, Icon, Window as W_Window, WindowBuilder as W_WindowBuilder,
+    },
+};
 
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+/// Identifies a window of a [`WindowApplication`], even after it was closed
+pub struct WindowId(pub(crate) u64);
+
+/// Window of the platform or simulated by the headless backend
+pub(crate) enum WindowHandle {
+    Native(W_Window),
+    Headless(HeadlessWindow),
+}
+
+impl WindowHandle {
+    /// Return the size of the inside of the window, in logical pixels
+    fn get_size(&self) -> (u32, u32) {
+        let (width, height) = match self {
+            WindowHandle::Native(handle) => handle.inner_size().into(),
+            WindowHandle::Headless(handle) => handle.get_size(),
+        };
+        let scale_factor = self.get_scale_factor();
+
+        (
+            (f64::from(width) / scale_factor).round() as u32,
+            (f64::from(height) / scale_factor).round() as u32,
+        )
+    }
+
+    fn set_size(&self, width: u32, height: u32) {
+        match self {
+            WindowHandle::Native(handle) => handle.set_inner_size(LogicalSize::new(width, height)),
+            WindowHandle::Headless(handle) => {
+                let scale_factor = handle.get_scale_factor();
+                handle.set_size(
+                    (f64::from(width) * scale_factor).round() as u32,
+                    (f64::from(height) * scale_factor).round() as u32,
+                );
+            }
+        }
+    }
+
+    fn get_position(&self) -> Option<(i32, i32)> {
+        match self {
+            WindowHandle::Native(handle) => handle.outer_position().ok().map(Into::into),
+            WindowHandle::Headless(handle) => Some(handle.get_position()),
+        }
+    }
+
+    fn set_position(&self, x: i32, y: i32) {
+        match self {
+            WindowHandle::Native(handle) => handle.set_outer_position(PhysicalPosition::new(x, y)),
+            WindowHandle::Headless(handle) => handle.set_position(x, y),
+        }
+    }
+
+    fn get_scale_factor(&self) -> f64 {
+        match self {
+            WindowHandle::Native(handle) => handle.scale_factor(),
+            WindowHandle::Headless(handle) => handle.get_scale_factor(),
+        }
+    }
+}
+
+/// Attributes of a window which the platform can't be queried for
+struct Attributes {
+    title: String,
+    min_size: Option<(u32, u32)>,
+    max_size: Option<(u32, u32)>,
+    resizable: bool,
+    decorations: bool,
+    fullscreen: Option<Fullscreen>,
+    always_on_top: bool,
+    transparent: bool,
+    visible: bool,
+    cursor: Cursor,
+    cursor_visible: bool,
+    cursor_grab: CursorGrab,
+}
+
+/// A window of the application, closed when dropped
+///
+/// Sizes are given in logical pixels, which are scaled by the scale factor of the monitor,
+/// while positions are given in physical pixels on the desktop.
 pub struct Window {
-    handle: W_Window,
-    callbacks: Rc<RefCell<WindowCallbacks>>,
+    id: WindowId,
+    callbacks: Rc<WindowCallbacks>,
+    attributes: Attributes,
 }
 
 impl Window {
-    pub fn new(app: &mut WindowApplication) -> Window {
-        let handle = W_Window::new(app.get_event_loop()).unwrap();
+    /// Create a window with the default settings, see [`WindowBuilder`]
+    pub fn new(app: &mut WindowApplication) -> NazaraResult<Window> {
+        WindowBuilder::new().build(app)
+    }
+
+    pub fn get_id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Return the text shown in the title bar of the window
+    pub fn get_title(&self) -> &str {
+        &self.attributes.title
+    }
+
+    pub fn set_title<T: Into<String>>(&mut self, title: T) {
+        let title = title.into();
+        self.with_native(|handle| handle.set_title(&title));
+        self.attributes.title = title;
+    }
+
+    /// Return the size of the inside of the window, in logical pixels
+    pub fn get_size(&self) -> (u32, u32) {
+        self.callbacks
+            .with_handle(WindowHandle::get_size)
+            .unwrap_or((0, 0))
+    }
+
+    /// Resize the inside of the window
+    ///
+    /// # Arguments
+    /// * `width` - Width in logical pixels
+    /// * `height` - Height in logical pixels
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.callbacks
+            .with_handle(|handle| handle.set_size(width, height));
+    }
+
+    /// Return the position of the top-left corner of the window, `None` if the platform
+    /// doesn't expose it (Wayland)
+    pub fn get_position(&self) -> Option<(i32, i32)> {
+        self.callbacks
+            .with_handle(WindowHandle::get_position)
+            .flatten()
+    }
+
+    /// Move the window, ignored by platforms which don't allow it (Wayland)
+    ///
+    /// # Arguments
+    /// * `x` - Position of the left side of the window on the desktop, in physical pixels
+    /// * `y` - Position of the top side of the window on the desktop, in physical pixels
+    pub fn set_position(&mut self, x: i32, y: i32) {
+        self.callbacks
+            .with_handle(|handle| handle.set_position(x, y));
+    }
+
+    /// Return the minimum size of the window in logical pixels, `None` if it has none
+    pub fn get_min_size(&self) -> Option<(u32, u32)> {
+        self.attributes.min_size
+    }
+
+    /// Prevent the user from making the window smaller than a size
+    ///
+    /// # Arguments
+    /// * `size` - Minimum width and height in logical pixels, `None` to remove the limit
+    pub fn set_min_size(&mut self, size: Option<(u32, u32)>) {
+        self.with_native(|handle| {
+            handle.set_min_inner_size(size.map(|(w, h)| LogicalSize::new(w, h)))
+        });
+        self.attributes.min_size = size;
+    }
+
+    /// Return the maximum size of the window in logical pixels, `None` if it has none
+    pub fn get_max_size(&self) -> Option<(u32, u32)> {
+        self.attributes.max_size
+    }
+
+    /// Prevent the user from making the window larger than a size
+    ///
+    /// # Arguments
+    /// * `size` - Maximum width and height in logical pixels, `None` to remove the limit
+    pub fn set_max_size(&mut self, size: Option<(u32, u32)>) {
+        self.with_native(|handle| {
+            handle.set_max_inner_size(size.map(|(w, h)| LogicalSize::new(w, h)))
+        });
+        self.attributes.max_size = size;
+    }
+
+    /// Return true if the user can resize the window
+    pub fn is_resizable(&self) -> bool {
+        self.attributes.resizable
+    }
+
+    /// Allow or prevent the user from resizing the window
+    pub fn set_resizable(&mut self, resizable: bool) {
+        self.with_native(|handle| handle.set_resizable(resizable));
+        self.attributes.resizable = resizable;
+    }
+
+    /// Return true if the window has a title bar and borders
+    pub fn has_decorations(&self) -> bool {
+        self.attributes.decorations
+    }
+
+    /// Show or hide the title bar and the borders of the window
+    pub fn set_decorations(&mut self, decorations: bool) {
+        self.with_native(|handle| handle.set_decorations(decorations));
+        self.attributes.decorations = decorations;
+    }
 
-        Self::new_from_handle(app, handle)
+    /// Return the fullscreen mode of the window, `None` if it is windowed
+    pub fn get_fullscreen(&self) -> Option<Fullscreen> {
+        self.attributes.fullscreen.clone()
     }
 
-    pub fn set_render_callback<F>(&mut self, func: F)
+    /// Make the window cover a monitor, or return to windowed mode
+    ///
+    /// The window stays windowed if the monitor doesn't support the video mode requested.
+    ///
+    /// # Arguments
+    /// * `fullscreen` - Fullscreen mode, `None` for windowed mode
+    pub fn set_fullscreen(&mut self, fullscreen: Option<Fullscreen>) {
+        let applied = self.callbacks.with_handle(|handle| match handle {
+            WindowHandle::Native(handle) => {
+                let w_fullscreen = fullscreen.as_ref().and_then(|fullscreen| {
+                    to_winit_fullscreen(fullscreen, handle.current_monitor())
+                });
+                let applied = w_fullscreen.is_some() == fullscreen.is_some();
+                handle.set_fullscreen(w_fullscreen);
+
+                applied
+            }
+            WindowHandle::Headless(_) => fullscreen.as_ref().is_none_or(is_headless_mode_supported),
+        });
+
+        if let Some(applied) = applied {
+            self.attributes.fullscreen = fullscreen.filter(|_| applied);
+        }
+    }
+
+    /// Return true if the window is kept above the other windows
+    pub fn is_always_on_top(&self) -> bool {
+        self.attributes.always_on_top
+    }
+
+    /// Keep the window above the other windows
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        self.with_native(|handle| handle.set_always_on_top(always_on_top));
+        self.attributes.always_on_top = always_on_top;
+    }
+
+    /// Return true if the window was created with a transparent background, which can't be
+    /// changed afterwards
+    pub fn is_transparent(&self) -> bool {
+        self.attributes.transparent
+    }
+
+    /// Return true if the window is shown
+    pub fn is_visible(&self) -> bool {
+        self.attributes.visible
+    }
+
+    /// Show or hide the window
+    pub fn set_visible(&mut self, visible: bool) {
+        self.with_native(|handle| handle.set_visible(visible));
+        self.attributes.visible = visible;
+    }
+
+    /// Change the icon shown in the title bar and the taskbar
+    ///
+    /// # Arguments
+    /// * `icon` - 2D image in [`RGBA8`], [`RGB8`] or [`L8`] format, `None` to restore the
+    ///   default icon
+    ///
+    /// [`RGBA8`]: PixelFormatType::RGBA8
+    /// [`RGB8`]: PixelFormatType::RGB8
+    /// [`L8`]: PixelFormatType::L8
+    pub fn set_icon(&mut self, icon: Option<&Image>) -> NazaraResult<()> {
+        let icon = icon.map(icon_from_image).transpose()?;
+        self.with_native(|handle| handle.set_window_icon(icon));
+
+        Ok(())
+    }
+
+    pub fn get_cursor(&self) -> &Cursor {
+        &self.attributes.cursor
+    }
+
+    /// Change the image of the cursor while it is over the window
+    ///
+    /// Custom cursors are only supported by X11 and headless windows, the other windowing
+    /// systems return an error and keep the previous cursor.
+    ///
+    /// # Arguments
+    /// * `cursor` - [`SystemCursor`](crate::enums::SystemCursor) or
+    ///   [`CustomCursor`](crate::cursor::CustomCursor) to show
+    pub fn set_cursor<C: Into<Cursor>>(&mut self, cursor: C) -> NazaraResult<()> {
+        let cursor = cursor.into();
+        match &cursor {
+            Cursor::System(system) => {
+                self.with_native(|handle| handle.set_cursor_icon((*system).into()))
+            }
+            Cursor::Custom(custom) => {
+                let mut result = Ok(());
+                self.with_native(|handle| result = cursor::define_native_cursor(handle, custom));
+                result.map_err(PlatformError::Unsupported)?;
+            }
+        }
+        self.attributes.cursor = cursor;
+
+        Ok(())
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        self.attributes.cursor_visible
+    }
+
+    /// Show or hide the cursor while it is over the window, a locked cursor is always hidden
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        let locked = self.attributes.cursor_grab == CursorGrab::Locked;
+        self.with_native(|handle| {
+            show_native_cursor(handle, &self.attributes.cursor, visible && !locked)
+        });
+        self.attributes.cursor_visible = visible;
+    }
+
+    pub fn get_cursor_grab(&self) -> CursorGrab {
+        self.attributes.cursor_grab
+    }
+
+    /// Confine or lock the cursor in the window, or release it
+    ///
+    /// While the cursor is locked, it is hidden and [`on_mouse_moved`](Window::on_mouse_moved)
+    /// is no longer emitted: the movements of the mouse are reported by
+    /// [`on_raw_mouse_motion`](Window::on_raw_mouse_motion).
+    ///
+    /// ```
+    /// use nazara_core::config::Config;
+    /// use nazara_platform::enums::{Backend, CursorGrab, RawMotionEvent, WindowEvent};
+    /// use nazara_platform::mouse::Mouse;
+    /// use nazara_platform::window::WindowBuilder;
+    /// use nazara_platform::window_application::WindowApplication;
+    /// use std::{cell::Cell, rc::Rc};
+    ///
+    /// let mut app = WindowApplication::with_backend(Config::new(), Backend::Headless);
+    /// let mut window = WindowBuilder::new().build(&mut app).unwrap();
+    /// window.set_cursor_grab(CursorGrab::Locked).unwrap();
+    /// assert_eq!(window.get_cursor_grab(), CursorGrab::Locked);
+    ///
+    /// let motion = Rc::new(Cell::new(None));
+    /// let received = motion.clone();
+    /// window
+    ///     .on_raw_mouse_motion()
+    ///     .connect(move |_| received.set(Some(Mouse::get_raw_motion())));
+    ///
+    /// let event = RawMotionEvent { delta_x: 4.0, delta_y: -2.0 };
+    /// app.get_event_injector()
+    ///     .push(window.get_id(), WindowEvent::RawMouseMotion(event));
+    /// assert!(app.step().unwrap());
+    /// assert_eq!(motion.get(), Some((4.0, -2.0)));
+    ///
+    /// // The motion is reset after each frame
+    /// assert_eq!(Mouse::get_raw_motion(), (0.0, 0.0));
+    /// ```
+    ///
+    /// # Arguments
+    /// * `grab` - How the cursor is held by the window
+    pub fn set_cursor_grab(&mut self, grab: CursorGrab) -> NazaraResult<()> {
+        let visible = self.attributes.cursor_visible && grab != CursorGrab::Locked;
+        let mut result = Ok(());
+        self.with_native(|handle| {
+            result = handle
+                .set_cursor_grab(grab != CursorGrab::None)
+                .map_err(|err| PlatformError::Unsupported(format!("cursor grab: {}", err)));
+            if result.is_ok() {
+                show_native_cursor(handle, &self.attributes.cursor, visible);
+            }
+            if result.is_ok() && grab == CursorGrab::Locked {
+                // The cursor shows up at the center of the window once released
+                let size = handle.inner_size();
+                let center = PhysicalPosition::new(
+                    f64::from(size.width) / 2.0,
+                    f64::from(size.height) / 2.0,
+                );
+                let _ = handle.set_cursor_position(center);
+            }
+        });
+        result?;
+
+        self.callbacks.set_cursor_locked(grab == CursorGrab::Locked);
+        self.attributes.cursor_grab = grab;
+
+        Ok(())
+    }
+
+    /// Move the cursor
+    ///
+    /// # Arguments
+    /// * `x` - Horizontal position relative to the left of the window, in physical pixels
+    /// * `y` - Vertical position relative to the top of the window, in physical pixels
+    pub fn set_cursor_position(&mut self, x: f64, y: f64) -> NazaraResult<()> {
+        self.callbacks
+            .with_handle(|handle| match handle {
+                WindowHandle::Native(handle) => handle
+                    .set_cursor_position(PhysicalPosition::new(x, y))
+                    .map_err(|err| {
+                        PlatformError::Unsupported(format!("cursor position: {}", err)).into()
+                    }),
+                WindowHandle::Headless(handle) => {
+                    handle.set_cursor_position(x, y);
+                    Ok(())
+                }
+            })
+            .unwrap_or(Ok(()))
+    }
+
+    /// Return the monitor the window is on, `None` if it can't be determined
+    pub fn get_current_monitor(&self) -> Option<Monitor> {
+        self.callbacks.with_handle(|handle| match handle {
+            WindowHandle::Native(handle) => {
+                Monitor::from_native(handle.current_monitor(), &handle.primary_monitor())
+            }
+            WindowHandle::Headless(_) => Monitor::headless(),
+        })
+    }
+
+    /// Return the ratio between physical and logical pixels of the window
+    pub fn get_scale_factor(&self) -> f64 {
+        self.callbacks
+            .with_handle(WindowHandle::get_scale_factor)
+            .unwrap_or(1.0)
+    }
+
+    /// Replace all slots connected to [`Window::on_render`] by a single callback
+    pub fn set_render_callback<F>(&mut self, mut func: F)
     where
-        F: FnMut() -> () + 'static,
+        F: FnMut() + 'static,
     {
-        self.callbacks.borrow_mut().render_callback = Box::new(func);
+        let signal = &self.callbacks.on_render;
+        signal.disconnect_all();
+        signal.connect(move |_| func());
+    }
+
+    /// Signal emitted each time the window has to be redrawn
+    pub fn on_render(&self) -> &Signal<()> {
+        &self.callbacks.on_render
+    }
+
+    /// Signal emitted for every event received by the window, before the signal dedicated to
+    /// the type of the event
+    pub fn on_event(&self) -> &Signal<WindowEvent> {
+        &self.callbacks.on_event
+    }
+
+    /// Signal emitted when a key is pressed, and repeatedly while it is held
+    pub fn on_key_pressed(&self) -> &Signal<KeyEvent> {
+        &self.callbacks.on_key_pressed
+    }
+
+    /// Signal emitted when a key is released
+    pub fn on_key_released(&self) -> &Signal<KeyEvent> {
+        &self.callbacks.on_key_released
+    }
+
+    /// Signal emitted for each character typed, including text committed by an input method
+    pub fn on_text_entered(&self) -> &Signal<TextEvent> {
+        &self.callbacks.on_text_entered
+    }
+
+    /// Signal emitted when a mouse button is pressed inside the window
+    pub fn on_mouse_button_pressed(&self) -> &Signal<MouseButtonEvent> {
+        &self.callbacks.on_mouse_button_pressed
     }
 
-    pub(crate) fn new_from_handle(app: &mut WindowApplication, handle: W_Window) -> Window {
-        let callbacks = app.register_window(handle.id());
+    /// Signal emitted when a mouse button is released
+    pub fn on_mouse_button_released(&self) -> &Signal<MouseButtonEvent> {
+        &self.callbacks.on_mouse_button_released
+    }
+
+    /// Signal emitted when the cursor moves inside the window
+    pub fn on_mouse_moved(&self) -> &Signal<MouseMoveEvent> {
+        &self.callbacks.on_mouse_moved
+    }
+
+    /// Signal emitted when the mouse moves while the window has the focus
+    pub fn on_raw_mouse_motion(&self) -> &Signal<RawMotionEvent> {
+        &self.callbacks.on_raw_mouse_motion
+    }
+
+    /// Signal emitted when the mouse wheel moves while the cursor is inside the window
+    pub fn on_mouse_wheel_moved(&self) -> &Signal<MouseWheelEvent> {
+        &self.callbacks.on_mouse_wheel_moved
+    }
+
+    /// Signal emitted when the cursor enters the window
+    pub fn on_mouse_entered(&self) -> &Signal<()> {
+        &self.callbacks.on_mouse_entered
+    }
+
+    /// Signal emitted when the cursor leaves the window
+    pub fn on_mouse_left(&self) -> &Signal<()> {
+        &self.callbacks.on_mouse_left
+    }
+
+    /// Signal emitted when the user asks to close the window
+    ///
+    /// The window is closed right after, unless disabled with
+    /// [`set_close_on_request`](Window::set_close_on_request).
+    pub fn on_close_requested(&self) -> &Signal<()> {
+        &self.callbacks.on_close_requested
+    }
+
+    /// Signal emitted when the inside of the window is resized
+    pub fn on_resized(&self) -> &Signal<ResizeEvent> {
+        &self.callbacks.on_resized
+    }
+
+    /// Signal emitted when the window moves on the desktop
+    pub fn on_moved(&self) -> &Signal<MoveEvent> {
+        &self.callbacks.on_moved
+    }
+
+    /// Signal emitted when the window gains the keyboard focus
+    pub fn on_focus_gained(&self) -> &Signal<()> {
+        &self.callbacks.on_focus_gained
+    }
+
+    /// Signal emitted when the window loses the keyboard focus
+    pub fn on_focus_lost(&self) -> &Signal<()> {
+        &self.callbacks.on_focus_lost
+    }
+
+    /// Signal emitted when the window is minimized
+    pub fn on_minimized(&self) -> &Signal<()> {
+        &self.callbacks.on_minimized
+    }
+
+    /// Signal emitted when the window is restored after being minimized
+    pub fn on_restored(&self) -> &Signal<()> {
+        &self.callbacks.on_restored
+    }
+
+    /// Signal emitted when the scale factor of the window changes
+    pub fn on_scale_factor_changed(&self) -> &Signal<ScaleFactorEvent> {
+        &self.callbacks.on_scale_factor_changed
+    }
 
-        Window { handle, callbacks }
+    /// Close the window, its signals are no longer emitted
+    ///
+    /// Once the last window is closed, the application quits unless
+    /// [`WindowApplication::set_quit_on_last_window_closed`] disabled it.
+    pub fn close(&self) {
+        self.callbacks.close();
+    }
+
+    /// Return false once the window was closed
+    pub fn is_open(&self) -> bool {
+        self.callbacks.is_open()
+    }
+
+    /// Choose whether the window closes itself when the user asks to close it, which is the
+    /// default
+    ///
+    /// Disabling it lets [`on_close_requested`](Window::on_close_requested) slots ask for a
+    /// confirmation and call [`close`](Window::close) themselves.
+    ///
+    /// # Arguments
+    /// * `close` - False to keep the window open on close requests
+    pub fn set_close_on_request(&mut self, close: bool) {
+        self.callbacks.set_close_on_request(close);
+    }
+
+    /// Run a function with the platform window, attributes are only stored by headless windows
+    fn with_native<F: FnOnce(&W_Window)>(&self, func: F) {
+        self.callbacks.with_handle(|handle| {
+            if let WindowHandle::Native(handle) = handle {
+                func(handle);
+            }
+        });
     }
 }
 
+impl Drop for Window {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Settings of a window to create
+///
+/// Settings which are not given are read from the configuration of the application
+/// (`window.title`, `window.width` and `window.height`).
 pub struct WindowBuilder {
-    title: String,
+    title: Option<String>,
+    size: Option<(u32, u32)>,
+    position: Option<(i32, i32)>,
+    min_size: Option<(u32, u32)>,
+    max_size: Option<(u32, u32)>,
+    resizable: bool,
+    decorations: bool,
+    fullscreen: Option<Fullscreen>,
+    always_on_top: bool,
+    transparent: bool,
+    visible: bool,
+    icon: Option<Icon>,
 }
 
 impl WindowBuilder {
     pub fn new() -> WindowBuilder {
         WindowBuilder {
-            title: String::from("untitled Nazara window"),
+            title: None,
+            size: None,
+            position: None,
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            decorations: true,
+            fullscreen: None,
+            always_on_top: false,
+            transparent: false,
+            visible: true,
+            icon: None,
         }
     }
 
     pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
-        self.title = title.into();
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the size of the inside of the window
+    ///
+    /// # Arguments
+    /// * `width` - Width in logical pixels
+    /// * `height` - Height in logical pixels
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
         self
     }
 
-    pub fn build(self, app: &mut WindowApplication) -> Window {
-        let wbuilder = W_WindowBuilder::new();
-        let window = wbuilder.with_title(self.title).build(app.get_event_loop());
+    /// Set the position of the window, the platform chooses it otherwise
+    ///
+    /// # Arguments
+    /// * `x` - Position of the left side of the window on the desktop, in physical pixels
+    /// * `y` - Position of the top side of the window on the desktop, in physical pixels
+    pub fn with_position(mut self, x: i32, y: i32) -> Self {
+        self.position = Some((x, y));
+        self
+    }
 
-        Window::new_from_handle(app, window.unwrap())
+    /// Prevent the user from making the window smaller than a size
+    ///
+    /// # Arguments
+    /// * `width` - Minimum width in logical pixels
+    /// * `height` - Minimum height in logical pixels
+    pub fn with_min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
     }
+
+    /// Prevent the user from making the window larger than a size
+    ///
+    /// # Arguments
+    /// * `width` - Maximum width in logical pixels
+    /// * `height` - Maximum height in logical pixels
+    pub fn with_max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// Allow the user to resize the window, true by default
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Show the title bar and the borders of the window, true by default
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Create the window in fullscreen, on the primary monitor unless another one is given
+    ///
+    /// The window is created windowed if the monitor doesn't support the video mode requested.
+    pub fn with_fullscreen(mut self, fullscreen: Fullscreen) -> Self {
+        self.fullscreen = Some(fullscreen);
+        self
+    }
+
+    /// Keep the window above the other windows, false by default
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    /// Make the background of the window transparent, false by default
+    ///
+    /// The content rendered must have an alpha channel for the desktop to show through.
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    /// Show the window once created, true by default
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Set the icon shown in the title bar and the taskbar
+    ///
+    /// ```
+    /// use nazara_core::enums::PixelFormatType;
+    /// use nazara_core::image::Image;
+    /// use nazara_platform::window::WindowBuilder;
+    ///
+    /// let icon = Image::new_2d(PixelFormatType::RGBA8, 32, 32);
+    /// let builder = WindowBuilder::new().with_icon(&icon).unwrap();
+    ///
+    /// // Only formats which convert to RGBA without loss are accepted
+    /// let icon = Image::new_2d(PixelFormatType::R32F, 32, 32);
+    /// assert!(WindowBuilder::new().with_icon(&icon).is_err());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `icon` - 2D image in [`RGBA8`], [`RGB8`] or [`L8`] format
+    ///
+    /// [`RGBA8`]: PixelFormatType::RGBA8
+    /// [`RGB8`]: PixelFormatType::RGB8
+    /// [`L8`]: PixelFormatType::L8
+    pub fn with_icon(mut self, icon: &Image) -> NazaraResult<Self> {
+        self.icon = Some(icon_from_image(icon)?);
+        Ok(self)
+    }
+
+    /// Create the window
+    ///
+    /// Fails if there is no display, if the application is already running, or if the platform
+    /// refuses the settings. Headless windows only simulate their size, position and scale
+    /// factor.
+    pub fn build(self, app: &mut WindowApplication) -> NazaraResult<Window> {
+        let config = app.get_config();
+        let title = self
+            .title
+            .unwrap_or_else(|| config.get("window.title").unwrap_or_default());
+        let (width, height) = self.size.unwrap_or_else(|| {
+            (
+                config.get("window.width").unwrap_or(800),
+                config.get("window.height").unwrap_or(600),
+            )
+        });
+
+        // The identifier is allocated once the window exists, a failed build doesn't use one
+        let (id, handle, fullscreen_applied) = if app.is_headless() {
+            let id = app.allocate_window_id();
+            let position = self.position.unwrap_or((0, 0));
+            let handle =
+                HeadlessWindow::new(id, (width, height), position, app.get_scheduled_events());
+
+            let applied = self
+                .fullscreen
+                .as_ref()
+                .is_none_or(is_headless_mode_supported);
+            (id, WindowHandle::Headless(handle), applied)
+        } else {
+            let event_loop = app.get_event_loop()?;
+            let fullscreen = self.fullscreen.as_ref().and_then(|fullscreen| {
+                to_winit_fullscreen(fullscreen, event_loop.primary_monitor())
+            });
+            let fullscreen_applied = fullscreen.is_some();
+
+            let mut wbuilder = W_WindowBuilder::new()
+                .with_title(title.clone())
+                .with_inner_size(LogicalSize::new(width, height))
+                .with_resizable(self.resizable)
+                .with_decorations(self.decorations)
+                .with_always_on_top(self.always_on_top)
+                .with_transparent(self.transparent)
+                .with_window_icon(self.icon)
+                // Windows are shown once moved, so they don't appear at the wrong place first
+                .with_visible(self.visible && self.position.is_none())
+                .with_fullscreen(fullscreen);
+            if let Some((width, height)) = self.min_size {
+                wbuilder = wbuilder.with_min_inner_size(LogicalSize::new(width, height));
+            }
+            if let Some((width, height)) = self.max_size {
+                wbuilder = wbuilder.with_max_inner_size(LogicalSize::new(width, height));
+            }
+
+            let handle = wbuilder
+                .build(event_loop)
+                .map_err(|err| PlatformError::WindowCreation(err.to_string()))?;
+            if let Some((x, y)) = self.position {
+                handle.set_outer_position(PhysicalPosition::new(x, y));
+                handle.set_visible(self.visible);
+            }
+
+            (
+                app.allocate_window_id(),
+                WindowHandle::Native(handle),
+                fullscreen_applied,
+            )
+        };
+
+        let attributes = Attributes {
+            title,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            resizable: self.resizable,
+            decorations: self.decorations,
+            fullscreen: self.fullscreen.filter(|_| fullscreen_applied),
+            always_on_top: self.always_on_top,
+            transparent: self.transparent,
+            visible: self.visible,
+            cursor: Cursor::default(),
+            cursor_visible: true,
+            cursor_grab: CursorGrab::None,
+        };
+
+        Ok(Window {
+            id,
+            callbacks: app.register_window(id, handle),
+            attributes,
+        })
+    }
+}
+
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find the winit equivalent of a fullscreen mode
+///
+/// # Arguments
+/// * `fullscreen` - Fullscreen mode to convert
+/// * `default_monitor` - Monitor used if the mode gives none
+fn to_winit_fullscreen(
+    fullscreen: &Fullscreen,
+    default_monitor: MonitorHandle,
+) -> Option<W_Fullscreen> {
+    let get_handle = |monitor: &Monitor| {
+        monitor
+            .get_handle()
+            .cloned()
+            .unwrap_or_else(|| default_monitor.clone())
+    };
+
+    match fullscreen {
+        Fullscreen::Borderless(monitor) => Some(W_Fullscreen::Borderless(
+            monitor.as_ref().map_or(default_monitor.clone(), get_handle),
+        )),
+        Fullscreen::Exclusive(monitor, mode) => {
+            let video_mode = get_handle(monitor)
+                .video_modes()
+                .find(|video_mode| VideoMode::from(video_mode) == *mode);
+            if video_mode.is_none() {
+                warn_unsupported_mode(mode);
+            }
+
+            video_mode.map(W_Fullscreen::Exclusive)
+        }
+    }
+}
+
+/// Check a fullscreen mode against the monitor simulated by the headless backend
+fn is_headless_mode_supported(fullscreen: &Fullscreen) -> bool {
+    match fullscreen {
+        Fullscreen::Borderless(_) => true,
+        Fullscreen::Exclusive(monitor, mode) => {
+            let supported = monitor.get_video_modes().contains(mode);
+            if !supported {
+                warn_unsupported_mode(mode);
+            }
+
+            supported
+        }
+    }
+}
+
+fn warn_unsupported_mode(mode: &VideoMode) {
+    nz_warning!(
+        "platform",
+        "video mode {}x{} {}bpp {}Hz is not supported by the monitor",
+        mode.width,
+        mode.height,
+        mode.bit_depth,
+        mode.refresh_rate
+    );
+}
+
+/// Show or hide the cursor of a platform window
+///
+/// The platform shows its own cursor again, a custom one has to be defined once more.
+fn show_native_cursor(handle: &W_Window, cursor: &Cursor, visible: bool) {
+    handle.set_cursor_visible(visible);
+    if let (true, Cursor::Custom(custom)) = (visible, cursor) {
+        let _ = cursor::define_native_cursor(handle, custom);
+    }
+}
+
+/// Convert a 2D image to RGBA pixels, return them with the width and height of the image
+pub(crate) fn image_to_rgba(image: &Image) -> NazaraResult<(Vec<u8>, u32, u32)> {
+    let dims = image.get_dims();
+    if dims.z != 1 {
+        return Err(NazaraError::from(ImageError::DimensionError));
+    }
+
+    let pixels = image.get_mipmap_content(0);
+    let rgba = match image.get_pixel_format() {
+        PixelFormatType::RGBA8 => pixels.to_vec(),
+        PixelFormatType::RGB8 => {
+            let mut rgba = Vec::with_capacity(pixels.len() / 3 * 4);
+            for rgb in pixels.chunks_exact(3) {
+                rgba.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+            }
+            rgba
+        }
+        PixelFormatType::L8 => {
+            let mut rgba = Vec::with_capacity(pixels.len() * 4);
+            for &luminance in pixels {
+                rgba.extend_from_slice(&[luminance, luminance, luminance, 255]);
+            }
+            rgba
+        }
+        format => {
+            return Err(NazaraError::from(ImageError::UnsupportedPixelFormat(
+                format,
+            )))
+        }
+    };
+
+    Ok((rgba, dims.x as u32, dims.y as u32))
+}
+
+/// Convert a 2D image to the RGBA pixels of a window icon
+fn icon_from_image(image: &Image) -> NazaraResult<Icon> {
+    let (rgba, width, height) = image_to_rgba(image)?;
+
+    Icon::from_rgba(rgba, width, height).map_err(|_| NazaraError::from(ImageError::DimensionError))
 }
@@ -1,65 +1,717 @@
 /// Represents a Nazara application with Windows
-use nazara_core::application::Application;
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use crate::{
+    clipboard::Clipboard,
+    enums::{
+        Backend, Key, KeyEvent, KeyModifiers, MouseButtonEvent, MouseMoveEvent, MouseWheelEvent,
+        MoveEvent, RawMotionEvent, ResizeEvent, ScaleFactorEvent, TextEvent, WindowEvent,
+    },
+    gamepad::GamepadManager,
+    headless::{EventInjector, ScheduledEvents},
+    input,
+    monitor::Monitor,
+    window::{WindowHandle, WindowId},
+};
+use nazara_core::{
+    application::Application,
+    config::Config,
+    errors::{NazaraResult, PlatformError},
+    nz_warning,
+    signal::Signal,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    time::{Duration, Instant},
+};
 use winit::{
-    event::{Event, WindowEvent},
+    event::{DeviceEvent, ElementState, Event, WindowEvent as W_WindowEvent},
     event_loop::{ControlFlow, EventLoop},
-    window::{Window, WindowId},
+    window::WindowId as W_WindowId,
 };
 
 pub(crate) struct WindowCallbacks {
-    pub(crate) render_callback: Box<dyn FnMut() -> ()>,
+    pub(crate) on_render: Signal<()>,
+    pub(crate) on_event: Signal<WindowEvent>,
+    pub(crate) on_key_pressed: Signal<KeyEvent>,
+    pub(crate) on_key_released: Signal<KeyEvent>,
+    pub(crate) on_text_entered: Signal<TextEvent>,
+    pub(crate) on_mouse_button_pressed: Signal<MouseButtonEvent>,
+    pub(crate) on_mouse_button_released: Signal<MouseButtonEvent>,
+    pub(crate) on_mouse_moved: Signal<MouseMoveEvent>,
+    pub(crate) on_raw_mouse_motion: Signal<RawMotionEvent>,
+    pub(crate) on_mouse_wheel_moved: Signal<MouseWheelEvent>,
+    pub(crate) on_mouse_entered: Signal<()>,
+    pub(crate) on_mouse_left: Signal<()>,
+    pub(crate) on_close_requested: Signal<()>,
+    pub(crate) on_resized: Signal<ResizeEvent>,
+    pub(crate) on_moved: Signal<MoveEvent>,
+    pub(crate) on_focus_gained: Signal<()>,
+    pub(crate) on_focus_lost: Signal<()>,
+    pub(crate) on_minimized: Signal<()>,
+    pub(crate) on_restored: Signal<()>,
+    pub(crate) on_scale_factor_changed: Signal<ScaleFactorEvent>,
+    // Dropping the handle closes the window
+    handle: RefCell<Option<WindowHandle>>,
+    close_on_request: Cell<bool>,
+    minimized: Cell<bool>,
+    focused: Cell<bool>,
+    // The platform reports the cursor being moved back while it's locked
+    cursor_locked: Cell<bool>,
+    // Input state of the window, completing the events reported by the platform
+    cursor_position: Cell<Option<(f64, f64)>>,
+    pressed_keys: RefCell<HashSet<u32>>,
+}
+
+impl WindowCallbacks {
+    fn new(handle: WindowHandle) -> WindowCallbacks {
+        WindowCallbacks {
+            on_render: Signal::new(),
+            on_event: Signal::new(),
+            on_key_pressed: Signal::new(),
+            on_key_released: Signal::new(),
+            on_text_entered: Signal::new(),
+            on_mouse_button_pressed: Signal::new(),
+            on_mouse_button_released: Signal::new(),
+            on_mouse_moved: Signal::new(),
+            on_raw_mouse_motion: Signal::new(),
+            on_mouse_wheel_moved: Signal::new(),
+            on_mouse_entered: Signal::new(),
+            on_mouse_left: Signal::new(),
+            on_close_requested: Signal::new(),
+            on_resized: Signal::new(),
+            on_moved: Signal::new(),
+            on_focus_gained: Signal::new(),
+            on_focus_lost: Signal::new(),
+            on_minimized: Signal::new(),
+            on_restored: Signal::new(),
+            on_scale_factor_changed: Signal::new(),
+            handle: RefCell::new(Some(handle)),
+            close_on_request: Cell::new(true),
+            minimized: Cell::new(false),
+            focused: Cell::new(false),
+            cursor_locked: Cell::new(false),
+            cursor_position: Cell::new(None),
+            pressed_keys: RefCell::new(HashSet::new()),
+        }
+    }
+
+    pub(crate) fn close(&self) {
+        self.handle.borrow_mut().take();
+    }
+
+    /// Run a function with the handle of the window, unless it was closed
+    pub(crate) fn with_handle<R, F: FnOnce(&WindowHandle) -> R>(&self, func: F) -> Option<R> {
+        self.handle.borrow().as_ref().map(func)
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.handle.borrow().is_some()
+    }
+
+    pub(crate) fn set_close_on_request(&self, close: bool) {
+        self.close_on_request.set(close);
+    }
+
+    /// Return true if the window received the focus and didn't lose it since
+    pub(crate) fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    pub(crate) fn set_cursor_locked(&self, locked: bool) {
+        self.cursor_locked.set(locked);
+    }
+
+    /// Emit an event on the generic signal, then on the signal dedicated to its type
+    ///
+    /// The window is closed after a close request was emitted, unless this was disabled.
+    pub(crate) fn dispatch(&self, event: &WindowEvent) {
+        self.on_event.emit(event);
+
+        match event {
+            WindowEvent::KeyPressed(e) => self.on_key_pressed.emit(e),
+            WindowEvent::KeyReleased(e) => self.on_key_released.emit(e),
+            WindowEvent::TextEntered(e) => self.on_text_entered.emit(e),
+            WindowEvent::MouseButtonPressed(e) => self.on_mouse_button_pressed.emit(e),
+            WindowEvent::MouseButtonReleased(e) => self.on_mouse_button_released.emit(e),
+            WindowEvent::MouseMoved(e) => self.on_mouse_moved.emit(e),
+            WindowEvent::RawMouseMotion(e) => self.on_raw_mouse_motion.emit(e),
+            WindowEvent::MouseWheelMoved(e) => self.on_mouse_wheel_moved.emit(e),
+            WindowEvent::MouseEntered => self.on_mouse_entered.emit(&()),
+            WindowEvent::MouseLeft => self.on_mouse_left.emit(&()),
+            WindowEvent::CloseRequested => {
+                self.on_close_requested.emit(&());
+                if self.close_on_request.get() {
+                    self.close();
+                }
+            }
+            WindowEvent::Resized(e) => self.on_resized.emit(e),
+            WindowEvent::Moved(e) => self.on_moved.emit(e),
+            WindowEvent::FocusGained => {
+                self.focused.set(true);
+                self.on_focus_gained.emit(&());
+            }
+            WindowEvent::FocusLost => {
+                self.focused.set(false);
+                self.on_focus_lost.emit(&());
+            }
+            WindowEvent::Minimized => self.on_minimized.emit(&()),
+            WindowEvent::Restored => self.on_restored.emit(&()),
+            WindowEvent::ScaleFactorChanged(e) => self.on_scale_factor_changed.emit(e),
+        }
+    }
+
+    /// Convert a winit event to a Nazara event, updating the input state of the window
+    fn translate(&self, event: &W_WindowEvent, modifiers: KeyModifiers) -> Option<WindowEvent> {
+        match event {
+            W_WindowEvent::KeyboardInput { input, .. } => {
+                let key_event = |repeated| KeyEvent {
+                    scancode: input.scancode,
+                    key: input.virtual_keycode.map_or(Key::Undefined, Key::from),
+                    modifiers,
+                    repeated,
+                };
+
+                let mut pressed_keys = self.pressed_keys.borrow_mut();
+                Some(match input.state {
+                    ElementState::Pressed => {
+                        let repeated = !pressed_keys.insert(input.scancode);
+                        WindowEvent::KeyPressed(key_event(repeated))
+                    }
+                    ElementState::Released => {
+                        pressed_keys.remove(&input.scancode);
+                        WindowEvent::KeyReleased(key_event(false))
+                    }
+                })
+            }
+            W_WindowEvent::ReceivedCharacter(character) => {
+                Some(WindowEvent::TextEntered(TextEvent {
+                    character: *character,
+                }))
+            }
+            W_WindowEvent::MouseInput { state, button, .. } => {
+                let (x, y) = self.cursor_position.get().unwrap_or((0.0, 0.0));
+                let button_event = MouseButtonEvent {
+                    button: (*button).into(),
+                    x,
+                    y,
+                    modifiers,
+                };
+
+                Some(match state {
+                    ElementState::Pressed => WindowEvent::MouseButtonPressed(button_event),
+                    ElementState::Released => WindowEvent::MouseButtonReleased(button_event),
+                })
+            }
+            W_WindowEvent::CursorMoved { position, .. } => {
+                let (x, y) = (f64::from(position.x), f64::from(position.y));
+                let (previous_x, previous_y) = self.cursor_position.get().unwrap_or((x, y));
+                self.cursor_position.set(Some((x, y)));
+                if self.cursor_locked.get() {
+                    return None;
+                }
+
+                Some(WindowEvent::MouseMoved(MouseMoveEvent {
+                    x,
+                    y,
+                    delta_x: x - previous_x,
+                    delta_y: y - previous_y,
+                }))
+            }
+            W_WindowEvent::MouseWheel { delta, .. } => {
+                Some(WindowEvent::MouseWheelMoved(MouseWheelEvent {
+                    delta: (*delta).into(),
+                    modifiers,
+                }))
+            }
+            W_WindowEvent::CursorEntered { .. } => Some(WindowEvent::MouseEntered),
+            W_WindowEvent::CursorLeft { .. } => {
+                self.cursor_position.set(None);
+                Some(WindowEvent::MouseLeft)
+            }
+            W_WindowEvent::CloseRequested => Some(WindowEvent::CloseRequested),
+            W_WindowEvent::Resized(size) => {
+                // Platforms report minimization as the window shrinking to nothing
+                let minimized = size.width == 0 && size.height == 0;
+                if minimized == self.minimized.replace(minimized) {
+                    if minimized {
+                        return None;
+                    }
+
+                    Some(WindowEvent::Resized(ResizeEvent {
+                        width: size.width,
+                        height: size.height,
+                    }))
+                } else if minimized {
+                    Some(WindowEvent::Minimized)
+                } else {
+                    Some(WindowEvent::Restored)
+                }
+            }
+            W_WindowEvent::Moved(position) => Some(WindowEvent::Moved(MoveEvent {
+                // Positions left or above of the primary monitor are negative, winit reports
+                // them as unsigned
+                x: position.x as i32,
+                y: position.y as i32,
+            })),
+            W_WindowEvent::Focused(true) => Some(WindowEvent::FocusGained),
+            W_WindowEvent::Focused(false) => {
+                // Keys released while the window is not focused are never reported
+                self.pressed_keys.borrow_mut().clear();
+                Some(WindowEvent::FocusLost)
+            }
+            W_WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => Some(WindowEvent::ScaleFactorChanged(ScaleFactorEvent {
+                scale_factor: *scale_factor,
+                width: new_inner_size.width,
+                height: new_inner_size.height,
+            })),
+            _ => None,
+        }
+    }
 }
 
 pub struct WindowApplication {
     app: Application,
+    backend: Backend,
     event_loop: Option<EventLoop<()>>,
-    window_callbacks: HashMap<WindowId, Rc<RefCell<WindowCallbacks>>>,
+    // Why the event loop couldn't be created
+    display_error: Option<String>,
+    windows: HashMap<WindowId, Rc<WindowCallbacks>>,
+    native_ids: HashMap<W_WindowId, WindowId>,
+    next_window_id: u64,
+    scheduled_events: Rc<ScheduledEvents>,
+    modifiers: KeyModifiers,
+    quit_on_last_window_closed: bool,
+    frame_count: u64,
+    start_time: Instant,
+    // Frames per second of simulated time, for the headless backend
+    headless_frame_rate: u32,
+    gamepads: GamepadManager,
+    clipboard: Clipboard,
 }
 
 impl WindowApplication {
     pub fn new() -> WindowApplication {
+        WindowApplication::with_config(Config::new())
+    }
+
+    /// Create an application configured by a [`Config`]
+    ///
+    /// Besides the options declared by [`Application::with_config`], the default window
+    /// settings used by [`WindowBuilder`](crate::window::WindowBuilder) are declared:
+    /// `window.title`, `window.width` and `window.height`. The headless backend is used if
+    /// `window.headless` is true, running `window.headless_frame_rate` frames per second of
+    /// simulated time.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration, which may contain values for options not declared yet
+    pub fn with_config(mut config: Config) -> WindowApplication {
+        declare_options(&mut config);
+
+        let backend = if config.get("window.headless").unwrap_or(false) {
+            Backend::Headless
+        } else {
+            Backend::Native
+        };
+
+        WindowApplication::with_backend(config, backend)
+    }
+
+    /// Create an application using a given backend, whatever the configuration says
+    ///
+    /// # Arguments
+    /// * `config` - Configuration, which may contain values for options not declared yet
+    /// * `backend` - Implementation of the windows
+    pub fn with_backend(mut config: Config, backend: Backend) -> WindowApplication {
+        declare_options(&mut config);
+
+        let (event_loop, display_error) = match backend {
+            Backend::Native => match create_event_loop() {
+                Ok(event_loop) => (Some(event_loop), None),
+                Err(reason) => {
+                    nz_warning!(
+                        "platform",
+                        "no display available ({}), windows can't be created",
+                        reason
+                    );
+                    (None, Some(reason))
+                }
+            },
+            Backend::Headless => (None, None),
+        };
+        let clipboard = match backend {
+            Backend::Native if event_loop.is_some() => Clipboard::new(),
+            _ => Clipboard::new_in_memory(),
+        };
+        let headless_frame_rate = config.get("window.headless_frame_rate").unwrap_or(60);
+
+        // Headless applications only see the gamepads of the backends they add
+        let mut gamepads = GamepadManager::new();
+        #[cfg(target_os = "linux")]
+        {
+            if backend == Backend::Native {
+                gamepads.add_backend(Box::new(crate::gamepad::evdev::EvdevBackend::new()));
+            }
+        }
+
         WindowApplication {
-            app: Application::new(),
-            event_loop: Some(EventLoop::new()),
-            window_callbacks: HashMap::new(),
+            app: Application::with_config(config),
+            backend,
+            event_loop,
+            display_error,
+            windows: HashMap::new(),
+            native_ids: HashMap::new(),
+            next_window_id: 0,
+            scheduled_events: Rc::new(ScheduledEvents::default()),
+            modifiers: KeyModifiers::default(),
+            quit_on_last_window_closed: true,
+            frame_count: 0,
+            start_time: Instant::now(),
+            headless_frame_rate,
+            gamepads,
+            clipboard,
         }
     }
 
+    /// Run the application until it quits
+    ///
+    /// Without a display, no window can be created to keep the application running: it
+    /// returns immediately. The headless backend runs its frames as fast as possible, and
+    /// returns after the first one if no window was created.
+    ///
+    /// ```
+    /// use nazara_core::config::Config;
+    /// use nazara_platform::enums::Backend;
+    /// use nazara_platform::window_application::WindowApplication;
+    ///
+    /// // Nothing could ever close this application
+    /// let app = WindowApplication::with_backend(Config::new(), Backend::Headless);
+    /// app.run();
+    /// ```
     pub fn run(mut self) {
-        let event_loop = self.event_loop.take().unwrap();
+        let event_loop = match self.event_loop.take() {
+            Some(event_loop) => event_loop,
+            None if self.display_error.is_some() => {
+                nz_warning!(
+                    "platform",
+                    "no display available, the application doesn't run"
+                );
+                return;
+            }
+            None => {
+                // Closing the last window is the only way for the platform to stop the frames
+                while self.run_frame() && self.next_window_id > 0 {}
+                return;
+            }
+        };
 
-        event_loop.run(move |event, window_target, control_flow| match event {
-            Event::MainEventsCleared => {
-                if !self.app.execute() {
-                    *control_flow = ControlFlow::Exit;
+        event_loop.run(move |event, _window_target, control_flow| match event {
+            Event::MainEventsCleared if !self.run_frame() => {
+                *control_flow = ControlFlow::Exit;
+            }
+            Event::RedrawRequested(native_id) => {
+                if let Some(callbacks) = self.get_native_window(native_id) {
+                    callbacks.on_render.emit(&());
                 }
             }
-            Event::RedrawRequested(win_id) => {
-                if let Some(callback) = self.window_callbacks.get(&win_id) {
-                    let callback = &mut callback.borrow_mut().render_callback;
-                    callback();
+            Event::WindowEvent { window_id, event } => {
+                if let Some(callbacks) = self.get_native_window(window_id) {
+                    if let Some(event) = callbacks.translate(&event, self.modifiers) {
+                        input::inject_event(&event);
+                        callbacks.dispatch(&event);
+                    }
+                }
+            }
+            Event::DeviceEvent {
+                event: DeviceEvent::ModifiersChanged(state),
+                ..
+            } => {
+                self.modifiers = state.into();
+            }
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                // Raw motion isn't tied to a window, it's given to the one having the focus
+                let event = WindowEvent::RawMouseMotion(RawMotionEvent {
+                    delta_x: delta.0,
+                    delta_y: delta.1,
+                });
+                for callbacks in self.windows.values().filter(|c| c.is_focused()) {
+                    input::inject_event(&event);
+                    callbacks.dispatch(&event);
                 }
             }
             _ => (),
         });
     }
 
-    pub(crate) fn get_event_loop(&self) -> &EventLoop<()> {
-        assert!(self.event_loop.is_some());
+    /// Run a single frame of a headless application, return false once it should close
+    ///
+    /// The native backend can only be run with [`run`](WindowApplication::run).
+    pub fn step(&mut self) -> NazaraResult<bool> {
+        match self.backend {
+            Backend::Native => Err(PlatformError::Unsupported(String::from(
+                "the native backend can't be stepped",
+            ))
+            .into()),
+            Backend::Headless => Ok(self.run_frame()),
+        }
+    }
+
+    /// Return the object scheduling events for the windows of the application
+    ///
+    /// The events are delivered by both backends, as if the platform sent them.
+    pub fn get_event_injector(&self) -> EventInjector {
+        EventInjector::new(self.scheduled_events.clone())
+    }
+
+    pub fn get_backend(&self) -> Backend {
+        self.backend
+    }
 
-        self.event_loop.as_ref().unwrap()
+    /// Return the number of frames run
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
     }
 
-    pub(crate) fn register_window(&mut self, id: WindowId) -> Rc<RefCell<WindowCallbacks>> {
-        let callbacks = WindowCallbacks {
-            render_callback: Box::new(|| {}),
+    /// Return the time elapsed since the application was created, simulated by the headless
+    /// backend
+    pub fn get_elapsed_time(&self) -> Duration {
+        match self.backend {
+            Backend::Native => self.start_time.elapsed(),
+            Backend::Headless => Duration::from_secs(self.frame_count) / self.headless_frame_rate,
+        }
+    }
+
+    /// Return the gamepads, updated at the beginning of each frame
+    pub fn get_gamepads(&mut self) -> &mut GamepadManager {
+        &mut self.gamepads
+    }
+
+    /// Return the monitors connected to the computer, a single simulated one with the headless
+    /// backend, none if no display is available
+    pub fn get_monitors(&self) -> Vec<Monitor> {
+        if self.is_headless() {
+            return vec![Monitor::headless()];
+        }
+
+        match &self.event_loop {
+            Some(event_loop) => {
+                let primary = event_loop.primary_monitor();
+                event_loop
+                    .available_monitors()
+                    .map(|handle| Monitor::from_native(handle, &primary))
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Return the main monitor of the desktop, `None` if no monitor can be found
+    pub fn get_primary_monitor(&self) -> Option<Monitor> {
+        self.get_monitors().into_iter().find(Monitor::is_primary)
+    }
+
+    /// Return the clipboard, which only lives in memory with the headless backend
+    pub fn get_clipboard(&mut self) -> &mut Clipboard {
+        &mut self.clipboard
+    }
+
+    /// Choose whether the application quits once all its windows are closed, which is the
+    /// default
+    ///
+    /// # Arguments
+    /// * `quit` - False to keep the application running without windows
+    pub fn set_quit_on_last_window_closed(&mut self, quit: bool) {
+        self.quit_on_last_window_closed = quit;
+    }
+
+    /// Return the [`Config`] holding the options of the application and of the engine
+    pub fn get_config(&mut self) -> &mut Config {
+        self.app.get_config()
+    }
+
+    /// Return true if windows can be created: the windowing system could be reached, or the
+    /// backend is headless
+    ///
+    /// ```
+    /// use nazara_platform::window::Window;
+    /// use nazara_platform::window_application::WindowApplication;
+    ///
+    /// let mut app = WindowApplication::new();
+    /// if !app.has_display() {
+    ///     assert!(Window::new(&mut app).is_err());
+    /// }
+    /// ```
+    pub fn has_display(&self) -> bool {
+        self.display_error.is_none()
+    }
+
+    pub(crate) fn is_headless(&self) -> bool {
+        self.backend == Backend::Headless
+    }
+
+    pub(crate) fn get_event_loop(&self) -> NazaraResult<&EventLoop<()>> {
+        match (&self.event_loop, &self.display_error) {
+            (Some(event_loop), _) => Ok(event_loop),
+            (None, Some(reason)) => Err(PlatformError::NoDisplay(reason.clone()).into()),
+            (None, None) => Err(PlatformError::EventLoopUnavailable.into()),
+        }
+    }
+
+    pub(crate) fn get_scheduled_events(&self) -> Rc<ScheduledEvents> {
+        self.scheduled_events.clone()
+    }
+
+    pub(crate) fn allocate_window_id(&mut self) -> WindowId {
+        self.next_window_id += 1;
+
+        WindowId(self.next_window_id)
+    }
+
+    pub(crate) fn register_window(
+        &mut self,
+        id: WindowId,
+        handle: WindowHandle,
+    ) -> Rc<WindowCallbacks> {
+        if let WindowHandle::Native(native) = &handle {
+            self.native_ids.insert(native.id(), id);
+        }
+
+        let callbacks = Rc::new(WindowCallbacks::new(handle));
+        self.windows.insert(id, callbacks.clone());
+
+        callbacks
+    }
+
+    fn get_native_window(&self, native_id: W_WindowId) -> Option<&Rc<WindowCallbacks>> {
+        self.native_ids
+            .get(&native_id)
+            .and_then(|id| self.windows.get(id))
+    }
+
+    /// Deliver the scheduled events, update the gamepads and the clipboard, execute the
+    /// application and update the input state
+    fn run_frame(&mut self) -> bool {
+        let time = self.get_elapsed_time();
+        for (id, event) in self.scheduled_events.take_due(self.frame_count, time) {
+            if let Some(callbacks) = self.windows.get(&id) {
+                callbacks.with_handle(|handle| {
+                    if let WindowHandle::Headless(handle) = handle {
+                        handle.apply(&event);
+                    }
+                });
+                input::inject_event(&event);
+                callbacks.dispatch(&event);
+            }
+        }
+
+        self.gamepads.update();
+        self.clipboard.update();
+
+        self.remove_closed_windows();
+        let running = self.app.execute();
+
+        // Headless windows are redrawn continuously
+        if self.is_headless() {
+            for callbacks in self.windows.values() {
+                callbacks.on_render.emit(&());
+            }
+        }
+
+        input::update();
+        self.frame_count += 1;
+
+        running
+    }
+
+    fn remove_closed_windows(&mut self) {
+        let window_count = self.windows.len();
+        self.windows.retain(|_, callbacks| callbacks.is_open());
+
+        let windows = &self.windows;
+        self.native_ids.retain(|_, id| windows.contains_key(id));
+
+        if self.quit_on_last_window_closed && window_count > 0 && self.windows.is_empty() {
+            self.app.quit();
+        }
+    }
+}
+
+impl Default for WindowApplication {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create the event loop once a windowing system is known to be reachable, winit panics
+/// when there is none
+fn create_event_loop() -> Result<EventLoop<()>, String> {
+    find_display()?;
+
+    Ok(EventLoop::new())
+}
+
+/// Check a Wayland compositor or an X server can be reached
+#[cfg(target_os = "linux")]
+fn find_display() -> Result<(), String> {
+    use std::{env, path::Path, ptr};
+
+    if let Some(display) = env::var_os("WAYLAND_DISPLAY") {
+        // Relative socket names are in the runtime directory
+        let socket = match env::var_os("XDG_RUNTIME_DIR") {
+            Some(dir) => Path::new(&dir).join(&display),
+            None => Path::new(&display).to_path_buf(),
         };
+        if socket.exists() {
+            return Ok(());
+        }
+    }
 
-        let callback_ref = RefCell::new(callbacks);
-        let callback_rc = Rc::new(callback_ref);
-        self.window_callbacks.insert(id, callback_rc.clone());
+    if env::var_os("DISPLAY").is_none() {
+        return Err(String::from("neither WAYLAND_DISPLAY nor DISPLAY is set"));
+    }
 
-        callback_rc
+    let xlib = x11_dl::xlib::Xlib::open().map_err(|err| err.to_string())?;
+    // Safety: the connection is closed right after being opened
+    unsafe {
+        let display = (xlib.XOpenDisplay)(ptr::null());
+        if display.is_null() {
+            return Err(String::from("can't connect to the X server"));
+        }
+        (xlib.XCloseDisplay)(display);
     }
+
+    Ok(())
+}
+
+/// Other platforms always have a display
+#[cfg(not(target_os = "linux"))]
+fn find_display() -> Result<(), String> {
+    Ok(())
+}
+
+fn declare_options(config: &mut Config) {
+    config
+        .declare("window.title", "untitled Nazara window")
+        .with_description("Default title of the windows");
+    config
+        .declare("window.width", 800)
+        .with_range(1.0, 16384.0)
+        .with_description("Default width of the windows");
+    config
+        .declare("window.height", 600)
+        .with_range(1.0, 16384.0)
+        .with_description("Default height of the windows");
+    config
+        .declare("window.headless", false)
+        .with_description("Simulate the windows, for automated tests without a display");
+    config
+        .declare("window.headless_frame_rate", 60)
+        .with_range(1.0, 1000.0)
+        .with_description("Frames per second of simulated time of the headless backend");
 }
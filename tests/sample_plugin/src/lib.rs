@@ -0,0 +1,50 @@
+//! Plugin used to test plugin loading, providing a module and a loader for `.nzraw` images
+//! (two little-endian u16 for width and height followed by RGBA8 pixels)
+
+use nazara_core::{
+    application::{Module, PluginRegistrar},
+    enums::PixelFormatType,
+    errors::{ImageError, NazaraError, NazaraResult},
+    image::{Image, ImageFormatLoader},
+};
+
+struct RawImageLoader;
+
+impl ImageFormatLoader for RawImageLoader {
+    fn supports_extension(&self, extension: &str) -> bool {
+        extension == "nzraw"
+    }
+
+    fn load(&self, data: &[u8]) -> NazaraResult<Image> {
+        if data.len() < 4 {
+            return Err(NazaraError::from(ImageError::NotEnoughData));
+        }
+
+        let width = u16::from_le_bytes([data[0], data[1]]) as usize;
+        let height = u16::from_le_bytes([data[2], data[3]]) as usize;
+        let pixels = &data[4..];
+        if pixels.len() != width * height * 4 {
+            return Err(NazaraError::from(ImageError::NotEnoughData));
+        }
+
+        let mut image = Image::new_2d(PixelFormatType::RGBA8, width, height);
+        image.update_mipmap_content(0, pixels.to_vec());
+
+        Ok(image)
+    }
+}
+
+struct SampleModule;
+
+impl Module for SampleModule {
+    fn get_name(&self) -> &str {
+        "sample"
+    }
+}
+
+fn register(registrar: &mut dyn PluginRegistrar) {
+    registrar.register_image_loader(Box::new(RawImageLoader));
+    registrar.register_module(Box::new(SampleModule));
+}
+
+nazara_core::nazara_plugin!("sample_plugin", register);
@@ -0,0 +1,62 @@
+use std::{env, path::PathBuf};
+
+use nazara_core::{application::Application, enums::PixelFormatType, image::ImageLoader};
+
+fn plugin_path() -> PathBuf {
+    let name = format!(
+        "{}sample_plugin{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    );
+
+    // Test executables are built in target/<profile>/deps, next to the library
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    [deps.join(&name), deps.parent().unwrap().join(&name)]
+        .iter()
+        .find(|path| path.exists())
+        .cloned()
+        .expect("the sample plugin library is built before its tests")
+}
+
+#[test]
+fn load_and_unload() {
+    let image_path = env::temp_dir().join("nazara_sample_plugin.nzraw");
+    let mut content = vec![2, 0, 1, 0];
+    content.extend_from_slice(&[255, 0, 0, 255, 0, 0, 255, 255]);
+    std::fs::write(&image_path, content).unwrap();
+
+    let mut app = Application::new();
+    assert!(ImageLoader::load_from_file(&image_path).is_err());
+
+    let plugin = app.load_plugin(&plugin_path()).unwrap();
+    assert_eq!(app.get_plugin_name(plugin), Some("sample_plugin"));
+    assert_eq!(app.get_module_names(), vec!["sample"]);
+    app.execute();
+
+    let image = ImageLoader::load_from_file(&image_path).unwrap();
+    assert_eq!(image.get_pixel_format(), PixelFormatType::RGBA8);
+    assert_eq!(image.get_dims().x, 2);
+    assert_eq!(image.get_mipmap_content(0)[6], 255);
+    drop(image);
+
+    app.unload_plugin(plugin).unwrap();
+    assert!(app.get_plugins().is_empty());
+    assert!(app.get_module_names().is_empty());
+    assert!(ImageLoader::load_from_file(&image_path).is_err());
+    assert!(app.unload_plugin(plugin).is_err());
+
+    // Plugins still loaded are unloaded with the application
+    app.load_plugin(&plugin_path()).unwrap();
+    drop(app);
+    assert!(ImageLoader::load_from_file(&image_path).is_err());
+
+    std::fs::remove_file(&image_path).unwrap();
+}
+
+#[test]
+fn reject_invalid_library() {
+    let mut app = Application::new();
+    assert!(app
+        .load_plugin(&env::temp_dir().join("missing_plugin.so"))
+        .is_err());
+}
@@ -1,17 +1,57 @@
-use nazara_platform::{
-    window::{Window, WindowBuilder},
-    window_application::WindowApplication,
-};
+use nazara_core::{config::Config, nz_error, nz_info};
+use nazara_platform::{window::WindowBuilder, window_application::WindowApplication};
 
 fn main() {
-    let mut app = WindowApplication::new();
+    // Window size can be changed with --window.width=X --window.height=Y
+    let mut config = Config::new();
+    config.apply_args(std::env::args()).unwrap();
+
+    let mut app = WindowApplication::with_config(config);
 
     let mut window = WindowBuilder::new()
         .with_title("Hello Nazara")
-        .build(&mut app);
+        .build(&mut app)
+        .unwrap_or_else(|err| {
+            nz_error!("simple_window", "{}", err);
+            std::process::exit(1);
+        });
 
     window.set_render_callback(|| {
-        println!("Render callback!");
+        nz_info!("simple_window", "Render callback!");
+    });
+
+    window.on_key_pressed().connect(|event| {
+        nz_info!("simple_window", "Key pressed: {:?}", event.key);
+    });
+    window.on_text_entered().connect(|event| {
+        nz_info!("simple_window", "Text entered: {}", event.character);
+    });
+    window.on_mouse_button_pressed().connect(|event| {
+        nz_info!(
+            "simple_window",
+            "{:?} button pressed at {}x{}",
+            event.button,
+            event.x,
+            event.y
+        );
+    });
+
+    window.on_resized().connect(|event| {
+        nz_info!(
+            "simple_window",
+            "Window resized to {}x{}",
+            event.width,
+            event.height
+        );
+    });
+    window.on_focus_lost().connect(|_| {
+        nz_info!("simple_window", "Focus lost");
+    });
+    window.on_close_requested().connect(|_| {
+        nz_info!(
+            "simple_window",
+            "Closing, the application quits with its last window"
+        );
     });
 
     app.run();
@@ -0,0 +1,111 @@
+use std::{cell::RefCell, rc::Rc};
+
+use nazara_core::config::Config;
+use nazara_platform::{
+    enums::{
+        Backend, Key, KeyEvent, KeyModifiers, MouseButton, MouseButtonEvent, ResizeEvent,
+        TextEvent, WindowEvent,
+    },
+    keyboard::Keyboard,
+    mouse::Mouse,
+    window::WindowBuilder,
+    window_application::WindowApplication,
+};
+
+fn headless_app() -> WindowApplication {
+    let mut config = Config::new();
+    config
+        .apply_args(vec![String::from("--window.headless=true")])
+        .unwrap();
+
+    WindowApplication::with_config(config)
+}
+
+#[test]
+fn input_events() {
+    let mut app = headless_app();
+    assert_eq!(app.get_backend(), Backend::Headless);
+
+    let window = WindowBuilder::new()
+        .with_title("Hello Nazara")
+        .build(&mut app)
+        .unwrap();
+
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let text = received.clone();
+    window.on_text_entered().connect(move |event| {
+        text.borrow_mut().push(event.character);
+    });
+
+    let key_event = KeyEvent {
+        scancode: 30,
+        key: Key::A,
+        modifiers: KeyModifiers::default(),
+        repeated: false,
+    };
+    let button_event = MouseButtonEvent {
+        button: MouseButton::Left,
+        x: 10.0,
+        y: 20.0,
+        modifiers: KeyModifiers::default(),
+    };
+
+    let injector = app.get_event_injector();
+    injector.push(window.get_id(), WindowEvent::KeyPressed(key_event));
+    injector.push(
+        window.get_id(),
+        WindowEvent::TextEntered(TextEvent { character: 'a' }),
+    );
+    injector.push_at_frame(1, window.get_id(), WindowEvent::KeyReleased(key_event));
+    injector.push_at_frame(
+        1,
+        window.get_id(),
+        WindowEvent::MouseButtonPressed(button_event),
+    );
+
+    assert!(app.step().unwrap());
+    assert!(Keyboard::is_key_pressed(Key::A));
+    assert_eq!(*received.borrow(), vec!['a']);
+
+    assert!(app.step().unwrap());
+    assert!(!Keyboard::is_key_pressed(Key::A));
+    assert!(Mouse::is_button_pressed(MouseButton::Left));
+    assert_eq!(Mouse::get_position(), Some((10.0, 20.0)));
+    assert_eq!(injector.get_pending_count(), 0);
+}
+
+#[test]
+fn window_lifecycle() {
+    let mut app = headless_app();
+    let mut window = WindowBuilder::new()
+        .with_size(640, 480)
+        .with_position(100, 50)
+        .build(&mut app)
+        .unwrap();
+    assert_eq!(window.get_size(), (640, 480));
+    assert_eq!(window.get_position(), Some((100, 50)));
+
+    let resizes = Rc::new(RefCell::new(Vec::new()));
+    let events = resizes.clone();
+    window.on_resized().connect(move |event| {
+        events.borrow_mut().push(*event);
+    });
+
+    // Changes made by the application are reported at the next frame, like real platforms do
+    window.set_size(1024, 768);
+    assert!(resizes.borrow().is_empty());
+    assert!(app.step().unwrap());
+    assert_eq!(
+        *resizes.borrow(),
+        vec![ResizeEvent {
+            width: 1024,
+            height: 768
+        }]
+    );
+
+    // Closing the last window quits the application
+    app.get_event_injector()
+        .push(window.get_id(), WindowEvent::CloseRequested);
+    assert!(!app.step().unwrap());
+    assert!(!window.is_open());
+}